@@ -1,6 +1,6 @@
 use anyhow::Result;
 use crate::db::Database;
-use crate::models::{Word, LearningLog};
+use crate::models::{Word, LearningLog, ReviewDirection};
 use crate::sm2;
 
 pub enum CurrentScreen {
@@ -74,7 +74,9 @@ impl App {
     }
 
     pub fn start_review(&mut self) -> Result<()> {
+        // Oldest due first: cards are popped from the end
         self.review_queue = self.db.get_due_reviews()?;
+        self.review_queue.reverse();
         self.total_review_count = self.review_queue.len();
         self.completed_review_count = 0;
         self.show_completion_message = false;
@@ -130,11 +132,13 @@ impl App {
     pub fn submit_review(&mut self, quality: u8) -> Result<()> {
         if let Some((word, mut log)) = self.current_review_item.take() {
             let word_id = word.id.unwrap();
-            sm2::process_review(&mut log, quality);
+            let mastered_review = self.db.get_mastered_review()?;
+            let mastery = self.db.get_mastery_rule()?;
+            sm2::process_review(&mut log, quality, mastered_review, mastery);
             self.db.update_log(&log)?;
 
             // Record review in history
-            self.db.add_review_history(word_id, quality, &log)?;
+            self.db.add_review_history(word_id, quality, &log, None, Some(ReviewDirection::EnToZh), "v1")?;
 
             // Refresh statistics
             self.refresh_stats();
@@ -197,3 +201,17 @@ impl App {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn start_review_presents_most_overdue_first() {
+        let db = Database::with_due_words(&[("recent", 1), ("oldest", 72), ("middle", 24)]).unwrap();
+        let mut app = App::new(db);
+        app.start_review().unwrap();
+        let first = app.current_review_item.as_ref().map(|(word, _)| word.spelling.as_str());
+        assert_eq!(first, Some("oldest"));
+    }
+}
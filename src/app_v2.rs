@@ -2,12 +2,16 @@ use crate::components::*;
 use crate::components::{
     dashboard::DashboardComponent, dictionary::DictionaryComponent, history::HistoryComponent,
     review::ReviewComponent, statistics::StatisticsComponent, wordbook::WordbookComponent,
-    favorites::FavoritesComponent, settings::SettingsComponent,
+    favorites::FavoritesComponent, settings::SettingsComponent, search::SearchComponent,
+    summary::SummaryComponent,
 };
 use crate::db::Database;
+use crate::i18n::{self, t, t_n};
+use crate::logger;
+use crate::models::{DictEnterAction, SearchFocusKey, SessionComplete, WordOrder};
 use crate::theme::Theme;
 use anyhow::Result;
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{layout::Rect, Frame};
 
 pub struct AppV2 {
@@ -20,12 +24,42 @@ pub struct AppV2 {
     wordbook: Option<WordbookComponent>,
     favorites: Option<FavoritesComponent>,
     settings: Option<SettingsComponent>,
+    search: Option<SearchComponent>,
+    summary: Option<SummaryComponent>,
     notification: Option<(String, std::time::Instant)>, // (message, timestamp)
+    glance: TodayGlance,
+    review_origin: Option<Screen>, // Screen a single-word review returns to
+}
+
+/// Numbers shown on the glance line under the tabs on every screen
+#[derive(Debug, Default)]
+struct TodayGlance {
+    due: i64,
+    completed: i64,
+    goal: i64,
+    streak: i64,
+}
+
+impl TodayGlance {
+    fn load(db: &Database) -> Self {
+        Self {
+            due: db.get_stats().map(|(_, _, due)| due).unwrap_or(0),
+            completed: db.get_today_completed_count().unwrap_or(0),
+            goal: db.get_daily_goal().unwrap_or(20),
+            streak: db.get_checkin_streak().unwrap_or(0),
+        }
+    }
 }
 
 impl AppV2 {
-    pub fn new(db: Database) -> Result<Self> {
-        Ok(Self {
+    /// `start_in_review` (--review or the setting): open a due review right away when anything is due
+    pub fn new(db: Database, start_in_review: bool) -> Result<Self> {
+        i18n::set_lang(db.get_language()?);
+        Theme::set_emphasis(db.get_display_emphasis()?);
+        Theme::set_border_density(db.get_border_density()?);
+        let glance = TodayGlance::load(&db);
+        let start_in_review = start_in_review || db.get_start_in_review()?;
+        let mut app = Self {
             current_screen: Screen::Dashboard,
             dashboard: DashboardComponent::new(db),
             review: None,
@@ -35,20 +69,127 @@ impl AppV2 {
             wordbook: None,
             favorites: None,
             settings: None,
+            search: None,
+            summary: None,
             notification: None,
-        })
+            glance,
+            review_origin: None,
+        };
+        app.check_achievements();
+        if start_in_review {
+            if app.glance.due > 0 {
+                app.navigate_to(Screen::Review)?;
+            } else {
+                // Nothing due: stay on the dashboard with the "all done" message
+                app.dashboard.set_completion_message(true);
+            }
+        }
+        Ok(app)
+    }
+
+    /// Unlock newly reached achievements and announce them in a toast
+    fn check_achievements(&mut self) {
+        // The dashboard's handle sees every other screen's committed writes (WAL)
+        let newly = match self.dashboard.db().check_achievements() {
+            Ok(newly) => newly,
+            Err(e) => {
+                logger::error(&format!("Failed to check achievements: {}", e));
+                return;
+            }
+        };
+        if newly.is_empty() {
+            return;
+        }
+        let names: Vec<&str> = newly.iter().map(|a| t(a.label_key())).collect();
+        let msg = format!("{}{}", t("ach.unlocked"), names.join("  "));
+        self.notification = Some((msg, std::time::Instant::now()));
+        self.dashboard.refresh_stats();
+    }
+
+    fn refresh_glance(&mut self) {
+        self.glance = TodayGlance::load(self.dashboard.db());
+    }
+
+    /// Flush state before the terminal is restored (also when the loop exits with an error)
+    pub fn shutdown(&self) {
+        self.save_dictionary_position();
+        self.dashboard.checkpoint();
+    }
+
+    /// Remember where the dictionary was before it is dropped (leaving it, or quitting on it)
+    fn save_dictionary_position(&self) {
+        if self.current_screen != Screen::Dictionary {
+            return;
+        }
+        if let Some(Err(e)) = self.dictionary.as_ref().map(|dict| dict.save_position()) {
+            logger::error(&format!("Failed to save dictionary position: {}", e));
+        }
+    }
+
+    /// One-line recap printed after the terminal is restored (None when the setting is off)
+    pub fn exit_summary(&self) -> Result<Option<String>> {
+        let db = self.dashboard.db();
+        if !db.get_exit_summary()? {
+            return Ok(None);
+        }
+        let glance = TodayGlance::load(db);
+        let next_review = if glance.due > 0 {
+            t("exit.now").to_string()
+        } else {
+            match db.get_next_review_time()? {
+                Some(next) => next.with_timezone(&chrono::Local).format("%m-%d %H:%M").to_string(),
+                None => "-".to_string(),
+            }
+        };
+        Ok(Some(format!(
+            "LexRain  {}{}/{}  ·  {}{}  ·  {}  ·  {}{}",
+            t("glance.today"),
+            glance.completed,
+            glance.goal,
+            t("glance.due"),
+            glance.due,
+            t_n("glance.streak", glance.streak),
+            t("exit.next_review"),
+            next_review
+        )))
     }
 
     pub fn handle_key(&mut self, key: KeyEvent) -> Result<bool> {
+        // Ctrl-r: 任何页面直接开始复习到期单词（输入框有焦点时不拦截）
+        if key.code == KeyCode::Char('r') && key.modifiers.contains(KeyModifiers::CONTROL) && !self.is_typing() {
+            self.jump_to_due_review()?;
+            return Ok(false);
+        }
+        // /: 全局搜索（学过的单词、复习历史、收藏）；复习中有卡片时 `/` 是查词浮窗
+        let reviewing = self.current_screen == Screen::Review && self.review.as_ref().is_some_and(|review| review.has_card());
+        if key.code == KeyCode::Char('/') && self.current_screen != Screen::Search && !reviewing && !self.is_typing() {
+            self.navigate_to(Screen::Search)?;
+            return Ok(false);
+        }
+
         let action = match self.current_screen {
             Screen::Dashboard => self.dashboard.handle_key(key)?,
             Screen::Review => {
                 if let Some(review) = &mut self.review {
+                    let completed_before = review.completed_count();
                     let action = review.handle_key(key)?;
+                    if let Some(notice) = review.take_notice() {
+                        self.notification = Some((notice, std::time::Instant::now()));
+                    }
+                    if review.completed_count() != completed_before {
+                        self.glance = TodayGlance::load(self.dashboard.db());
+                    }
+                    // A single-word review goes back where it came from, finished or not
+                    if let Some(origin) = self.review_origin.clone() {
+                        let finished = review.is_complete() && matches!(action, Action::None);
+                        if finished || matches!(action, Action::NavigateTo(Screen::Dashboard)) {
+                            self.return_from_single_review(origin)?;
+                            return Ok(false);
+                        }
+                    }
                     // Check if review is complete after handling key
                     if review.is_complete() && matches!(action, Action::None) {
-                        self.dashboard.set_completion_message(true);
-                        self.navigate_to(Screen::Dashboard)?;
+                        self.finish_session()?;
                         return Ok(false);
                     }
                     action
@@ -98,11 +239,108 @@ impl AppV2 {
                     Action::NavigateTo(Screen::Dashboard)
                 }
             }
+            Screen::Search => {
+                if let Some(search) = &mut self.search {
+                    search.handle_key(key)?
+                } else {
+                    Action::NavigateTo(Screen::Dashboard)
+                }
+            }
+            Screen::Summary => {
+                if let Some(summary) = &mut self.summary {
+                    summary.handle_key(key)?
+                } else {
+                    Action::NavigateTo(Screen::Dashboard)
+                }
+            }
         };
 
         self.handle_action(action)
     }
 
+    /// Whether the active screen has a text field focused
+    fn is_typing(&self) -> bool {
+        match self.current_screen {
+            Screen::Dictionary => self.dictionary.as_ref().is_some_and(|dict| dict.is_typing()),
+            Screen::Settings => self.settings.as_ref().is_some_and(|settings| settings.is_typing()),
+            Screen::Search => self.search.as_ref().is_some_and(|search| search.is_typing()),
+            Screen::Review => self.review.as_ref().is_some_and(|review| review.is_typing()),
+            Screen::Wordbook => self.wordbook.as_ref().is_some_and(|wordbook| wordbook.is_typing()),
+            _ => false,
+        }
+    }
+
+    /// Configured search box toggle of the active screen (Tab unless changed in Settings)
+    fn search_focus_key(&self) -> SearchFocusKey {
+        match self.current_screen {
+            Screen::Dictionary => self.dictionary.as_ref().map(|dict| dict.search_focus_key()),
+            Screen::Search => self.search.as_ref().map(|search| search.search_focus_key()),
+            _ => None,
+        }
+        .unwrap_or(SearchFocusKey::Tab)
+    }
+
+    /// Footer label of the dictionary's Enter key (configurable in Settings)
+    fn dictionary_enter_hint(&self) -> &'static str {
+        match self.dictionary.as_ref().map(|dict| dict.enter_action()) {
+            Some(DictEnterAction::StartReview) => t("key.review_word"),
+            Some(DictEnterAction::AddToLearning) => t("key.add_to_learning"),
+            _ => t("key.open"),
+        }
+    }
+
+    /// Footer key for focusing the search box: "Tab/i", "F2/i" or just "i"
+    fn search_open_hint(&self) -> String {
+        let extra = if self.current_screen == Screen::Search { "/" } else { "i" };
+        match self.search_focus_key() {
+            SearchFocusKey::Unbound => extra.to_string(),
+            key => format!("{}/{}", key.open_hint(), extra),
+        }
+    }
+
+    /// Footer key for leaving the search box: "Tab/Esc", "F2/Esc" or just "Esc"
+    fn search_close_hint(&self) -> String {
+        match self.search_focus_key() {
+            SearchFocusKey::Unbound => "Esc".to_string(),
+            key => format!("{}/Esc", key.close_hint()),
+        }
+    }
+
+    /// After the last card: dashboard, overview or another new-words session (setting)
+    fn finish_session(&mut self) -> Result<()> {
+        let db = Database::initialize()?;
+        match db.get_on_session_complete()? {
+            SessionComplete::Dashboard => {}
+            SessionComplete::Summary => return self.navigate_to(Screen::Summary),
+            SessionComplete::LoopNew => {
+                self.dashboard.checkpoint();
+                let mut review = ReviewComponent::new(db);
+                if review.start_new_words()? {
+                    self.review = Some(review);
+                    return Ok(());
+                }
+                // Nothing left to learn (or paused today): say why on the dashboard
+                if let Some(message) = review.empty_message() {
+                    self.notification = Some((message.to_string(), std::time::Instant::now()));
+                }
+            }
+        }
+        self.dashboard.set_completion_message(true);
+        self.navigate_to(Screen::Dashboard)
+    }
+
+    /// Start a due review from anywhere; a review already in progress is left alone
+    fn jump_to_due_review(&mut self) -> Result<()> {
+        let reviewing = self.current_screen == Screen::Review
+            && self.review.as_ref().is_some_and(|review| review.has_card());
+        if reviewing {
+            self.notification = Some(("Already reviewing".to_string(), std::time::Instant::now()));
+            return Ok(());
+        }
+        self.dashboard.checkpoint();
+        self.navigate_to(Screen::Review)
+    }
+
     fn handle_action(&mut self, action: Action) -> Result<bool> {
         match action {
             Action::Quit => Ok(true),
@@ -110,8 +348,49 @@ impl AppV2 {
                 self.navigate_to(screen)?;
                 Ok(false)
             }
-            Action::StartWordbookReview(tag, shuffle) => {
-                self.start_wordbook_review(&tag, shuffle)?;
+            Action::StartWordbookReview(tag, order, due_only) => {
+                self.start_wordbook_review(&tag, order, due_only)?;
+                Ok(false)
+            }
+            Action::StartOverdueReview => {
+                self.dashboard.checkpoint();
+                let mut review = ReviewComponent::new(Database::initialize()?);
+                review.start_review(review::ReviewMode::Overdue)?;
+                self.review = Some(review);
+                self.review_origin = None;
+                self.current_screen = Screen::Review;
+                Ok(false)
+            }
+            Action::StartForgottenReview => {
+                self.dashboard.checkpoint();
+                let mut review = ReviewComponent::new(Database::initialize()?);
+                if review.start_review(review::ReviewMode::Forgotten)? {
+                    self.review = Some(review);
+                    self.review_origin = None;
+                    self.current_screen = Screen::Review;
+                } else if let Some(message) = review.empty_message() {
+                    self.notification = Some((message.to_string(), std::time::Instant::now()));
+                }
+                Ok(false)
+            }
+            Action::StartBrowse => {
+                self.dashboard.checkpoint();
+                self.start_browse(review::ReviewMode::Due, "现在没有到期的单词可浏览")?;
+                Ok(false)
+            }
+            Action::StartWordbookBrowse(tag, order, due_only) => {
+                self.start_browse(review::ReviewMode::Wordbook(tag, order, due_only), "这个单词本没有可浏览的单词")?;
+                Ok(false)
+            }
+            Action::StartSingleReview(word_id) => {
+                let mut review = ReviewComponent::new(Database::initialize()?);
+                if review.start_review(review::ReviewMode::Single(word_id))? {
+                    self.review = Some(review);
+                    self.review_origin = Some(self.current_screen.clone());
+                    self.current_screen = Screen::Review;
+                } else if let Some(message) = review.empty_message() {
+                    self.notification = Some((message.to_string(), std::time::Instant::now()));
+                }
                 Ok(false)
             }
             Action::ToggleFavorite(word_id) => {
@@ -138,11 +417,64 @@ impl AppV2 {
                 
                 Ok(false)
             }
+            Action::OpenSearchHit(source, word_id, spelling) => {
+                self.open_search_hit(source, word_id, &spelling)?;
+                Ok(false)
+            }
             Action::None => Ok(false),
         }
     }
 
+    /// 全局搜索结果：历史打开复习记录详情，收藏选中该词，其余打开词典详情
+    fn open_search_hit(&mut self, source: SearchSource, word_id: i64, spelling: &str) -> Result<()> {
+        match source {
+            SearchSource::History => {
+                let mut history = HistoryComponent::new(Database::initialize()?)?;
+                // Only recent reviews are listed; older ones fall back to the dictionary
+                if history.open_word(word_id) {
+                    self.history = Some(history);
+                    self.current_screen = Screen::History;
+                    return Ok(());
+                }
+            }
+            SearchSource::Favorites => {
+                let mut favorites = FavoritesComponent::new(Database::initialize()?)?;
+                favorites.select_word(word_id);
+                self.favorites = Some(favorites);
+                self.current_screen = Screen::Favorites;
+                return Ok(());
+            }
+            SearchSource::Learned => {}
+        }
+        let mut dictionary = DictionaryComponent::new(Database::initialize()?)?;
+        dictionary.open_word(word_id, spelling)?;
+        self.dictionary = Some(dictionary);
+        self.current_screen = Screen::Dictionary;
+        Ok(())
+    }
+
+    /// 单词复习结束：回到原页面（保留词典的搜索和选中位置），刷新状态
+    fn return_from_single_review(&mut self, origin: Screen) -> Result<()> {
+        self.review_origin = None;
+        self.review = None;
+        self.check_achievements();
+        self.glance = TodayGlance::load(self.dashboard.db());
+        if let Some(dict) = &mut self.dictionary {
+            dict.refresh()?;
+        }
+        self.current_screen = origin;
+        Ok(())
+    }
+
     fn navigate_to(&mut self, screen: Screen) -> Result<()> {
+        self.save_dictionary_position();
+        self.dashboard.checkpoint();
+        // Leaving a review session: milestones may have been reached
+        if self.current_screen == Screen::Review {
+            self.check_achievements();
+        }
+        self.refresh_glance();
+
         match screen {
             Screen::Dashboard => {
                 self.dashboard.refresh_stats();
@@ -153,15 +485,11 @@ impl AppV2 {
                 let db = Database::initialize()?;
                 let mut review = ReviewComponent::new(db);
 
-                // Try to start due reviews first
-                if !review.start_review(review::ReviewMode::Due)? {
-                    // No due reviews, show completion message
-                    self.dashboard.set_completion_message(true);
-                    self.current_screen = Screen::Dashboard;
-                    return Ok(());
-                }
+                // Nothing due: the review screen shows its empty state with next actions
+                review.start_review(review::ReviewMode::Due)?;
 
                 self.review = Some(review);
+                self.review_origin = None;
                 self.current_screen = Screen::Review;
             }
             Screen::Dictionary => {
@@ -194,22 +522,41 @@ impl AppV2 {
                 self.settings = Some(SettingsComponent::new(db)?);
                 self.current_screen = Screen::Settings;
             }
+            Screen::Search => {
+                let db = Database::initialize()?;
+                self.search = Some(SearchComponent::new(db));
+                self.current_screen = Screen::Search;
+            }
+            Screen::Summary => {
+                let db = Database::initialize()?;
+                self.summary = Some(SummaryComponent::new(db)?);
+                self.current_screen = Screen::Summary;
+            }
         }
         Ok(())
     }
 
+    /// Forward Tick events to the active screen
+    pub fn on_tick(&mut self) {
+        if let (Screen::Review, Some(review)) = (&self.current_screen, &mut self.review) {
+            review.on_tick();
+        }
+    }
+
     pub fn render(&mut self, frame: &mut Frame) {
         let area = frame.area();
 
         // Render header
         self.render_header(frame, area);
 
-        // Calculate content area (excluding header and footer)
+        self.render_glance(frame, area);
+
+        // Calculate content area (excluding header, glance line and footer)
         let content_area = Rect {
             x: area.x,
-            y: area.y + 3,
+            y: area.y + 4,
             width: area.width,
-            height: area.height.saturating_sub(6),
+            height: area.height.saturating_sub(7),
         };
 
         // Render current screen
@@ -250,6 +597,16 @@ impl AppV2 {
                     settings.view(frame, content_area);
                 }
             }
+            Screen::Search => {
+                if let Some(search) = &mut self.search {
+                    search.view(frame, content_area);
+                }
+            }
+            Screen::Summary => {
+                if let Some(summary) = &mut self.summary {
+                    summary.view(frame, content_area);
+                }
+            }
         }
 
         // Render footer
@@ -268,37 +625,36 @@ impl AppV2 {
     fn render_header(&self, frame: &mut Frame, area: Rect) {
         use ratatui::{
             style::{Modifier, Style},
-            widgets::{Block, BorderType, Borders, Tabs},
+            widgets::Tabs,
         };
 
         let titles = vec![
-            "Dashboard",
-            "Review",
-            "Dictionary",
-            "History",
-            "Statistics",
-            "Wordbook",
-            "Favorites",
-            "Settings",
-            "Quit",
+            t("tab.dashboard"),
+            t("tab.review"),
+            t("tab.dictionary"),
+            t("tab.history"),
+            t("tab.statistics"),
+            t("tab.wordbook"),
+            t("tab.favorites"),
+            t("tab.settings"),
+            t("tab.quit"),
         ];
         let tabs = Tabs::new(titles)
             .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .border_type(BorderType::Thick)
+                Theme::bordered()
                     .border_style(Theme::text_normal())
                     .title(" LexRain ")
             )
             .select(match self.current_screen {
-                Screen::Dashboard => 0,
-                Screen::Review => 1,
-                Screen::Dictionary => 2,
-                Screen::History => 3,
-                Screen::Statistics => 4,
-                Screen::Wordbook => 5,
-                Screen::Favorites => 6,
-                Screen::Settings => 7,
+                Screen::Dashboard => Some(0),
+                Screen::Review => Some(1),
+                Screen::Dictionary => Some(2),
+                Screen::History => Some(3),
+                Screen::Statistics => Some(4),
+                Screen::Wordbook => Some(5),
+                Screen::Favorites => Some(6),
+                Screen::Settings => Some(7),
+                Screen::Search | Screen::Summary => None, // Not a tab
             })
             .highlight_style(
                 Style::default()
@@ -317,6 +673,46 @@ impl AppV2 {
         frame.render_widget(tabs, header_area);
     }
 
+    /// 标签栏下方的一行概览：待复习、今日进度、连续打卡
+    fn render_glance(&self, frame: &mut Frame, area: Rect) {
+        use ratatui::{
+            text::{Line, Span},
+            widgets::Paragraph,
+        };
+
+        let glance = &self.glance;
+        let separator = || Span::styled("  │  ", Theme::text_secondary());
+        // Stays up until a dictionary query succeeds again
+        let line = if crate::db::dict_unavailable() {
+            Line::from(Span::styled(t("glance.dict_unavailable"), Theme::text_warning()))
+        } else {
+            Line::from(vec![
+                Span::styled(t("glance.due"), Theme::text_secondary()),
+                Span::styled(
+                    glance.due.to_string(),
+                    if glance.due > 0 { Theme::text_warning() } else { Theme::text_success() },
+                ),
+                separator(),
+                Span::styled(t("glance.today"), Theme::text_secondary()),
+                Span::styled(format!("{}/{}", glance.completed, glance.goal), Theme::text_title()),
+                Span::styled(
+                    if glance.completed >= glance.goal { " ✓" } else { "" },
+                    Theme::text_success(),
+                ),
+                separator(),
+                Span::styled(t_n("glance.streak", glance.streak), Theme::text_secondary()),
+            ])
+        };
+
+        let glance_area = Rect {
+            x: area.x,
+            y: area.y + 3,
+            width: area.width,
+            height: 1.min(area.height.saturating_sub(3)),
+        };
+        frame.render_widget(Paragraph::new(line).alignment(ratatui::layout::Alignment::Center), glance_area);
+    }
+
     fn render_footer(&self, frame: &mut Frame, area: Rect) {
         use crate::components::common::StatusBar;
 
@@ -329,65 +725,154 @@ impl AppV2 {
 
         let status_bar = match self.current_screen {
             Screen::Dashboard => StatusBar::new()
-                .add_item("r", "Review")
-                .add_item("w", "Wordbook")
-                .add_item("f", "Favorites")
-                .add_item("d", "Dictionary")
-                .add_item("h", "History")
-                .add_item("s", "Statistics")
-                .add_item("c", "Settings")
-                .add_item("q", "Quit"),
+                .add_item("r", t("tab.review"))
+                .add_item("R", t("key.oldest_due"))
+                .add_item("F", t("key.review_forgotten"))
+                .add_item("b", t("key.browse"))
+                .add_item("w", t("tab.wordbook"))
+                .add_item("f", t("tab.favorites"))
+                .add_item("d", t("tab.dictionary"))
+                .add_item("h", t("tab.history"))
+                .add_item("s", t("tab.statistics"))
+                .add_item("o", t("key.deck_summary"))
+                .add_item("p", t("key.skip_new"))
+                .add_item("c", t("tab.settings"))
+                .add_item("/", t("key.global_search"))
+                .add_item("H", t("key.calendar_style"))
+                .add_item("q", t("tab.quit")),
+            Screen::Review if self.review.as_ref().is_some_and(|r| !r.has_card()) => StatusBar::new()
+                .add_item("n", t("key.learn_new"))
+                .add_item("w", t("tab.wordbook"))
+                .add_item("d", t("tab.dictionary"))
+                .add_item("q/Esc", t("key.back")),
+            Screen::Review if self.review.as_ref().is_some_and(|r| r.wants_hint_key()) => StatusBar::new()
+                .add_item("Space", t("key.show_answer"))
+                .add_item("h", t("key.hint"))
+                .add_item("/", t("key.lookup"))
+                .add_item("q/Esc", t("key.back")),
+            Screen::Review if self.review.as_ref().is_some_and(|r| r.is_browsing()) => StatusBar::new()
+                .add_item("Space", t("key.next_card"))
+                .add_item("f", t("key.favorite"))
+                .add_item("Tab/h/l", t("key.switch_panel"))
+                .add_item("J/K", t("key.scroll_both"))
+                .add_item("/", t("key.lookup"))
+                .add_item("q/Esc", t("key.back")),
             Screen::Review => StatusBar::new()
-                .add_item("Space", "Show Answer")
-                .add_item("1", "Hard")
-                .add_item("2", "Difficult")
-                .add_item("3", "Good")
-                .add_item("4", "Easy")
-                .add_item("f", "Favorite")
-                .add_item("q/Esc", "Back"),
+                .add_item("Space", t("key.show_answer"))
+                .add_item("1", t("key.hard"))
+                .add_item("2", t("key.difficult"))
+                .add_item("3", t("key.good"))
+                .add_item("4", t("key.easy"))
+                .add_item("f", t("key.favorite"))
+                .add_item("Tab/h/l", t("key.switch_panel"))
+                .add_item("J/K", t("key.scroll_both"))
+                .add_item("g", t("key.top"))
+                .add_item("/", t("key.lookup"))
+                .add_item("q/Esc", t("key.back")),
+            Screen::Dictionary if self.dictionary.as_ref().is_some_and(|dict| dict.in_search_box()) => StatusBar::new()
+                .add_item("Enter", t("key.search"))
+                .add_item(self.search_close_hint(), t("key.results")),
+            Screen::Dictionary if self.is_typing() => StatusBar::new()
+                .add_item("Enter", t("key.confirm"))
+                .add_item("Esc", t("key.cancel_back")),
             Screen::Dictionary => StatusBar::new()
-                .add_item("Type", "Search")
-                .add_item("↑/↓/j/k", "Navigate")
-                .add_item("f", "Favorite")
-                .add_item("g/G", "First/Last")
-                .add_item("PgUp/PgDn", "Page")
-                .add_item("q/Esc", "Back"),
+                .add_item("Enter", self.dictionary_enter_hint())
+                .add_item(self.search_open_hint(), t("key.search"))
+                .add_item(":", t("key.goto"))
+                .add_item("↑/↓/j/k", t("key.navigate"))
+                .add_item("f", t("key.favorite"))
+                .add_item("t", t("key.self_test"))
+                .add_item("R", t("key.review_word"))
+                .add_item("v", t("key.variants"))
+                .add_item("b", t("key.freq_band"))
+                .add_item("T", t("key.tag_filter"))
+                .add_item("m", t("key.show_mastered"))
+                .add_item("r", t("key.reschedule"))
+                .add_item("e", t("key.reset_ease"))
+                .add_item("u", t("key.unmaster"))
+                .add_item("Ctrl-d/u", t("key.scroll_detail"))
+                .add_item("g/G", t("key.first_last"))
+                .add_item("PgUp/PgDn", t("key.page"))
+                .add_item("q/Esc", t("key.back")),
             Screen::History => StatusBar::new()
-                .add_item("f", "Favorite")
-                .add_item("q/Esc", "Back"),
-            Screen::Statistics => StatusBar::new().add_item("q/Esc", "Back"),
+                .add_item("f", t("key.favorite"))
+                .add_item("Ctrl-r", t("key.quick_due"))
+                .add_item("q/Esc", t("key.back")),
+            Screen::Statistics => StatusBar::new()
+                .add_item("g", t("key.granularity"))
+                .add_item("Ctrl-r", t("key.quick_due"))
+                .add_item("q/Esc", t("key.back")),
             Screen::Wordbook => StatusBar::new()
-                .add_item("Enter", "Start Review")
-                .add_item("s", "Toggle Shuffle")
-                .add_item("↑/↓", "Select")
-                .add_item("q", "Back"),
+                .add_item("Enter", t("key.start_review"))
+                .add_item("b", t("key.browse"))
+                .add_item("s", t("key.word_order"))
+                .add_item("d", t("key.toggle_due_only"))
+                .add_item("f", t("key.focus"))
+                .add_item("p", t("key.priority"))
+                .add_item("c", t("key.compare"))
+                .add_item("x/X", t("key.suspend_resume"))
+                .add_item("↑/↓", t("key.select"))
+                .add_item("q", t("key.back")),
             Screen::Favorites => StatusBar::new()
-                .add_item("↑/↓/j/k", "Navigate")
-                .add_item("f/u", "Unfavorite")
-                .add_item("q/Esc", "Back"),
+                .add_item("↑/↓/j/k", t("key.navigate"))
+                .add_item("f/u", t("key.unfavorite"))
+                .add_item("q/Esc", t("key.back")),
             Screen::Settings => StatusBar::new()
-                .add_item("e", "Edit")
-                .add_item("Enter", "Save")
-                .add_item("Esc", "Cancel/Back"),
+                .add_item("↑/↓", t("key.select"))
+                .add_item("e/Enter", t("key.edit"))
+                .add_item("←/→", t("key.change"))
+                .add_item("PgUp/PgDn", t("key.scroll_guide"))
+                .add_item("Esc", t("key.cancel_back")),
+            Screen::Search if self.is_typing() => StatusBar::new()
+                .add_item("Enter", t("key.search"))
+                .add_item(self.search_close_hint(), t("key.results")),
+            Screen::Search => StatusBar::new()
+                .add_item("↑/↓/j/k", t("key.navigate"))
+                .add_item("Enter", t("key.open"))
+                .add_item(self.search_open_hint(), t("key.search"))
+                .add_item("q/Esc", t("key.back")),
+            Screen::Summary => StatusBar::new()
+                .add_item("↑/↓/j/k", t("key.scroll"))
+                .add_item("q/Esc", t("key.back")),
         };
 
         status_bar.render(frame, footer_area);
     }
 
-    pub fn start_wordbook_review(&mut self, tag: &str, shuffle: bool) -> Result<()> {
+    pub fn start_wordbook_review(&mut self, tag: &str, order: WordOrder, due_only: bool) -> Result<()> {
         let db = Database::initialize()?;
         let mut review = ReviewComponent::new(db);
 
-        if !review.start_review(review::ReviewMode::Wordbook(tag.to_string(), shuffle))? {
+        if !review.start_review(review::ReviewMode::Wordbook(tag.to_string(), order, due_only))? {
             // No words available in this wordbook
+            if let Some(message) = review.empty_message() {
+                self.notification = Some((message.to_string(), std::time::Instant::now()));
+            } else if due_only {
+                self.notification = Some((t("wordbook.no_due").to_string(), std::time::Instant::now()));
+            }
             return Ok(());
         }
 
         self.review = Some(review);
+        self.review_origin = None;
         self.current_screen = Screen::Review;
         Ok(())
     }
     
+    /// 浏览模式（不计分）：集合为空时只提示，不进入复习页
+    fn start_browse(&mut self, mode: review::ReviewMode, empty: &str) -> Result<()> {
+        let mut review = ReviewComponent::new(Database::initialize()?);
+        if review.start_browse(mode)? {
+            self.review = Some(review);
+            self.review_origin = None;
+            self.current_screen = Screen::Review;
+        } else {
+            let message = review.empty_message().unwrap_or(empty).to_string();
+            self.notification = Some((message, std::time::Instant::now()));
+        }
+        Ok(())
+    }
+
     fn render_notification(&self, frame: &mut Frame, area: Rect, message: &str) {
         use ratatui::{
             layout::Alignment,
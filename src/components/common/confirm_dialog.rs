@@ -0,0 +1,52 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Rect},
+    text::{Line, Span},
+    widgets::{Clear, Paragraph, Wrap},
+    Frame,
+};
+use super::popup::centered_rect;
+use crate::theme::Theme;
+
+/// 确认对话框，用于批量修改等不可撤销的操作
+pub struct ConfirmDialog {
+    title: String,
+    message: String,
+}
+
+impl ConfirmDialog {
+    pub fn new(title: String, message: String) -> Self {
+        Self { title, message }
+    }
+
+    /// y/Enter 确认返回 Some(true)，n/Esc/q 取消返回 Some(false)，其他键忽略
+    pub fn handle_key(&self, key: KeyEvent) -> Option<bool> {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Enter => Some(true),
+            KeyCode::Char('n') | KeyCode::Char('q') | KeyCode::Esc => Some(false),
+            _ => None,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let dialog_area = centered_rect(60, 30, area);
+        frame.render_widget(Clear, dialog_area);
+
+        let lines = vec![
+            Line::from(""),
+            Line::from(Span::styled(self.message.as_str(), Theme::text_normal())),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled(" y ", Theme::text_normal().bg(Theme::ACCENT)),
+                Span::raw(" 确认    "),
+                Span::styled(" n ", Theme::text_normal().bg(Theme::INFO)),
+                Span::raw(" 取消"),
+            ]),
+        ];
+        let paragraph = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .block(Theme::block_accent().title(format!(" {} ", self.title)));
+        frame.render_widget(paragraph, dialog_area);
+    }
+}
@@ -2,8 +2,225 @@ pub mod progress_bar;
 pub mod search_input;
 pub mod status_bar;
 pub mod popup;
+pub mod confirm_dialog;
 
 pub use progress_bar::ProgressBar;
 pub use search_input::SearchInput;
 pub use status_bar::StatusBar;
 pub use popup::Popup;
+pub use confirm_dialog::ConfirmDialog;
+
+use crate::models::{SearchFocusKey, Word};
+use crate::theme::Theme;
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+/// Whether `code` is the configured search-box toggle (`i` and Esc are handled by the caller)
+pub fn is_search_focus_key(focus_key: SearchFocusKey, code: KeyCode) -> bool {
+    matches!(
+        (focus_key, code),
+        (SearchFocusKey::Tab, KeyCode::Tab) | (SearchFocusKey::F2, KeyCode::F(2))
+    )
+}
+
+/// Compact word preview (spelling, phonetic, first translation lines) for list side panels
+pub fn word_preview_lines(word: &Word) -> Vec<Line<'_>> {
+    let mut lines = vec![Line::from(Span::styled(word.spelling.as_str(), Theme::text_title()))];
+    if let Some(phonetic) = &word.phonetic {
+        lines.push(Line::from(Span::styled(format!("[{}]", phonetic), Theme::text_secondary())));
+    }
+    lines.push(Line::from(""));
+    if let Some(translation) = &word.translation {
+        for line in translation.lines().take(5) {
+            lines.push(Line::from(line));
+        }
+    }
+    lines
+}
+
+/// 中文释义按义项编号显示（"1. …"）。ECDICT 一般一行一个词性（"v. 去, 离开"），
+/// 也有挤在一行的 "[n.] …; [v.] …"；只有一个义项时照原样缩进显示
+pub fn format_translation(translation: &str) -> Vec<Line<'static>> {
+    let meanings = split_meanings(translation);
+    if meanings.len() <= 1 {
+        return meanings
+            .into_iter()
+            .map(|meaning| Line::from(Span::styled(format!("  {}", meaning), Theme::text_content())))
+            .collect();
+    }
+    meanings
+        .into_iter()
+        .enumerate()
+        .map(|(i, meaning)| {
+            Line::from(vec![
+                Span::styled(format!("  {}. ", i + 1), Theme::text_secondary()),
+                Span::styled(meaning.to_string(), Theme::text_content()),
+            ])
+        })
+        .collect()
+}
+
+/// Blank lines closing a detail section (doubled when emphasis is on)
+pub fn push_section_gap(lines: &mut Vec<Line<'_>>) {
+    lines.extend(std::iter::repeat_n(Line::from(""), Theme::section_gap()));
+}
+
+/// One meaning per non-empty line; a single line is split at semicolons that
+/// start a new part of speech, so "n. 行动; 作用" stays one meaning
+fn split_meanings(translation: &str) -> Vec<&str> {
+    let lines: Vec<&str> = translation.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+    let [line] = lines[..] else {
+        return lines;
+    };
+    let mut meanings = Vec::new();
+    let mut start = 0;
+    for (pos, sep) in line.char_indices().filter(|(_, c)| *c == ';' || *c == '；') {
+        let next = pos + sep.len_utf8();
+        if starts_with_pos(line[next..].trim_start()) {
+            meanings.push(line[start..pos].trim());
+            start = next;
+        }
+    }
+    meanings.push(line[start..].trim());
+    meanings.retain(|meaning| !meaning.is_empty());
+    meanings
+}
+
+/// "n. …", "vt. …", "[n.] …" or a bracketed label such as "[计] …"
+fn starts_with_pos(text: &str) -> bool {
+    if text.starts_with('[') {
+        return text.contains(']');
+    }
+    let letters = text.chars().take_while(|c| c.is_ascii_alphabetic()).count();
+    (1..=5).contains(&letters) && text[letters..].starts_with('.')
+}
+
+/// 整理 ECDICT 英文释义的排版残留，只影响显示：字面的 `\n`、HTML 标签（`<br>` 换行，
+/// 其余去掉）、WordNet 的 `s.`/`r.` 词性（改成 adj./adv.）、多余空白和空行
+pub fn clean_definition(definition: &str) -> String {
+    let mut text = definition.replace("\\n", "\n").replace('\r', "");
+    for br in ["<br>", "<br/>", "<br />", "<BR>"] {
+        text = text.replace(br, "\n");
+    }
+    let text = strip_tags(&text);
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .map(|line| match line.split_once(' ') {
+            Some(("s.", rest)) => format!("adj. {rest}"),
+            Some(("r.", rest)) => format!("adv. {rest}"),
+            _ => line,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Drop `<tag …>` / `</tag>`; a `<` not followed by a letter or `/` is kept as text
+fn strip_tags(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let is_tag = after.starts_with(|c: char| c.is_ascii_alphabetic() || c == '/');
+        match after.find('>').filter(|_| is_tag) {
+            Some(end) if !after[..end].contains('<') => rest = &after[end + 1..],
+            _ => {
+                out.push('<');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// English definition lines to display: cleaned (setting, default on) or the raw ECDICT text
+pub fn definition_lines(definition: &str, clean: bool) -> Vec<String> {
+    if clean {
+        return clean_definition(definition).lines().map(str::to_string).collect();
+    }
+    definition.lines().filter(|line| !line.trim().is_empty()).map(str::to_string).collect()
+}
+
+/// Largest useful scroll offset for a paragraph rendered into `area`, so panels
+/// stop at their last line instead of scrolling into blank space.
+///
+/// Assumes a bordered block; unbordered text is measured two columns narrower,
+/// which errs towards allowing a line more of scroll.
+pub fn max_scroll(paragraph: &Paragraph, area: Rect) -> u16 {
+    content_height(paragraph, area).saturating_sub(area.height)
+}
+
+/// Rendered height of a bordered paragraph in `area`, borders included
+/// (compare with `area.height`, as `max_scroll` does).
+pub fn content_height(paragraph: &Paragraph, area: Rect) -> u16 {
+    // line_count() adds the block's borders but wraps at the width it is given
+    paragraph.line_count(area.width.saturating_sub(2)) as u16
+}
+
+/// Step to the next/previous value of a choice, wrapping around
+pub fn cycle<T: Copy + PartialEq>(choices: &[T], current: T, forward: bool) -> T {
+    let index = choices.iter().position(|c| *c == current).unwrap_or(0);
+    let next = if forward {
+        (index + 1) % choices.len()
+    } else {
+        (index + choices.len() - 1) % choices.len()
+    };
+    choices[next]
+}
+
+/// Scroll position for a panel title: " top ", " 12% ", " bottom ",
+/// or an empty string when the content fits and nothing scrolls.
+pub fn scroll_indicator(scroll: u16, content_h: u16, view_h: u16) -> String {
+    let max = content_h.saturating_sub(view_h);
+    if max == 0 {
+        String::new()
+    } else if scroll == 0 {
+        " top ".to_string()
+    } else if scroll >= max {
+        " bottom ".to_string()
+    } else {
+        format!(" {}% ", u32::from(scroll) * 100 / u32::from(max))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clean_definition_splits_literal_newlines_and_breaks() {
+        let raw = "n. a written message\\nv. send a letter<br>n. a character<BR/>";
+        assert_eq!(
+            clean_definition(raw),
+            "n. a written message\nv. send a letter\nn. a character"
+        );
+    }
+
+    #[test]
+    fn clean_definition_strips_markup_but_keeps_brackets_and_comparisons() {
+        let raw = "<b>n.</b> <i>(Physics)</i> a unit [of energy] < 1 joule";
+        assert_eq!(clean_definition(raw), "n. (Physics) a unit [of energy] < 1 joule");
+        assert_eq!(clean_definition("[计] a <unclosed tag"), "[计] a <unclosed tag");
+    }
+
+    #[test]
+    fn clean_definition_renames_wordnet_pos_and_drops_blank_lines() {
+        let raw = "s.  very   large\\n\\n  \\nr. quickly\r\nadj. s. is kept mid-line";
+        assert_eq!(
+            clean_definition(raw),
+            "adj. very large\nadv. quickly\nadj. s. is kept mid-line"
+        );
+    }
+
+    #[test]
+    fn clean_definition_of_empty_or_markup_only_text_is_empty() {
+        assert_eq!(clean_definition(""), "");
+        assert_eq!(clean_definition("<br>\\n<p></p>"), "");
+    }
+}
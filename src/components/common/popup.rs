@@ -4,12 +4,14 @@ use ratatui::{
     widgets::{Clear, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap},
     Frame,
 };
+use super::scroll_indicator;
 use crate::theme::Theme;
 
 /// 通用浮窗组件，支持滚动和关闭
 pub struct Popup {
     scroll: u16,
     title: String,
+    section_rows: Vec<u16>, // Rendered row of each section header, measured on the last render
 }
 
 impl Popup {
@@ -17,6 +19,7 @@ impl Popup {
         Self {
             scroll: 0,
             title,
+            section_rows: Vec::new(),
         }
     }
 
@@ -32,6 +35,41 @@ impl Popup {
         self.scroll = 0;
     }
 
+    /// 跳到下一个小节标题（没有更多小节时不动）
+    pub fn next_section(&mut self) {
+        if let Some(row) = self.section_rows.iter().find(|row| **row > self.scroll) {
+            self.scroll = *row;
+        }
+    }
+
+    /// 跳到上一个小节标题，已在第一个小节时回到顶部
+    pub fn prev_section(&mut self) {
+        self.scroll = self.section_rows.iter().rev().find(|row| **row < self.scroll).copied().unwrap_or(0);
+    }
+
+    /// 渲染浮窗；`sections` 是各小节标题在 `content_lines` 中的下标，用于 Tab/Shift-Tab 跳转
+    pub fn render_with_sections(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        content_lines: Vec<Line<'_>>,
+        sections: &[usize],
+    ) {
+        let inner_width = centered_rect(80, 90, area).width.saturating_sub(2);
+        // Long definitions wrap, so convert line indices into rendered rows
+        let mut row = 0u16;
+        let mut section_rows = Vec::with_capacity(sections.len());
+        for (index, line) in content_lines.iter().enumerate() {
+            if sections.contains(&index) {
+                section_rows.push(row);
+            }
+            let height = Paragraph::new(line.clone()).wrap(Wrap { trim: true }).line_count(inner_width);
+            row = row.saturating_add(height.max(1) as u16);
+        }
+        self.section_rows = section_rows;
+        self.render(frame, area, content_lines);
+    }
+
     /// 渲染浮窗，返回内容区域
     pub fn render(&mut self, frame: &mut Frame, area: Rect, content_lines: Vec<Line<'_>>) {
         // 计算居中的浮窗区域（80% 宽度，90% 高度）
@@ -43,16 +81,21 @@ impl Popup {
         // 渲染浮窗边框
         let block = Theme::block_accent()
             .title(format!(" {} ", self.title))
-            .title_bottom(" q: 关闭 | j/k: 滚动 ");
-
+            .title_bottom(if self.section_rows.is_empty() {
+                " q: 关闭 | j/k: 滚动 "
+            } else {
+                " q: 关闭 | j/k: 滚动 | Tab/Shift-Tab: 上下小节 "
+            });
         let inner_area = block.inner(popup_area);
-        frame.render_widget(block, popup_area);
 
-        // 渲染内容
+        // 渲染内容（内容区没有边框，直接按内部宽度测量）
         let content_height = content_lines.len() as u16;
         let paragraph = Paragraph::new(content_lines)
             .wrap(Wrap { trim: true })
             .scroll((self.scroll, 0));
+        let rendered_height = paragraph.line_count(inner_area.width) as u16;
+        let indicator = scroll_indicator(self.scroll, rendered_height, inner_area.height);
+        frame.render_widget(block.title(Line::from(indicator).right_aligned()), popup_area);
         frame.render_widget(paragraph, inner_area);
 
         // 渲染滚动条
@@ -73,7 +116,7 @@ impl Popup {
 }
 
 /// 计算居中的矩形区域
-fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+pub(super) fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
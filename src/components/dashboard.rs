@@ -1,45 +1,293 @@
 use super::{Action, Component, Screen};
-use crate::db::Database;
+use crate::db::{Database, ECDICT_VERSION, RECENTLY_FORGOTTEN_LIMIT};
+use crate::models::{tag_display_name, Achievement, CalendarStyle, FreqBand, MaturityCounts, NewWordBudget, PriorityWordbook};
+use crate::i18n::{t, t_n};
 use crate::theme::Theme;
 use anyhow::Result;
+use chrono::{DateTime, Local};
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier},
     text::{Line, Span},
-    widgets::{Gauge, Paragraph},
+    widgets::{Gauge, Paragraph, Sparkline},
     widgets::calendar::{CalendarEventStore, Monthly},
     Frame,
 };
+use std::cmp::Ordering;
 use time::OffsetDateTime;
 
 pub struct DashboardComponent {
     db: Database,
     stats: (i64, i64, i64), // total, mastered, due
+    maturity: MaturityCounts,
     today_completed: i64,
     wordbook_count: usize,
+    corrupt_log_count: usize, // learning_log rows with unparseable next_review; full scan, so startup only
+    added_by_day: Vec<u64>,    // Learned words by added_at, last VELOCITY_DAYS days
+    mastered_by_day: Vec<u64>, // Words newly mastered per day
+    calendar_style: CalendarStyle,
+    focus_tag: Option<String>, // Wordbook new words are drawn from first
+    priority: Option<PriorityWordbook>, // Time-boxed priority wordbook, overrides focus_tag while active
+    freq_band: FreqBand,       // Frequency band new words are drawn from
+    new_word_budget: NewWordBudget,
+    achievements: Vec<Achievement>, // Unlocked badges, oldest first
+    forgotten: Vec<(String, DateTime<Local>)>, // Recently forgotten words (spelling, last lapse), newest first
+    reminder_hour: Option<u32>,       // Nudge when nothing was reviewed by this hour
+    reminder_dismissed_on: Option<time::Date>, // Esc hides the nudge for the rest of that day
+    skip_new_today: bool, // p: reviews only today, no new words
+    dict_age: Ordering, // ECDICT release vs the one the app expects (warning when not Equal)
     show_completion_message: bool,
 }
 
+/// Heatmap shades, from under half the daily goal to twice the goal or more
+const HEATMAP_LEVELS: [Color; 4] = [
+    Color::Rgb(0, 60, 30),
+    Color::Rgb(0, 100, 45),
+    Color::Rgb(0, 150, 65),
+    Color::Rgb(40, 200, 90),
+];
+
+/// Width of the new/young/mature stacked bar on the stats card
+const MATURITY_BAR_WIDTH: usize = 20;
+
+/// Days covered by the learning velocity sparklines
+const VELOCITY_DAYS: i64 = 14;
+
+/// Recently forgotten words listed on the dashboard (F reviews the whole list)
+const FORGOTTEN_SHOWN: usize = 5;
+
+fn load_forgotten(db: &Database) -> Vec<(String, DateTime<Local>)> {
+    db.get_recently_forgotten(RECENTLY_FORGOTTEN_LIMIT)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(word, _, forgot_at)| (word.spelling, forgot_at.with_timezone(&Local)))
+        .collect()
+}
+
+/// Parse a `YYYY-MM-DD` date as stored in the progress database
+fn parse_ymd(date: &str) -> Option<time::Date> {
+    let mut parts = date.split('-');
+    let year = parts.next()?.parse::<i32>().ok()?;
+    let month = time::Month::try_from(parts.next()?.parse::<u8>().ok()?).ok()?;
+    let day = parts.next()?.parse::<u8>().ok()?;
+    time::Date::from_calendar_date(year, month, day).ok()
+}
+
 impl DashboardComponent {
     pub fn new(db: Database) -> Self {
         let stats = db.get_stats().unwrap_or((0, 0, 0));
+        let maturity = db.get_maturity_counts().unwrap_or_default();
         let today_completed = db.get_today_completed_count().unwrap_or(0);
         let wordbook_count = db.get_wordbooks().unwrap_or_default().len();
+        let corrupt_log_count = db.find_corrupt_logs().map(|v| v.len()).unwrap_or(0);
+        let added_by_day = db.get_words_added_by_day(VELOCITY_DAYS).unwrap_or_default();
+        let mastered_by_day = db.get_words_mastered_by_day(VELOCITY_DAYS).unwrap_or_default();
+        let new_word_budget = db.suggest_new_word_budget().unwrap_or_default();
+        let calendar_style = db.get_calendar_style().unwrap_or(CalendarStyle::Checkin);
+        let focus_tag = db.get_focus_tag().unwrap_or(None);
+        let priority = db.get_priority_wordbook().unwrap_or(None);
+        let freq_band = db.get_freq_band().unwrap_or(FreqBand::All);
+        let achievements = db.get_achievements().unwrap_or_default();
+        let forgotten = load_forgotten(&db);
+        let reminder_hour = db.get_reminder_hour().unwrap_or(None);
+        let skip_new_today = db.get_skip_new_words_today().unwrap_or(false);
+        let dict_age = db.dict_version().age(ECDICT_VERSION);
 
         Self {
             db,
             stats,
+            maturity,
             today_completed,
             wordbook_count,
+            corrupt_log_count,
+            added_by_day,
+            mastered_by_day,
+            calendar_style,
+            focus_tag,
+            priority,
+            freq_band,
+            new_word_budget,
+            achievements,
+            forgotten,
+            reminder_hour,
+            reminder_dismissed_on: None,
+            skip_new_today,
+            dict_age,
             show_completion_message: false,
         }
     }
 
     pub fn refresh_stats(&mut self) {
         self.stats = self.db.get_stats().unwrap_or((0, 0, 0));
+        self.maturity = self.db.get_maturity_counts().unwrap_or_default();
         self.today_completed = self.db.get_today_completed_count().unwrap_or(0);
         self.wordbook_count = self.db.get_wordbooks().unwrap_or_default().len();
+        self.added_by_day = self.db.get_words_added_by_day(VELOCITY_DAYS).unwrap_or_default();
+        self.mastered_by_day = self.db.get_words_mastered_by_day(VELOCITY_DAYS).unwrap_or_default();
+        self.new_word_budget = self.db.suggest_new_word_budget().unwrap_or_default();
+        self.focus_tag = self.db.get_focus_tag().unwrap_or(None);
+        self.priority = self.db.get_priority_wordbook().unwrap_or(None);
+        self.freq_band = self.db.get_freq_band().unwrap_or(FreqBand::All);
+        self.achievements = self.db.get_achievements().unwrap_or_default();
+        self.forgotten = load_forgotten(&self.db);
+        self.reminder_hour = self.db.get_reminder_hour().unwrap_or(None);
+        self.skip_new_today = self.db.get_skip_new_words_today().unwrap_or(false);
+    }
+
+    /// New / young / mature as one stacked bar followed by the three counts
+    fn maturity_line(&self) -> Line<'static> {
+        let MaturityCounts { new, young, mature } = self.maturity;
+        let total = (new + young + mature).max(1) as usize;
+        // Cumulative rounding so the segments always add up to the full width
+        let edge = |count: i64| (count as usize * MATURITY_BAR_WIDTH + total / 2) / total;
+        let (new_end, young_end) = (edge(new), edge(new + young));
+        let mature_end = if new + young + mature == 0 { 0 } else { MATURITY_BAR_WIDTH };
+        Line::from(vec![
+            Span::styled("█".repeat(new_end), Theme::text_info()),
+            Span::styled("█".repeat(young_end - new_end), Theme::text_warning()),
+            Span::styled("█".repeat(mature_end - young_end), Theme::text_success()),
+            Span::styled("░".repeat(MATURITY_BAR_WIDTH - mature_end), Theme::text_secondary()),
+            Span::styled(t("dash.maturity_new"), Theme::text_normal()),
+            Span::styled(new.to_string(), Theme::text_info()),
+            Span::styled(t("dash.maturity_young"), Theme::text_normal()),
+            Span::styled(young.to_string(), Theme::text_warning()),
+            Span::styled(t("dash.maturity_mature"), Theme::text_normal()),
+            Span::styled(mature.to_string(), Theme::text_success()),
+        ])
+    }
+
+    /// Current study day (setting "每天几点换日"), as a `time::Date` for the calendar
+    fn study_today(&self) -> time::Date {
+        self.db
+            .today()
+            .ok()
+            .and_then(|day| parse_ymd(&day.format("%Y-%m-%d").to_string()))
+            .unwrap_or_else(|| OffsetDateTime::now_local().unwrap_or_else(|_| OffsetDateTime::now_utc()).date())
+    }
+
+    /// 学习提醒：过了设定的时间今天还没复习，且今天没按 Esc 关掉
+    fn reminder_due(&self) -> bool {
+        let Some(hour) = self.reminder_hour else {
+            return false;
+        };
+        let now = OffsetDateTime::now_local().unwrap_or_else(|_| OffsetDateTime::now_utc());
+        self.today_completed == 0
+            && u32::from(now.hour()) >= hour
+            && self.reminder_dismissed_on != Some(self.study_today())
+    }
+
+    /// 打卡模式：未达标的学习日暗色标记，达成每日目标的日子绿色标记
+    fn add_checkin_marks(&self, today: time::Date, event_store: &mut CalendarEventStore) -> Line<'static> {
+        let (year, month) = (today.year(), today.month() as u32);
+        let active_dates = self.db.get_active_dates(year, month).unwrap_or_default();
+        let checkin_dates = self.db.get_checkin_dates(year, month).unwrap_or_default();
+        let active_style = Theme::text_warning().bg(Color::Rgb(60, 45, 0));
+        let checkin_style = Theme::text_success().bg(Color::Rgb(0, 50, 0));
+        for date in active_dates.iter().filter_map(|d| parse_ymd(d)) {
+            event_store.add(date, active_style);
+        }
+        for date in checkin_dates.iter().filter_map(|d| parse_ymd(d)) {
+            event_store.add(date, checkin_style);
+        }
+
+        Line::from(vec![
+            Span::styled(" ■ ", checkin_style),
+            Span::raw(t_n("dash.goal_days", checkin_dates.len())),
+            Span::styled(" ■ ", active_style),
+            Span::raw(t_n("dash.active_days", active_dates.len())),
+        ])
+    }
+
+    /// 热力图模式：按当天复习量（相对每日目标）分级着色
+    fn add_heatmap_marks(&self, today: time::Date, event_store: &mut CalendarEventStore) -> Line<'static> {
+        let goal = self.db.get_daily_goal().unwrap_or(20).max(1);
+        let month_prefix = format!("{:04}-{:02}-", today.year(), today.month() as u8);
+        let counts = self.db.get_daily_review_counts(today.day() as i64).unwrap_or_default();
+        for (date_str, count) in counts.iter().filter(|(d, _)| d.starts_with(&month_prefix)) {
+            if let Some(date) = parse_ymd(date_str) {
+                // HEATMAP_LEVELS 下标 0: < 半个目标, 1: < 目标, 2: 达标, 3: 两倍目标以上
+                let level = match *count {
+                    c if c >= goal * 2 => 3,
+                    c if c >= goal => 2,
+                    c if c * 2 >= goal => 1,
+                    _ => 0,
+                };
+                event_store.add(date, Theme::text_normal().bg(HEATMAP_LEVELS[level]));
+            }
+        }
+
+        let mut legend = vec![Span::raw(format!(" {} ", t("dash.heatmap_less")))];
+        legend.extend(HEATMAP_LEVELS.iter().map(|color| Span::styled("■", Theme::text_normal().fg(*color))));
+        legend.push(Span::raw(format!(" {} ", t("dash.heatmap_more"))));
+        Line::from(legend)
+    }
+
+    /// 学习速度卡片：近 14 天新增 / 掌握单词的迷你折线
+    fn render_velocity(&self, frame: &mut Frame, area: Rect) {
+        let block = Theme::block_with_title(t("dash.velocity_title"));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let series = [
+            (t("dash.added"), &self.added_by_day, Theme::PRIMARY),
+            (t("dash.mastered_short"), &self.mastered_by_day, Theme::SUCCESS),
+        ];
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Length(2)])
+            .split(inner);
+
+        for ((label, data, color), row) in series.into_iter().zip(rows.iter()) {
+            let cols = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Length(10), Constraint::Min(1)])
+                .split(*row);
+            let total: u64 = data.iter().sum();
+            frame.render_widget(
+                Paragraph::new(vec![
+                    Line::from(Span::styled(label, Theme::text_normal())),
+                    Line::from(Span::styled(format!("{}{}", total, t("dash.unit")), Theme::text_secondary())),
+                ]),
+                cols[0],
+            );
+            if total > 0 {
+                frame.render_widget(
+                    Sparkline::default()
+                        .data(data.as_slice())
+                        .style(Theme::text_normal().fg(color)),
+                    cols[1],
+                );
+            }
+        }
+    }
+
+    /// 最近忘记卡片：最近忘记的几个词和忘记的日期，F 把整个列表拿去复习
+    fn render_forgotten(&self, frame: &mut Frame, area: Rect) {
+        let lines: Vec<Line> = self
+            .forgotten
+            .iter()
+            .take(FORGOTTEN_SHOWN)
+            .map(|(spelling, forgot_at)| {
+                Line::from(vec![
+                    Span::styled(format!(" {:<16}", spelling), Theme::text_title()),
+                    Span::styled(forgot_at.format("%m-%d").to_string(), Theme::text_secondary()),
+                ])
+            })
+            .collect();
+        let block = Theme::block_with_title(t_n("dash.forgotten_title", self.forgotten.len()))
+            .title_bottom(Line::from(Span::styled(t("dash.forgotten_review"), Theme::text_warning())));
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    /// The dashboard's connection stays open for the whole session (exit summary)
+    pub fn db(&self) -> &Database {
+        &self.db
+    }
+
+    /// Checkpoint the progress database (screen transitions and exit)
+    pub fn checkpoint(&self) {
+        let _ = self.db.checkpoint();
     }
 
     pub fn set_completion_message(&mut self, show: bool) {
@@ -52,6 +300,9 @@ impl Component for DashboardComponent {
         match key.code {
             KeyCode::Char('q') => Ok(Action::Quit),
             KeyCode::Char('r') => Ok(Action::NavigateTo(Screen::Review)),
+            KeyCode::Char('R') => Ok(Action::StartOverdueReview),
+            KeyCode::Char('F') if !self.forgotten.is_empty() => Ok(Action::StartForgottenReview),
+            KeyCode::Char('b') => Ok(Action::StartBrowse),
             KeyCode::Char('w') => {
                 self.show_completion_message = false;
                 Ok(Action::NavigateTo(Screen::Wordbook))
@@ -72,12 +323,32 @@ impl Component for DashboardComponent {
                 self.show_completion_message = false;
                 Ok(Action::NavigateTo(Screen::Statistics))
             }
+            KeyCode::Char('o') => {
+                self.show_completion_message = false;
+                Ok(Action::NavigateTo(Screen::Summary))
+            }
             KeyCode::Char('c') => {
                 self.show_completion_message = false;
                 Ok(Action::NavigateTo(Screen::Settings))
             }
+            KeyCode::Char('p') => {
+                self.skip_new_today = !self.skip_new_today;
+                self.db.set_skip_new_words_today(self.skip_new_today)?;
+                Ok(Action::None)
+            }
+            KeyCode::Char('H') => {
+                self.calendar_style = match self.calendar_style {
+                    CalendarStyle::Checkin => CalendarStyle::Heatmap,
+                    CalendarStyle::Heatmap => CalendarStyle::Checkin,
+                };
+                self.db.set_calendar_style(self.calendar_style)?;
+                Ok(Action::None)
+            }
             KeyCode::Esc => {
                 self.show_completion_message = false;
+                if self.reminder_due() {
+                    self.reminder_dismissed_on = Some(self.study_today());
+                }
                 Ok(Action::None)
             }
             _ => Ok(Action::None),
@@ -98,19 +369,26 @@ impl Component for DashboardComponent {
         let left_chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(7),   // Stats card (2 rows)
+                Constraint::Length(9),   // Stats card (3 rows + maturity bar)
                 Constraint::Length(5),   // Wordbooks card
                 Constraint::Min(8),      // Actions/Messages
             ])
             .margin(1)
             .split(main_layout[0]);
 
-        // Right column layout
+        // Right column layout (velocity card hidden until there is any data)
+        let has_velocity = self.added_by_day.iter().chain(&self.mastered_by_day).any(|&n| n > 0);
+        let forgotten_height = match self.forgotten.len() {
+            0 => 0,
+            n => n.min(FORGOTTEN_SHOWN) as u16 + 2,
+        };
         let right_chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(10),  // Calendar
                 Constraint::Length(3),   // Today's progress
+                Constraint::Length(if has_velocity { 6 } else { 0 }), // Learning velocity
+                Constraint::Length(forgotten_height), // Recently forgotten (hidden when empty)
                 Constraint::Min(3),      // Progress bar
             ])
             .margin(1)
@@ -124,36 +402,38 @@ impl Component for DashboardComponent {
         let stats_lines = vec![
             Line::from(vec![
                 Span::styled("📚 ", Theme::text_title()),
-                Span::styled("词汇库: ", Theme::text_normal()),
+                Span::styled(t("dash.vocabulary"), Theme::text_normal()),
                 Span::styled(
                     format!("{}", total),
                     Theme::text_title(),
                 ),
-                Span::styled(" 个单词", Theme::text_secondary()),
+                Span::styled(t("dash.words_unit"), Theme::text_secondary()),
             ]),
             Line::from(""),
             Line::from(vec![
                 Span::styled("✓ ", Theme::text_success()),
-                Span::styled("已掌握: ", Theme::text_normal()),
+                Span::styled(t("dash.mastered"), Theme::text_normal()),
                 Span::styled(
                     format!("{}", mastered),
                     Theme::text_success(),
                 ),
-                Span::styled(" 个", Theme::text_secondary()),
+                Span::styled(t("dash.unit"), Theme::text_secondary()),
             ]),
             Line::from(""),
             Line::from(vec![
                 Span::styled("⏰ ", Theme::text_warning()),
-                Span::styled("待复习: ", Theme::text_normal()),
+                Span::styled(t("dash.due"), Theme::text_normal()),
                 Span::styled(
                     format!("{}", due),
                     Theme::text_warning(),
                 ),
-                Span::styled(" 个", Theme::text_secondary()),
+                Span::styled(t("dash.unit"), Theme::text_secondary()),
             ]),
+            Line::from(""),
+            self.maturity_line(),
         ];
         let stats_widget = Paragraph::new(stats_lines)
-            .block(Theme::block_with_title(" 📊 学习统计 "))
+            .block(Theme::block_with_title(t("dash.stats_title")))
             .style(Theme::text_normal());
         frame.render_widget(stats_widget, left_chunks[0]);
 
@@ -163,7 +443,7 @@ impl Component for DashboardComponent {
         let progress_text = vec![
             Line::from(vec![
                 Span::styled("🎯 ", Theme::text_accent()),
-                Span::styled("今日已复习: ", Theme::text_normal()),
+                Span::styled(t("dash.today_reviewed"), Theme::text_normal()),
                 Span::styled(
                     format!("{}", today_reviews),
                     if today_reviews >= daily_goal { Theme::text_success() } else { Theme::text_accent() },
@@ -173,118 +453,168 @@ impl Component for DashboardComponent {
                     format!("{}", daily_goal),
                     Theme::text_title(),
                 ),
-                Span::styled(" 个", Theme::text_secondary()),
+                Span::styled(t("dash.unit"), Theme::text_secondary()),
                 Span::styled(
                     if today_reviews >= daily_goal { " ✓" } else { "" },
                     Theme::text_success(),
                 ),
             ]),
+            Line::from(vec![
+                Span::styled("📚 ", Theme::text_info()),
+                Span::styled(t("dash.new_word_source"), Theme::text_normal()),
+                match self.priority.as_ref().map(|p| &p.tag).or(self.focus_tag.as_ref()) {
+                    Some(tag) => Span::styled(
+                        tag.split_whitespace().map(tag_display_name).collect::<Vec<_>>().join(" · "),
+                        Theme::text_warning(),
+                    ),
+                    None => Span::styled(t("dash.new_word_global"), Theme::text_secondary()),
+                },
+                match &self.priority {
+                    Some(priority) => Span::styled(
+                        t_n("dash.priority_until", priority.until.format("%Y-%m-%d")),
+                        Theme::text_accent(),
+                    ),
+                    None => Span::raw(""),
+                },
+                if self.freq_band == FreqBand::All {
+                    Span::raw("")
+                } else {
+                    Span::styled(
+                        format!("{}{}", t("dash.freq_band"), self.freq_band.label()),
+                        Theme::text_info(),
+                    )
+                },
+            ]),
+            Line::from(vec![
+                Span::styled("⏸ ", Theme::text_warning()),
+                Span::styled(t("dash.skip_new"), Theme::text_normal()),
+                if self.skip_new_today {
+                    Span::styled(t("dash.on"), Theme::text_warning())
+                } else {
+                    Span::styled(t("dash.off"), Theme::text_secondary())
+                },
+                Span::styled(t("dash.skip_new_hint"), Theme::text_secondary()),
+            ]),
         ];
         let progress_widget = Paragraph::new(progress_text)
-            .block(Theme::block_with_title(" 📅 今日进度 "))
+            .block(Theme::block_with_title(t("dash.today_title")))
             .style(Theme::text_normal());
         frame.render_widget(progress_widget, left_chunks[1]);
 
         // Show completion message or instructions
-        if self.show_completion_message {
-            let completion_lines = vec![
-                Line::from(""),
-                Line::from(vec![
-                    Span::raw("    "),
-                    Span::styled(" r ", Theme::text_normal().bg(Theme::PRIMARY)),
-                    Span::raw(" Review   "),
-                    Span::styled(" w ", Theme::text_normal().bg(Theme::SUCCESS)),
-                    Span::raw(" Wordbook   "),
-                    Span::styled(" d ", Theme::text_normal().bg(Theme::WARNING)),
-                    Span::raw(" Dictionary  "),
-                ]),
-                Line::from(""),
-                Line::from(vec![
-                    Span::raw("    "),
-                    Span::styled(" f ", Theme::text_normal().bg(Color::Rgb(255, 200, 50))),
-                    Span::raw(" Favorites "),
-                    Span::styled(" h ", Theme::text_normal().bg(Theme::INFO)),
-                    Span::raw(" History    "),
-                    Span::styled(" s ", Theme::text_normal().bg(Theme::ACCENT)),
-                    Span::raw(" Statistics  "),
-                ]),
-            ];
-            let completion_msg = Paragraph::new(completion_lines)
-                .block(Theme::block_success_with_title(" 🎉 Quick Actions "))
-                .alignment(ratatui::layout::Alignment::Center);
-            frame.render_widget(completion_msg, left_chunks[2]);
+        let mut action_lines = vec![
+            Line::from(""),
+            Line::from(vec![
+                Span::raw("    "),
+                Span::styled(" r ", Theme::text_normal().bg(Theme::PRIMARY)),
+                Span::raw(format!(" {}   ", t("tab.review"))),
+                Span::styled(" w ", Theme::text_normal().bg(Theme::SUCCESS)),
+                Span::raw(format!(" {}   ", t("tab.wordbook"))),
+                Span::styled(" d ", Theme::text_normal().bg(Theme::WARNING)),
+                Span::raw(format!(" {}  ", t("tab.dictionary"))),
+            ]),
+            Line::from(""),
+            Line::from(vec![
+                Span::raw("    "),
+                Span::styled(" f ", Theme::text_normal().bg(Color::Rgb(255, 200, 50))),
+                Span::raw(format!(" {}   ", t("tab.favorites"))),
+                Span::styled(" h ", Theme::text_normal().bg(Theme::INFO)),
+                Span::raw(format!(" {}   ", t("tab.history"))),
+                Span::styled(" s ", Theme::text_normal().bg(Theme::ACCENT)),
+                Span::raw(format!(" {}  ", t("tab.statistics"))),
+            ]),
+        ];
+
+        if self.reminder_due() {
+            action_lines.push(Line::from(""));
+            action_lines.push(Line::from(vec![
+                Span::styled(t("dash.reminder"), Theme::text_warning()),
+                Span::styled(t("dash.reminder_dismiss"), Theme::text_secondary()),
+            ]));
+        }
+
+        if !self.achievements.is_empty() {
+            action_lines.push(Line::from(""));
+            action_lines.push(Line::from(vec![
+                Span::styled(t("dash.achievements"), Theme::text_secondary()),
+                Span::styled(
+                    self.achievements.iter().map(|a| t(a.label_key())).collect::<Vec<_>>().join("  "),
+                    Theme::text_accent(),
+                ),
+            ]));
+        }
+
+        // Corrupt timestamps are excluded from review, so make them visible here
+        if self.corrupt_log_count > 0 {
+            action_lines.push(Line::from(""));
+            action_lines.push(Line::from(Span::styled(
+                t_n("dash.corrupt_logs", self.corrupt_log_count),
+                Theme::text_accent(),
+            )));
+        }
+
+        // A different ECDICT release can mean different tag codes: say so instead of mis-rendering
+        if self.dict_age.is_ne() {
+            action_lines.push(Line::from(""));
+            action_lines.push(Line::from(Span::styled(
+                t_n(
+                    if self.dict_age.is_lt() { "dash.dict_older" } else { "dash.dict_newer" },
+                    ECDICT_VERSION,
+                ),
+                Theme::text_accent(),
+            )));
+        }
+
+        // Upcoming reviews exceed the daily goal: learning more now snowballs the backlog
+        if self.new_word_budget.backlogged {
+            action_lines.push(Line::from(""));
+            action_lines.push(Line::from(Span::styled(
+                t_n("dash.backlog_hint", self.new_word_budget.forecast_due),
+                Theme::text_warning(),
+            )));
+            if self.new_word_budget.overdue > 0 {
+                action_lines.push(Line::from(Span::styled(
+                    t_n("dash.backlog_overdue", self.new_word_budget.overdue),
+                    Theme::text_accent(),
+                )));
+            }
+        }
+
+        let actions_block = if self.show_completion_message {
+            Theme::block_success_with_title(t("dash.actions_done_title"))
         } else {
-            let instructions = Paragraph::new(vec![
-                Line::from(""),
-                Line::from(vec![
-                    Span::raw("    "),
-                    Span::styled(" r ", Theme::text_normal().bg(Theme::PRIMARY)),
-                    Span::raw(" Review   "),
-                    Span::styled(" w ", Theme::text_normal().bg(Theme::SUCCESS)),
-                    Span::raw(" Wordbook   "),
-                    Span::styled(" d ", Theme::text_normal().bg(Theme::WARNING)),
-                    Span::raw(" Dictionary  "),
-                ]),
-                Line::from(""),
-                Line::from(vec![
-                    Span::raw("    "),
-                    Span::styled(" f ", Theme::text_normal().bg(Color::Rgb(255, 200, 50))),
-                    Span::raw(" Favorites "),
-                    Span::styled(" h ", Theme::text_normal().bg(Theme::INFO)),
-                    Span::raw(" History    "),
-                    Span::styled(" s ", Theme::text_normal().bg(Theme::ACCENT)),
-                    Span::raw(" Statistics  "),
-                ]),
-            ])
-            .block(Theme::block_with_title(" ⌨️  Quick Actions "))
+            Theme::block_with_title(t("dash.actions_title"))
+        };
+        let actions_widget = Paragraph::new(action_lines)
+            .block(actions_block)
             .alignment(ratatui::layout::Alignment::Center);
-            frame.render_widget(instructions, left_chunks[2]);
-        }
+        frame.render_widget(actions_widget, left_chunks[2]);
 
         // === RIGHT COLUMN ===
 
         // Calendar with checkin marks
-        let today = OffsetDateTime::now_local()
-            .unwrap_or_else(|_| OffsetDateTime::now_utc())
-            .date();
+        let today = self.study_today();
         
-        // Create event store with today highlighted
-        let mut event_store = CalendarEventStore::today(
+        // Create event store with today (the study day, which may still be yesterday after midnight) highlighted
+        let mut event_store = CalendarEventStore::default();
+        event_store.add(
+            today,
             Theme::text_normal()
                 .add_modifier(Modifier::BOLD)
                 .bg(Theme::PRIMARY)
         );
 
-        // Add checkin marks for completed days
-        if let Ok(checkin_dates) = self.db.get_checkin_dates(today.year(), today.month() as u32) {
-            let checkin_style = Theme::text_success()
-                .bg(Color::Rgb(0, 50, 0));
-            
-            for date_str in checkin_dates {
-                // Parse YYYY-MM-DD format
-                let parts: Vec<&str> = date_str.split('-').collect();
-                if parts.len() == 3 {
-                    if let (Ok(y), Ok(m), Ok(d)) = (
-                        parts[0].parse::<i32>(),
-                        parts[1].parse::<u8>(),
-                        parts[2].parse::<u8>()
-                    ) {
-                        if let Ok(month) = time::Month::try_from(m) {
-                            if let Ok(parsed_date) = time::Date::from_calendar_date(y, month, d) {
-                                event_store.add(parsed_date, checkin_style);
-                            }
-                        }
-                    }
-                }
-            }
-        }
+        let legend = match self.calendar_style {
+            CalendarStyle::Checkin => self.add_checkin_marks(today, &mut event_store),
+            CalendarStyle::Heatmap => self.add_heatmap_marks(today, &mut event_store),
+        };
 
         let calendar = Monthly::new(today, event_store)
             .show_month_header(Theme::text_title())
             .show_weekdays_header(Theme::text_warning())
             .default_style(Theme::text_normal());
 
-        let calendar_block = Theme::block_with_title(" 📅 Calendar ");
+        let calendar_block = Theme::block_with_title(t("dash.calendar_title")).title_bottom(legend);
         let calendar_inner = calendar_block.inner(right_chunks[0]);
         frame.render_widget(calendar_block, right_chunks[0]);
         frame.render_widget(calendar, calendar_inner);
@@ -292,30 +622,37 @@ impl Component for DashboardComponent {
         // Today's date display
         let today_text = format!(
             "📆 {} {}",
-            today.format(&time::format_description::parse("[year]-[month]-[day]").unwrap())
+            today.format(&time::format_description::parse_borrowed::<2>("[year]-[month]-[day]").unwrap())
                 .unwrap_or_else(|_| "Unknown".to_string()),
-            today.format(&time::format_description::parse("[weekday]").unwrap())
+            today.format(&time::format_description::parse_borrowed::<2>("[weekday]").unwrap())
                 .unwrap_or_else(|_| "".to_string())
         );
         let today_widget = Paragraph::new(today_text)
-            .block(Theme::block_accent_with_title(" Today "))
+            .block(Theme::block_accent_with_title(t("dash.today")))
             .style(Theme::text_title());
         frame.render_widget(today_widget, right_chunks[1]);
 
+        if has_velocity {
+            self.render_velocity(frame, right_chunks[2]);
+        }
+        if !self.forgotten.is_empty() {
+            self.render_forgotten(frame, right_chunks[3]);
+        }
+
         // Overall learning progress bar
         let progress = if total > 0 {
             (mastered as f64 / total as f64) * 100.0
         } else {
             0.0
         };
-        let progress_title = format!(" ✓ Mastered: {} / {} ({:.1}%) ", mastered, total, progress);
+        let progress_title = t_n("dash.gauge_title", format!("{} / {} ({:.1}%)", mastered, total, progress));
         let gauge = Gauge::default()
             .block(
                 Theme::block_success().title(progress_title),
             )
             .gauge_style(Theme::text_success())
             .percent(progress as u16)
-            .label(format!("{} mastered", mastered));
-        frame.render_widget(gauge, right_chunks[2]);
+            .label(t_n("dash.gauge_label", mastered));
+        frame.render_widget(gauge, right_chunks[4]);
     }
 }
@@ -1,10 +1,13 @@
 use super::{Action, Component, Screen};
-use crate::components::common::{SearchInput, Popup};
-use crate::db::Database;
-use crate::models::{LearningLog, LearningStatus, Word};
+use crate::components::common::{content_height, definition_lines, format_translation, is_search_focus_key, max_scroll, push_section_gap, scroll_indicator, ConfirmDialog, SearchInput, Popup};
+use crate::db::{split_tags, word_has_tag, Database};
+use crate::models::{parse_exchange, tag_display_name, LearningLog, EXAM_TAGS, DictColumn, DictEnterAction, DictHlKeys, DictPosition, FreqBand, LearningStatus, ReviewDirection, SearchFocusKey, Word, WordDates};
+use crate::sm2;
 use crate::theme::Theme;
 use anyhow::Result;
-use crossterm::event::{KeyCode, KeyEvent};
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::{HashMap, HashSet};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Margin, Rect},
     style::Modifier,
@@ -15,27 +18,70 @@ use ratatui::{
     },
     Frame,
 };
-use std::collections::HashMap;
 
 #[derive(Debug, PartialEq)]
 enum Mode {
-    Normal,  // Navigation mode (j/k works)
-    Insert,  // Input mode (typing)
+    Normal,     // Navigation mode (j/k works)
+    Insert,     // Input mode (typing)
+    Reschedule, // Typing a new next review date
+    GoTo,       // Typing an exact spelling to jump to (`:`)
 }
 
-const LIST_LIMIT: usize = 30;
+/// Page size used until the first render measures the table
+const DEFAULT_PAGE_SIZE: usize = 30;
+/// Ctrl-d/Ctrl-u 详情滚动的行数
+const DETAIL_HALF_PAGE: u16 = 5;
+/// Reviews shown in the detail's rating trail (fits the detail panel on one line)
+const QUALITY_TRAIL_LEN: usize = 30;
 
-/// Parse exchange field into a readable format
-fn parse_exchange(exchange: &str) -> HashMap<&str, String> {
-    let mut result = HashMap::new();
-    for part in exchange.split('/') {
-        if let Some((key, value)) = part.split_once(':') {
-            result.insert(key, value.to_string());
+/// Ad-hoc recall test on the selected word, outside the scheduled queue
+#[derive(Debug, PartialEq)]
+enum SelfTest {
+    Hidden,   // Meaning blurred, trying to recall
+    Revealed, // Meaning shown; 1-4 logs a review, Esc leaves SM2 untouched
+}
+
+/// Replace every visible character with a blur block, keeping the text's shape
+fn blur(text: &str) -> String {
+    text.chars().map(|c| if c.is_whitespace() { c } else { '░' }).collect()
+}
+
+/// Furthest a review can be pushed back manually
+const MAX_RESCHEDULE_DAYS: i64 = 3650;
+
+/// Parse "+N" / "N" (days from now) or "YYYY-MM-DD" (local date) into a review time
+fn parse_review_date(input: &str) -> Result<DateTime<Utc>, &'static str> {
+    let input = input.trim();
+    let days = input.strip_prefix('+').unwrap_or(input);
+    if let Ok(days) = days.parse::<i64>() {
+        if !(0..=MAX_RESCHEDULE_DAYS).contains(&days) {
+            return Err("天数需在 0-3650 之间");
         }
+        return Ok(Utc::now() + Duration::days(days));
+    }
+
+    let date = NaiveDate::parse_from_str(input, "%Y-%m-%d").map_err(|_| "格式应为 +N 或 YYYY-MM-DD")?;
+    let today = Local::now().date_naive();
+    if date < today {
+        return Err("日期不能早于今天");
+    }
+    if (date - today).num_days() > MAX_RESCHEDULE_DAYS {
+        return Err("日期不能超过 10 年后");
     }
-    result
+    // Today means "due now"; later dates become due at local midnight
+    if date == today {
+        return Ok(Utc::now());
+    }
+    Local
+        .from_local_datetime(&date.and_time(NaiveTime::MIN))
+        .earliest()
+        .map(|time| time.with_timezone(&Utc))
+        .ok_or("无效的日期")
 }
 
+/// (word_id the family belongs to, family members with their learning logs)
+type FamilyCache = (Option<i64>, Vec<(Word, Option<LearningLog>)>);
+
 /// Get exchange type description
 fn exchange_type_name(key: &str) -> &str {
     match key {
@@ -84,59 +130,635 @@ fn parse_pos(pos: &str) -> String {
     }
 }
 
+/// Five-cell bar for the ease factor (1.3 → 1 cell, 2.5+ → 5 cells)
+fn ease_bar(e_factor: f64) -> String {
+    let t = ((e_factor - 1.3) / (2.5 - 1.3)).clamp(0.0, 1.0);
+    let filled = 1 + (t * 4.0).round() as usize;
+    format!("{}{}", "■".repeat(filled), "□".repeat(5 - filled))
+}
+
+/// 距下次复习的天数（按本地日期）："已到期" / "今天" / "明天" / "N天后"，第二项表示是否已逾期
+/// （到期后超过 `grace` 才算，宽限内只显示已到期、不突出）
+fn due_label(next_review: DateTime<Utc>, now: DateTime<Utc>, grace: chrono::Duration) -> (String, bool) {
+    if next_review <= now {
+        return ("已到期".to_string(), next_review + grace <= now);
+    }
+    let days = (next_review.with_timezone(&Local).date_naive() - now.with_timezone(&Local).date_naive()).num_days();
+    let label = match days {
+        0 => "今天".to_string(),
+        1 => "明天".to_string(),
+        n => format!("{}天后", n),
+    };
+    (label, false)
+}
+
+/// 归一化拼写（忽略大小写、连字符、点和空格），用来合并 ECDICT 中的近似重复词条
+fn variant_key(spelling: &str) -> String {
+    let key: String = spelling.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect();
+    if key.is_empty() { spelling.to_string() } else { key }
+}
+
+/// A dictionary row: the word and its learning log, if any
+type WordEntry = (Word, Option<LearningLog>);
+
+/// Tags of the loaded results for the legend: exam tags in EXAM_TAGS order, then any
+/// others alphabetically; at most nine so each gets a number key
+fn present_tags(results: &[WordEntry]) -> Vec<String> {
+    let found: HashSet<&str> = results
+        .iter()
+        .filter_map(|(word, _)| word.tag.as_deref())
+        .flat_map(split_tags)
+        .collect();
+    let mut others: Vec<&str> = found.iter().copied().filter(|tag| !EXAM_TAGS.contains(tag)).collect();
+    others.sort_unstable();
+    EXAM_TAGS
+        .iter()
+        .copied()
+        .filter(|tag| found.contains(tag))
+        .chain(others)
+        .take(9)
+        .map(str::to_string)
+        .collect()
+}
+
+/// Collapse results sharing a `variant_key` into their best entry (highest Collins, then Oxford).
+/// Groups keep the position of their first hit; expanded groups list the other entries after it.
+/// Returns the rows to show and the size of every group.
+fn group_variants(
+    results: &[WordEntry],
+    expanded: &HashSet<String>,
+) -> (Vec<WordEntry>, HashMap<String, usize>) {
+    let mut groups: Vec<(String, Vec<&WordEntry>)> = Vec::new();
+    let mut group_index: HashMap<String, usize> = HashMap::new();
+    for entry in results {
+        let key = variant_key(&entry.0.spelling);
+        match group_index.get(&key) {
+            Some(&index) => groups[index].1.push(entry),
+            None => {
+                group_index.insert(key.clone(), groups.len());
+                groups.push((key, vec![entry]));
+            }
+        }
+    }
+
+    let mut rows = Vec::with_capacity(results.len());
+    let mut sizes = HashMap::new();
+    for (key, mut members) in groups {
+        members.sort_by_key(|(word, _)| std::cmp::Reverse((word.collins, word.oxford)));
+        let shown = if expanded.contains(&key) { members.len() } else { 1 };
+        rows.extend(members.iter().take(shown).map(|entry| (*entry).clone()));
+        sizes.insert(key, members.len());
+    }
+    (rows, sizes)
+}
+
 pub struct DictionaryComponent {
     db: Database,
     search_input: SearchInput,
+    search_results: Vec<(Word, Option<LearningLog>)>, // Raw query results before grouping
+    expanded_variants: HashSet<String>, // variant_keys whose merged entries are shown
+    variant_counts: HashMap<String, usize>, // Group size per variant_key
     word_list: Vec<(Word, Option<LearningLog>)>,
     selected_index: usize,
     table_state: TableState,
+    page_size: usize,   // Rows that fit in the table, updated on every render
     detail_scroll: u16, // Scroll position for detail view
+    detail_max_scroll: u16, // Last scrollable line of the detail view, updated on every render
     show_popup: bool,   // Whether to show popup
     popup: Popup,       // Popup component
     mode: Mode,         // Current input mode
     searching: bool,    // Whether currently searching
     loading_frame: usize, // Loading animation frame
+    family: Option<FamilyCache>, // Cached word family of the selected word
+    dates: Option<(i64, WordDates)>, // Cached lifecycle dates of the selected word (cleared on refresh)
+    quality_trail: Option<(i64, Vec<u8>)>, // Cached recent ratings of the selected word, oldest first
+    reschedule_input: String,    // Input buffer in Reschedule mode
+    reschedule_error: Option<&'static str>,
+    goto_input: String,          // Input buffer in GoTo mode
+    add_confirm: Option<(Word, ConfirmDialog)>, // GoTo hit an ECDICT word that is not being learned yet
+    self_test: Option<SelfTest>,
+    hl_keys: DictHlKeys, // Whether h/l scroll the detail panel (setting)
+    enter_action: DictEnterAction, // What Enter does on the selected word (setting)
+    focus_key: SearchFocusKey, // Extra key toggling the search box besides i/Esc (setting)
+    freq_band: FreqBand, // Frequency band from Settings
+    band_filter: bool,   // b: only list words inside freq_band
+    show_tag_legend: bool,         // T: tag legend bar; number keys toggle its filters
+    present_tags: Vec<String>,     // Tags found in the loaded results, legend order
+    tag_filter: HashSet<String>,   // Listed words must carry every one of these tags
+    hide_mastered: bool, // Default list leaves out Mastered words (setting; m toggles for this visit)
+    hidden_mastered: i64, // Mastered words left out of the current list, for the title
+    ease_reset: Option<(i64, f64)>, // (word_id, e_factor before the last e reset), shown in the detail
+    columns: Vec<DictColumn>, // Visible table columns (setting)
+    clean_definitions: bool, // Tidy ECDICT markup in English definitions (setting)
+    remember_position: bool, // Save the cursor on leaving and restore it on return (setting)
+    overdue_grace: chrono::Duration, // Due words are highlighted as overdue only after this (setting)
+}
+
+/// 搜索框的查询语法：`def:` 英文释义、`zh:` 中文释义、`tag:` 标签，其余按拼写搜索
+enum SearchQuery<'a> {
+    Spelling(&'a str),
+    Definition(&'a str),
+    Translation(&'a str),
+    Tag(&'a str),
+}
+
+impl<'a> SearchQuery<'a> {
+    /// Unknown prefixes (and empty operands) fall back to the default search
+    fn parse(input: &'a str) -> Self {
+        let input = input.trim();
+        let Some((prefix, rest)) = input.split_once(':') else {
+            return SearchQuery::Spelling(input);
+        };
+        let rest = rest.trim();
+        if rest.is_empty() {
+            return SearchQuery::Spelling(input);
+        }
+        match prefix.to_ascii_lowercase().as_str() {
+            "def" => SearchQuery::Definition(rest),
+            "zh" => SearchQuery::Translation(rest),
+            "tag" => SearchQuery::Tag(rest),
+            _ => SearchQuery::Spelling(input),
+        }
+    }
+}
+
+/// 词条本身的内容行（拼写、词性、考试标签、释义、词形变化）和各小节标题的行号；
+/// 词典详情在后面接相关词和学习状态，复习中的查词浮窗直接用
+pub fn word_entry_lines(word: &Word, clean_definitions: bool) -> (Vec<Line<'_>>, Vec<usize>) {
+    let mut lines = vec![];
+    let mut sections = vec![]; // Header line of each ━━━ section, for popup jumps
+    
+    // Word + Phonetic
+    let mut word_line_spans = vec![
+        Span::styled(
+            &word.spelling,
+            Theme::text_headword()
+                .add_modifier(Modifier::UNDERLINED),
+        ),
+    ];
+    if let Some(phonetic) = &word.phonetic {
+        word_line_spans.push(Span::raw("  "));
+        word_line_spans.push(Span::styled(
+            format!("[ {} ]", phonetic),
+            Theme::text_secondary(),
+        ));
+    }
+    lines.push(Line::from(word_line_spans));
+    push_section_gap(&mut lines);
+    
+    // POS + Collins + Oxford
+    let mut meta_spans = vec![];
+    if let Some(pos) = &word.pos {
+        if !pos.is_empty() {
+            let pos_display = parse_pos(pos);
+            if !pos_display.is_empty() {
+                meta_spans.push(Span::styled(
+                    pos_display,
+                    Theme::text_warning(),
+                ));
+            }
+        }
+    }
+    if word.collins > 0 {
+        if !meta_spans.is_empty() {
+            meta_spans.push(Span::raw("  |  "));
+        }
+        meta_spans.push(Span::styled(
+            format!("柯林斯 {}", "★".repeat(word.collins as usize)),
+            Theme::text_info(),
+        ));
+    }
+    if word.oxford {
+        if !meta_spans.is_empty() {
+            meta_spans.push(Span::raw("  |  "));
+        }
+        meta_spans.push(Span::styled(
+            "牛津3000",
+            Theme::text_success(),
+        ));
+    }
+    if !meta_spans.is_empty() {
+        lines.push(Line::from(meta_spans));
+        push_section_gap(&mut lines);
+    }
+    
+    // Tags (考试标签)
+    if let Some(tag) = &word.tag {
+        if !tag.is_empty() {
+            let tag_display: Vec<&str> = tag.split_whitespace().map(tag_display_name).collect();
+            lines.push(Line::from(vec![
+                Span::styled(
+                    "考试: ",
+                    Theme::text_secondary(),
+                ),
+                Span::styled(
+                    tag_display.join(" · "),
+                    Theme::text_info(),
+                ),
+            ]));
+            push_section_gap(&mut lines);
+        }
+    }
+    
+    // Chinese Translation
+    if let Some(translation) = &word.translation {
+        let overridden = word.glossary.as_ref().is_some_and(|g| g.replaced_translation.is_some());
+        sections.push(lines.len());
+        lines.push(Line::from(Span::styled(
+            if overridden { "━━━ 中文释义 (📒 个人词汇表) ━━━" } else { "━━━ 中文释义 ━━━" },
+            Theme::text_title(),
+        )));
+        lines.extend(format_translation(translation));
+        push_section_gap(&mut lines);
+    }
+
+    // Personal glossary (个人词汇表): the user's note, plus the ECDICT meaning it overrode
+    if let Some(glossary) = &word.glossary {
+        sections.push(lines.len());
+        lines.push(Line::from(Span::styled(
+            "━━━ 📒 个人词汇表 ━━━",
+            Theme::text_info(),
+        )));
+        if let Some(note) = &glossary.note {
+            for line in note.lines().filter(|line| !line.trim().is_empty()) {
+                lines.push(Line::from(format!("  {}", line)));
+            }
+        }
+        if let Some(original) = &glossary.replaced_translation {
+            lines.push(Line::from(Span::styled("  ECDICT 原释义:", Theme::text_secondary())));
+            for line in original.lines().filter(|line| !line.trim().is_empty()) {
+                lines.push(Line::from(Span::styled(format!("    {}", line), Theme::text_secondary())));
+            }
+        }
+        push_section_gap(&mut lines);
+    }
+    
+    // English Definition
+    sections.push(lines.len());
+    lines.push(Line::from(Span::styled(
+        "━━━ English Definition ━━━",
+        Theme::text_warning(),
+    )));
+    for line in definition_lines(&word.definition, clean_definitions) {
+        lines.push(Line::from(format!("  {}", line)));
+    }
+    push_section_gap(&mut lines);
+    
+    // Exchange (词形变化)
+    if let Some(exchange) = &word.exchange {
+        if !exchange.is_empty() {
+            sections.push(lines.len());
+            lines.push(Line::from(Span::styled(
+                "━━━ 词形变化 ━━━",
+                Theme::text_accent(),
+            )));
+            
+            let exchange_map = parse_exchange(exchange);
+            let order = ["0", "p", "d", "i", "3", "s", "r", "t", "1"];
+            
+            for key in &order {
+                if let Some(value) = exchange_map.get(*key) {
+                    lines.push(Line::from(vec![
+                        Span::styled(
+                            format!("  {} ", exchange_type_name(key)),
+                            Theme::text_secondary(),
+                        ),
+                        Span::styled(
+                            value.clone(),
+                            Theme::text_title().add_modifier(Modifier::ITALIC),
+                        ),
+                    ]));
+                }
+            }
+            push_section_gap(&mut lines);
+        }
+    }
+
+    (lines, sections)
 }
 
 impl DictionaryComponent {
     pub fn new(db: Database) -> Result<Self> {
-        let word_list = db.get_all_words()?;
+        let hide_mastered = db.get_hide_mastered_in_dict()?;
+        let search_results = db.get_learned_words(hide_mastered)?;
+        let hl_keys = db.get_dict_hl_keys()?;
+        let enter_action = db.get_dict_enter_action()?;
+        let focus_key = db.get_search_focus_key()?;
+        let freq_band = db.get_freq_band()?;
+        let columns = db.get_dict_columns()?;
+        let clean_definitions = db.get_clean_definitions()?;
+        let remember_position = db.get_remember_dict_position()?;
+        let overdue_grace = chrono::Duration::hours(db.get_overdue_grace_hours()?);
         let mut table_state = TableState::default();
         table_state.select(Some(0));
-        Ok(Self {
+        let mut component = Self {
             db,
-            search_input: SearchInput::new().with_placeholder("Press 'i' to search...".to_string()),
-            word_list,
+            search_input: SearchInput::new()
+                .with_placeholder("Press 'i' to search...  def:释义  zh:中文  tag:cet6".to_string()),
+            search_results: Vec::new(),
+            expanded_variants: HashSet::new(),
+            variant_counts: HashMap::new(),
+            word_list: Vec::new(),
             selected_index: 0,
             table_state,
+            page_size: DEFAULT_PAGE_SIZE,
             detail_scroll: 0,
+            detail_max_scroll: 0,
             show_popup: false,
             popup: Popup::new("单词详情".to_string()),
             mode: Mode::Normal,
             searching: false,
             loading_frame: 0,
+            family: None,
+            dates: None,
+            quality_trail: None,
+            reschedule_input: String::new(),
+            reschedule_error: None,
+            goto_input: String::new(),
+            add_confirm: None,
+            self_test: None,
+            hl_keys,
+            enter_action,
+            focus_key,
+            freq_band,
+            band_filter: false,
+            show_tag_legend: false,
+            present_tags: Vec::new(),
+            tag_filter: HashSet::new(),
+            hide_mastered,
+            hidden_mastered: 0,
+            ease_reset: None,
+            columns,
+            clean_definitions,
+            remember_position,
+            overdue_grace,
+        };
+        component.set_results(search_results);
+        if remember_position {
+            if let Some(position) = component.db.get_dict_position()? {
+                component.restore_position(&position)?;
+            }
+        }
+        Ok(component)
+    }
+
+    /// Re-run the saved search and put the cursor back; a word no longer listed leaves the top row
+    fn restore_position(&mut self, position: &DictPosition) -> Result<()> {
+        if !position.query.is_empty() {
+            self.search_input.value = position.query.clone();
+            self.update_search()?;
+        }
+        if self.select_word(position.word_id, &position.spelling) {
+            self.detail_scroll = position.detail_scroll; // Clamped on the next render
+        }
+        Ok(())
+    }
+
+    /// 离开词典时记下当前搜索、选中的单词和详情滚动位置
+    pub fn save_position(&self) -> Result<()> {
+        if !self.remember_position {
+            return Ok(());
+        }
+        let Some((Word { id: Some(word_id), spelling, .. }, _)) = self.word_list.get(self.selected_index) else {
+            return Ok(());
+        };
+        self.db.set_dict_position(&DictPosition {
+            query: self.search_input.value.clone(),
+            word_id: *word_id,
+            spelling: spelling.clone(),
+            detail_scroll: self.detail_scroll,
         })
     }
 
+    /// Search box toggle key, for the footer
+    pub fn search_focus_key(&self) -> SearchFocusKey {
+        self.focus_key
+    }
+
+    /// Enter binding from Settings, for the footer
+    pub fn enter_action(&self) -> DictEnterAction {
+        self.enter_action
+    }
+
+    /// Typing into the search box (not the reschedule / go-to prompts)
+    pub fn in_search_box(&self) -> bool {
+        self.mode == Mode::Insert
+    }
+
     pub fn refresh(&mut self) -> Result<()> {
         // Refresh the word list to update favorited status
-        if self.search_input.value.is_empty() {
-            self.word_list = self.db.get_all_words()?;
-        } else {
-            self.word_list = self.db.search_words(&self.search_input.value)?;
+        self.dates = None;
+        self.quality_trail = None;
+        let results = self.run_search()?;
+        self.set_results(results);
+        Ok(())
+    }
+
+    /// 搜索该拼写并打开这个词条的详情浮窗（全局搜索跳转）
+    pub fn open_word(&mut self, word_id: i64, spelling: &str) -> Result<()> {
+        self.search_input.value = spelling.to_string();
+        self.update_search()?;
+        if self.select_word(word_id, spelling) {
+            self.show_popup = true;
+            self.popup.reset_scroll();
+        }
+        Ok(())
+    }
+
+    /// Move the cursor to `word_id` in the current list; false when it is not listed
+    fn select_word(&mut self, word_id: i64, spelling: &str) -> bool {
+        // A merged variant is only listed once its group is expanded
+        if !self.word_list.iter().any(|(word, _)| word.id == Some(word_id)) {
+            self.expanded_variants.insert(variant_key(spelling));
+            self.regroup();
+        }
+        match self.word_list.iter().position(|(word, _)| word.id == Some(word_id)) {
+            Some(index) => {
+                self.selected_index = index;
+                self.detail_scroll = 0;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// `:` 跳转：学习中的单词直接选中（当前列表里没有就按拼写搜出来），
+    /// 词典里有但还没学的询问是否加入学习，都没有就退回普通搜索
+    fn go_to_spelling(&mut self, spelling: &str) -> Result<()> {
+        if let Some(Word { id: Some(word_id), .. }) = self.db.find_learned_by_spelling(spelling)? {
+            if !self.select_word(word_id, spelling) {
+                self.search_input.value = spelling.to_string();
+                self.update_search()?;
+                self.select_word(word_id, spelling);
+            }
+            return Ok(());
+        }
+        match self.db.find_word_by_spelling(spelling)? {
+            Some(word) => {
+                let dialog = ConfirmDialog::new(
+                    "跳转到单词".to_string(),
+                    format!("「{}」还没有在学习，加入学习列表？（n 只显示搜索结果）", word.spelling),
+                );
+                self.add_confirm = Some((word, dialog));
+            }
+            None => {
+                self.search_input.value = spelling.to_string();
+                self.update_search()?;
+            }
         }
         Ok(())
     }
 
+    /// y: 加入学习并选中；n: 退回这个拼写的普通搜索结果
+    fn resolve_add_confirm(&mut self, add: bool) -> Result<()> {
+        let Some((word, _)) = self.add_confirm.take() else {
+            return Ok(());
+        };
+        let Some(word_id) = word.id else {
+            return Ok(());
+        };
+        if add {
+            self.db.init_learning_log(word_id)?;
+        }
+        self.search_input.value = word.spelling.clone();
+        self.update_search()?;
+        self.select_word(word_id, &word.spelling);
+        Ok(())
+    }
+
+    /// Store fresh query results and rebuild the grouped word list
+    fn set_results(&mut self, results: Vec<(Word, Option<LearningLog>)>) {
+        self.search_results = results;
+        self.hidden_mastered = if self.hide_mastered && self.search_input.value.is_empty() {
+            self.db.learning_status_counts().map(|(_, _, mastered)| mastered).unwrap_or(0)
+        } else {
+            0
+        };
+        self.present_tags = present_tags(&self.search_results);
+        self.tag_filter.retain(|tag| self.present_tags.contains(tag));
+        self.regroup();
+    }
+
+    fn regroup(&mut self) {
+        (self.word_list, self.variant_counts) = if self.band_filter || !self.tag_filter.is_empty() {
+            let filtered: Vec<WordEntry> = self
+                .search_results
+                .iter()
+                .filter(|(word, _)| !self.band_filter || self.freq_band.contains(word.frq, word.bnc))
+                .filter(|(word, _)| self.tag_filter.iter().all(|tag| word_has_tag(word, tag)))
+                .cloned()
+                .collect();
+            group_variants(&filtered, &self.expanded_variants)
+        } else {
+            group_variants(&self.search_results, &self.expanded_variants)
+        };
+        self.selected_index = self.selected_index.min(self.word_list.len().saturating_sub(1));
+    }
+
+    /// 1-9: 切换图例里第 n 个标签的筛选，0 清除全部
+    fn toggle_tag_filter(&mut self, digit: u32) {
+        if digit == 0 {
+            self.tag_filter.clear();
+        } else if let Some(tag) = self.present_tags.get(digit as usize - 1) {
+            if !self.tag_filter.remove(tag) {
+                self.tag_filter.insert(tag.clone());
+            }
+        } else {
+            return;
+        }
+        self.selected_index = 0;
+        self.detail_scroll = 0;
+        self.regroup();
+    }
+
+    /// Legend bar: "[1] 中考 12  [2] 高考 30 …", active filters highlighted
+    fn tag_legend_line(&self) -> Line<'static> {
+        if self.present_tags.is_empty() {
+            return Line::from(Span::styled(" 当前列表没有考试标签", Theme::text_secondary()));
+        }
+        let mut spans = vec![Span::styled(" 标签 ", Theme::text_secondary())];
+        for (index, tag) in self.present_tags.iter().enumerate() {
+            let count = self.search_results.iter().filter(|(word, _)| word_has_tag(word, tag)).count();
+            let style = if self.tag_filter.contains(tag) {
+                Theme::text_normal().bg(Theme::PRIMARY).add_modifier(Modifier::BOLD)
+            } else {
+                Theme::text_normal()
+            };
+            spans.push(Span::styled(format!("[{}]", index + 1), Theme::text_warning()));
+            spans.push(Span::styled(format!(" {} {} ", tag_display_name(tag), count), style));
+            spans.push(Span::raw(" "));
+        }
+        spans.push(Span::styled("0 清除  T 收起", Theme::text_secondary()));
+        Line::from(spans)
+    }
+
+    /// Spelling column: "+N" on a collapsed group, "↳" on the extra entries of an expanded one
+    fn spelling_cell<'a>(&self, index: usize, word: &'a Word) -> Line<'a> {
+        let key = variant_key(&word.spelling);
+        let is_variant = index > 0
+            && self.word_list.get(index - 1).is_some_and(|(prev, _)| variant_key(&prev.spelling) == key);
+        if is_variant {
+            return Line::from(vec![
+                Span::styled("↳ ", Theme::text_secondary()),
+                Span::styled(word.spelling.as_str(), Theme::text_normal()),
+            ]);
+        }
+        let mut spans = vec![Span::styled(word.spelling.as_str(), Theme::text_title())];
+        if word.glossary.is_some() {
+            spans.push(Span::styled(" ✎", Theme::text_info())); // Personal glossary entry applied
+        }
+        match self.variant_counts.get(&key) {
+            Some(&count) if count > 1 && self.expanded_variants.contains(&key) => {
+                spans.push(Span::styled(" ▾", Theme::text_secondary()));
+            }
+            Some(&count) if count > 1 => {
+                spans.push(Span::styled(format!(" +{}", count - 1), Theme::text_secondary()));
+            }
+            _ => {}
+        }
+        Line::from(spans)
+    }
+
+    /// v: 展开/收起选中词条合并掉的近似变体
+    fn toggle_variants(&mut self) {
+        let Some((word, _)) = self.word_list.get(self.selected_index) else {
+            return;
+        };
+        let key = variant_key(&word.spelling);
+        if self.variant_counts.get(&key).copied().unwrap_or(1) < 2 {
+            return;
+        }
+        if !self.expanded_variants.remove(&key) {
+            self.expanded_variants.insert(key.clone());
+        }
+        self.regroup();
+        // Keep the cursor on the group's best entry (its first row)
+        if let Some(index) = self.word_list.iter().position(|(word, _)| variant_key(&word.spelling) == key) {
+            self.selected_index = index;
+        }
+        self.detail_scroll = 0;
+    }
+
+    /// Run the current search box query (all words when empty)
+    fn run_search(&self) -> Result<Vec<(Word, Option<LearningLog>)>> {
+        if self.search_input.value.is_empty() {
+            return self.db.get_learned_words(self.hide_mastered);
+        }
+        match SearchQuery::parse(&self.search_input.value) {
+            SearchQuery::Spelling(query) => self.db.search_words(query),
+            SearchQuery::Definition(query) => self.db.search_by_definition(query),
+            SearchQuery::Translation(query) => self.db.search_by_translation(query),
+            SearchQuery::Tag(tag) => self.db.search_by_tag(tag),
+        }
+    }
+
     fn update_search(&mut self) -> Result<()> {
         self.searching = true;
         
-        if self.search_input.value.is_empty() {
-            self.word_list = self.db.get_all_words()?;
-        } else {
-            self.word_list = self.db.search_words(&self.search_input.value)?;
-        }
+        let results = self.run_search()?;
+        self.expanded_variants.clear();
         self.selected_index = 0;
+        self.set_results(results);
         
         self.searching = false;
         Ok(())
@@ -145,7 +767,6 @@ impl DictionaryComponent {
     fn select_next(&mut self) {
         if !self.word_list.is_empty() {
             self.selected_index = (self.selected_index + 1).min(self.word_list.len() - 1);
-            self.table_state.select(Some(self.selected_index % LIST_LIMIT));
             self.detail_scroll = 0;
         }
     }
@@ -153,7 +774,6 @@ impl DictionaryComponent {
     fn select_previous(&mut self) {
         if !self.word_list.is_empty() && self.selected_index > 0 {
             self.selected_index -= 1;
-            self.table_state.select(Some(self.selected_index % LIST_LIMIT));
             self.detail_scroll = 0;
         }
     }
@@ -161,7 +781,6 @@ impl DictionaryComponent {
     fn select_first(&mut self) {
         if !self.word_list.is_empty() {
             self.selected_index = 0;
-            self.table_state.select(Some(0));
             self.detail_scroll = 0;
         }
     }
@@ -169,150 +788,73 @@ impl DictionaryComponent {
     fn select_last(&mut self) {
         if !self.word_list.is_empty() {
             self.selected_index = self.word_list.len() - 1;
-            self.table_state.select(Some(self.selected_index % LIST_LIMIT));
             self.detail_scroll = 0;
         }
     }
 
-    /// 生成单词详情的内容行（用于浮窗和详情面板）
-    fn build_detail_lines<'a>(&self, word: &'a Word, log: &Option<LearningLog>) -> Vec<Line<'a>> {
-        let mut lines = vec![];
-        
-        // Word + Phonetic
-        let mut word_line_spans = vec![
-            Span::styled(
-                &word.spelling,
-                Theme::text_title()
-                    .add_modifier(Modifier::UNDERLINED),
-            ),
-        ];
-        if let Some(phonetic) = &word.phonetic {
-            word_line_spans.push(Span::raw("  "));
-            word_line_spans.push(Span::styled(
-                format!("[ {} ]", phonetic),
-                Theme::text_secondary(),
-            ));
-        }
-        lines.push(Line::from(word_line_spans));
-        lines.push(Line::from(""));
-        
-        // POS + Collins + Oxford
-        let mut meta_spans = vec![];
-        if let Some(pos) = &word.pos {
-            if !pos.is_empty() {
-                let pos_display = parse_pos(pos);
-                if !pos_display.is_empty() {
-                    meta_spans.push(Span::styled(
-                        pos_display,
-                        Theme::text_warning(),
-                    ));
-                }
-            }
-        }
-        if word.collins > 0 {
-            if !meta_spans.is_empty() {
-                meta_spans.push(Span::raw("  |  "));
-            }
-            meta_spans.push(Span::styled(
-                format!("柯林斯 {}", "★".repeat(word.collins as usize)),
-                Theme::text_info(),
-            ));
-        }
-        if word.oxford {
-            if !meta_spans.is_empty() {
-                meta_spans.push(Span::raw("  |  "));
-            }
-            meta_spans.push(Span::styled(
-                "牛津3000",
-                Theme::text_success(),
-            ));
-        }
-        if !meta_spans.is_empty() {
-            lines.push(Line::from(meta_spans));
-            lines.push(Line::from(""));
-        }
-        
-        // Tags (考试标签)
-        if let Some(tag) = &word.tag {
-            if !tag.is_empty() {
-                let tags: Vec<&str> = tag.split_whitespace().collect();
-                let tag_display: Vec<String> = tags.iter().map(|t| {
-                    match *t {
-                        "zk" => "中考",
-                        "gk" => "高考",
-                        "cet4" => "CET-4",
-                        "cet6" => "CET-6",
-                        "ky" => "考研",
-                        "toefl" => "TOEFL",
-                        "ielts" => "IELTS",
-                        "gre" => "GRE",
-                        _ => t,
-                    }.to_string()
-                }).collect();
-                lines.push(Line::from(vec![
-                    Span::styled(
-                        "考试: ",
-                        Theme::text_secondary(),
-                    ),
-                    Span::styled(
-                        tag_display.join(" · "),
-                        Theme::text_info(),
-                    ),
-                ]));
-                lines.push(Line::from(""));
-            }
-        }
-        
-        // Chinese Translation
-        if let Some(translation) = &word.translation {
-            lines.push(Line::from(Span::styled(
-                "━━━ 中文释义 ━━━",
-                Theme::text_title(),
-            )));
-            for line in translation.lines() {
-                if !line.trim().is_empty() {
-                    lines.push(Line::from(format!("  {}", line)));
-                }
-            }
-            lines.push(Line::from(""));
+    /// 加载当前选中单词的相关词（按 word_id 缓存，避免每帧查询数据库）
+    fn load_family(&mut self) {
+        let Some((word, _)) = self.word_list.get(self.selected_index) else {
+            return;
+        };
+        if self.family.as_ref().is_some_and(|(id, _)| *id == word.id) {
+            return;
         }
-        
-        // English Definition
-        lines.push(Line::from(Span::styled(
-            "━━━ English Definition ━━━",
-            Theme::text_warning(),
-        )));
-        for line in word.definition.lines() {
-            if !line.trim().is_empty() {
-                lines.push(Line::from(format!("  {}", line)));
-            }
+        let word_id = word.id;
+        let members = self.db.get_word_family(word).unwrap_or_default();
+        self.family = Some((word_id, members));
+    }
+
+    /// 加载当前选中单词的开始学习/掌握时间（同样按 word_id 缓存）
+    fn load_dates(&mut self) {
+        let Some((Word { id: Some(word_id), .. }, Some(_))) = self.word_list.get(self.selected_index) else {
+            return;
+        };
+        if self.dates.is_some_and(|(id, _)| id == *word_id) {
+            return;
         }
-        lines.push(Line::from(""));
-        
-        // Exchange (词形变化)
-        if let Some(exchange) = &word.exchange {
-            if !exchange.is_empty() {
+        let word_id = *word_id;
+        self.dates = Some((word_id, self.db.get_word_dates(word_id).unwrap_or_default()));
+        self.quality_trail = Some((word_id, self.db.get_word_history(word_id, QUALITY_TRAIL_LEN).unwrap_or_default()));
+    }
+
+    /// 生成单词详情的内容行（用于浮窗和详情面板），以及各小节标题所在的行号
+    fn build_detail_lines<'a>(&self, word: &'a Word, log: &Option<LearningLog>) -> (Vec<Line<'a>>, Vec<usize>) {
+        let (mut lines, mut sections) = word_entry_lines(word, self.clean_definitions);
+
+        // Word family (相关词)
+        if let Some((family_id, members)) = &self.family {
+            if *family_id == word.id && !members.is_empty() {
+                sections.push(lines.len());
                 lines.push(Line::from(Span::styled(
-                    "━━━ 词形变化 ━━━",
-                    Theme::text_accent(),
+                    "━━━ 相关词 ━━━",
+                    Theme::text_info(),
                 )));
-                
-                let exchange_map = parse_exchange(exchange);
-                let order = ["0", "p", "d", "i", "3", "s", "r", "t", "1"];
-                
-                for key in &order {
-                    if let Some(value) = exchange_map.get(*key) {
-                        lines.push(Line::from(vec![
-                            Span::styled(
-                                format!("  {} ", exchange_type_name(key)),
-                                Theme::text_secondary(),
-                            ),
-                            Span::styled(
-                                value.clone(),
-                                Theme::text_title().add_modifier(Modifier::ITALIC),
-                            ),
-                        ]));
+                for (member, member_log) in members {
+                    let mut spans = vec![
+                        Span::raw("  "),
+                        Span::styled(member.spelling.clone(), Theme::text_title()),
+                    ];
+                    if let Some(member_log) = member_log {
+                        let (label, style) = match member_log.status {
+                            LearningStatus::New => ("待学", Theme::text_secondary()),
+                            LearningStatus::Learning => ("学习中", Theme::text_warning()),
+                            LearningStatus::Mastered => ("已掌握", Theme::text_success()),
+                        };
+                        spans.push(Span::styled(format!(" [{}]", label), style));
                     }
+                    if let Some(translation) = &member.translation {
+                        let first_meaning: String = translation
+                            .lines()
+                            .next()
+                            .unwrap_or("")
+                            .chars()
+                            .take(30)
+                            .collect();
+                        spans.push(Span::raw("  "));
+                        spans.push(Span::styled(first_meaning, Theme::text_secondary()));
+                    }
+                    lines.push(Line::from(spans));
                 }
                 lines.push(Line::from(""));
             }
@@ -342,6 +884,7 @@ impl DictionaryComponent {
 
         // Learning status
         if let Some(log) = log {
+            sections.push(lines.len());
             lines.push(Line::from(Span::styled(
                 "━━━ 学习状态 ━━━",
                 Theme::text_success(),
@@ -360,23 +903,83 @@ impl DictionaryComponent {
                     },
                 ),
             ]));
-            lines.push(Line::from(vec![
+            let mut counts_line = vec![
                 Span::styled(
                     format!("复习次数: {} | 间隔: {} 天 | 记忆因子: {:.2}", 
                         log.repetition, log.interval, log.e_factor),
                     Theme::text_secondary(),
                 ),
+            ];
+            match self.ease_reset {
+                Some((reset_id, before)) if word.id == Some(reset_id) => counts_line.push(Span::styled(
+                    format!("  (已从 {:.2} 重置)", before),
+                    Theme::text_success(),
+                )),
+                _ => counts_line.push(Span::styled("  (e: 重置)", Theme::text_secondary())),
+            }
+            lines.push(Line::from(counts_line));
+            lines.push(Line::from(vec![
+                Span::styled("下次复习: ", Theme::text_secondary()),
+                Span::styled(
+                    log.next_review.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string(),
+                    Theme::text_title(),
+                ),
+                Span::styled("  (r: 调整)", Theme::text_secondary()),
             ]));
+            let dates = match self.dates {
+                Some((dates_id, dates)) if word.id == Some(dates_id) => dates,
+                _ => WordDates::default(),
+            };
+            let format_date = |date: Option<DateTime<Utc>>| {
+                date.map_or("-".to_string(), |date| date.with_timezone(&Local).format("%Y-%m-%d").to_string())
+            };
+            lines.push(Line::from(Span::styled(
+                format!("开始学习: {} | 掌握于: {}", format_date(dates.added_at), format_date(dates.mastered_at)),
+                Theme::text_secondary(),
+            )));
+            // 评分轨迹：一格一次复习，越高越好（没复习过的新词不显示）
+            if let Some((_, trail)) = self.quality_trail.as_ref().filter(|(id, trail)| word.id == Some(*id) && !trail.is_empty()) {
+                let mut spans = vec![Span::styled("评分轨迹: ", Theme::text_secondary())];
+                spans.extend(trail.iter().map(|quality| {
+                    let (block, style) = match quality {
+                        1 => ("▂", Theme::text_accent()),
+                        2 => ("▄", Theme::text_warning()),
+                        3 => ("▆", Theme::text_success()),
+                        _ => ("█", Theme::text_info()),
+                    };
+                    Span::styled(block, style)
+                }));
+                spans.push(Span::styled(format!("  (最近 {} 次)", trail.len()), Theme::text_secondary()));
+                lines.push(Line::from(spans));
+            }
         }
 
-        lines
+        (lines, sections)
+    }
+
+    fn scroll_detail_down(&mut self, lines: u16) {
+        self.detail_scroll = self.detail_scroll.saturating_add(lines).min(self.detail_max_scroll);
     }
 
     fn handle_normal_mode(&mut self, key: KeyEvent) -> Result<Action> {
+        // Ctrl-d/Ctrl-u 按半屏滚动详情，需在普通字母键之前匹配
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            match key.code {
+                KeyCode::Char('d') => {
+                    self.scroll_detail_down(DETAIL_HALF_PAGE);
+                    return Ok(Action::None);
+                }
+                KeyCode::Char('u') => {
+                    self.detail_scroll = self.detail_scroll.saturating_sub(DETAIL_HALF_PAGE);
+                    return Ok(Action::None);
+                }
+                _ => {}
+            }
+        }
         match key.code {
             KeyCode::Char('q') => Ok(Action::NavigateTo(Screen::Dashboard)),
             KeyCode::Esc => Ok(Action::NavigateTo(Screen::Dashboard)),
-            KeyCode::Tab | KeyCode::Char('i') => {
+            code if code == KeyCode::Char('i') || is_search_focus_key(self.focus_key, code) => {
                 // Enter insert mode
                 self.mode = Mode::Insert;
                 Ok(Action::None)
@@ -390,11 +993,99 @@ impl DictionaryComponent {
                 }
                 Ok(Action::None)
             }
+            KeyCode::Char('t') => {
+                // Self-test only makes sense for words already being learned
+                if matches!(self.word_list.get(self.selected_index), Some((_, Some(_)))) {
+                    self.self_test = Some(SelfTest::Hidden);
+                    self.detail_scroll = 0;
+                }
+                Ok(Action::None)
+            }
+            KeyCode::Char('r') => {
+                // Only words already in the learning log have a schedule to override
+                if matches!(self.word_list.get(self.selected_index), Some((_, Some(_)))) {
+                    self.mode = Mode::Reschedule;
+                    self.reschedule_input = "+1".to_string();
+                    self.reschedule_error = None;
+                }
+                Ok(Action::None)
+            }
+            KeyCode::Char(':') => {
+                self.mode = Mode::GoTo;
+                self.goto_input.clear();
+                Ok(Action::None)
+            }
+            KeyCode::Char('v') => {
+                self.toggle_variants();
+                Ok(Action::None)
+            }
+            KeyCode::Char('T') => {
+                self.show_tag_legend = !self.show_tag_legend;
+                Ok(Action::None)
+            }
+            KeyCode::Char(c @ '0'..='9') if self.show_tag_legend => {
+                self.toggle_tag_filter(c.to_digit(10).unwrap_or(0));
+                Ok(Action::None)
+            }
+            KeyCode::Char('b') => {
+                // 按设置中的词频范围筛选当前列表（不限词频时没有效果）
+                self.band_filter = !self.band_filter && self.freq_band != FreqBand::All;
+                self.selected_index = 0;
+                self.regroup();
+                Ok(Action::None)
+            }
+            KeyCode::Char('m') => {
+                // 临时显示/隐藏已掌握的单词（只影响不带搜索词的默认列表）
+                self.hide_mastered = !self.hide_mastered;
+                self.selected_index = 0;
+                let results = self.run_search()?;
+                self.set_results(results);
+                Ok(Action::None)
+            }
+            KeyCode::Char('R') => {
+                // Drill the selected word right away; the review screen returns here
+                if let Some(word_id) = self.word_list.get(self.selected_index).and_then(|(word, _)| word.id) {
+                    return Ok(Action::StartSingleReview(word_id));
+                }
+                Ok(Action::None)
+            }
+            KeyCode::Char('e') => {
+                // Reset ease only; the schedule stays as it is
+                if let Some((Word { id: Some(word_id), .. }, Some(log))) = self.word_list.get(self.selected_index) {
+                    self.ease_reset = Some((*word_id, log.e_factor));
+                    self.db.reset_ease(*word_id)?;
+                    self.refresh()?;
+                }
+                Ok(Action::None)
+            }
+            KeyCode::Char('u') => {
+                // Un-master: bring a mastered word back into review
+                if let Some((word, Some(log))) = self.word_list.get(self.selected_index) {
+                    if let (Some(word_id), LearningStatus::Mastered) = (word.id, log.status) {
+                        self.db.unmaster_word(word_id)?;
+                        self.refresh()?;
+                    }
+                }
+                Ok(Action::None)
+            }
             KeyCode::Enter => {
-                // Open popup for selected word
-                if !self.word_list.is_empty() {
-                    self.show_popup = true;
-                    self.popup.reset_scroll();
+                let Some((word, log)) = self.word_list.get(self.selected_index) else {
+                    return Ok(Action::None);
+                };
+                match (self.enter_action, word.id) {
+                    (DictEnterAction::OpenDetail, _) => {
+                        self.show_popup = true;
+                        self.popup.reset_scroll();
+                    }
+                    (DictEnterAction::StartReview, Some(word_id)) => return Ok(Action::StartSingleReview(word_id)),
+                    // Words already in the learning log keep their schedule
+                    (DictEnterAction::AddToLearning, Some(word_id)) if log.is_none() => {
+                        let spelling = word.spelling.clone();
+                        self.db.init_learning_log(word_id)?;
+                        self.refresh()?;
+                        self.select_word(word_id, &spelling);
+                    }
+                    _ => {}
                 }
                 Ok(Action::None)
             }
@@ -406,12 +1097,20 @@ impl DictionaryComponent {
                 self.select_next();
                 Ok(Action::None)
             }
-            KeyCode::Left | KeyCode::Char('h') => {
+            KeyCode::Left | KeyCode::Char('<') => {
                 self.detail_scroll = self.detail_scroll.saturating_sub(1);
                 Ok(Action::None)
             }
-            KeyCode::Right | KeyCode::Char('l') => {
-                self.detail_scroll = self.detail_scroll.saturating_add(1);
+            KeyCode::Right | KeyCode::Char('>') => {
+                self.scroll_detail_down(1);
+                Ok(Action::None)
+            }
+            KeyCode::Char('h') if self.hl_keys == DictHlKeys::ScrollDetail => {
+                self.detail_scroll = self.detail_scroll.saturating_sub(1);
+                Ok(Action::None)
+            }
+            KeyCode::Char('l') if self.hl_keys == DictHlKeys::ScrollDetail => {
+                self.scroll_detail_down(1);
                 Ok(Action::None)
             }
             KeyCode::Home | KeyCode::Char('g') => {
@@ -423,13 +1122,13 @@ impl DictionaryComponent {
                 Ok(Action::None)
             }
             KeyCode::PageUp => {
-                for _ in 0..10 {
+                for _ in 0..self.page_size {
                     self.select_previous();
                 }
                 Ok(Action::None)
             }
             KeyCode::PageDown => {
-                for _ in 0..10 {
+                for _ in 0..self.page_size {
                     self.select_next();
                 }
                 Ok(Action::None)
@@ -438,14 +1137,152 @@ impl DictionaryComponent {
         }
     }
     
+    /// 自测：Space 显示释义，揭晓后可选择 1-4 记录为一次复习
+    fn handle_self_test(&mut self, key: KeyEvent) -> Result<Action> {
+        match (key.code, &self.self_test) {
+            (KeyCode::Esc | KeyCode::Char('q'), _) => self.self_test = None,
+            (KeyCode::Char(' ') | KeyCode::Enter, Some(SelfTest::Hidden)) => {
+                self.self_test = Some(SelfTest::Revealed);
+            }
+            (KeyCode::Char(c @ '1'..='4'), Some(SelfTest::Revealed)) => {
+                let quality = c as u8 - b'0';
+                if let Some((Word { id: Some(word_id), .. }, Some(log))) =
+                    self.word_list.get(self.selected_index)
+                {
+                    let word_id = *word_id;
+                    let mut log = log.clone();
+                    sm2::process_review(&mut log, quality, self.db.get_mastered_review()?, self.db.get_mastery_rule()?);
+                    self.db.update_log(&log)?;
+                    self.db.add_review_history(word_id, quality, &log, None, Some(ReviewDirection::EnToZh), "self_test")?;
+                    let _ = self.db.record_checkin_if_goal_met();
+                    self.refresh()?;
+                }
+                self.self_test = None;
+            }
+            // Same scroll keys as normal mode: h/l only when the setting binds them
+            (KeyCode::Left | KeyCode::Char('<'), _) => {
+                self.detail_scroll = self.detail_scroll.saturating_sub(1);
+            }
+            (KeyCode::Right | KeyCode::Char('>'), _) => {
+                self.scroll_detail_down(1);
+            }
+            (KeyCode::Char('h'), _) if self.hl_keys == DictHlKeys::ScrollDetail => {
+                self.detail_scroll = self.detail_scroll.saturating_sub(1);
+            }
+            (KeyCode::Char('l'), _) if self.hl_keys == DictHlKeys::ScrollDetail => {
+                self.scroll_detail_down(1);
+            }
+            _ => {}
+        }
+        Ok(Action::None)
+    }
+
+    /// 自测未揭晓时的详情：只显示单词，释义模糊处理
+    fn build_self_test_lines<'a>(&self, word: &'a Word) -> Vec<Line<'a>> {
+        let mut lines = vec![Line::from(Span::styled(
+            &word.spelling,
+            Theme::text_title().add_modifier(Modifier::UNDERLINED),
+        ))];
+        if let Some(phonetic) = &word.phonetic {
+            lines.push(Line::from(Span::styled(
+                format!("[ {} ]", phonetic),
+                Theme::text_secondary(),
+            )));
+        }
+        lines.push(Line::from(""));
+
+        if let Some(translation) = &word.translation {
+            lines.push(Line::from(Span::styled("━━━ 中文释义 ━━━", Theme::text_title())));
+            for line in translation.lines().filter(|l| !l.trim().is_empty()) {
+                lines.push(Line::from(Span::styled(
+                    format!("  {}", blur(line)),
+                    Theme::text_secondary(),
+                )));
+            }
+            lines.push(Line::from(""));
+        }
+
+        lines.push(Line::from(Span::styled("━━━ English Definition ━━━", Theme::text_warning())));
+        for line in definition_lines(&word.definition, self.clean_definitions) {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", blur(&line)),
+                Theme::text_secondary(),
+            )));
+        }
+
+        lines
+    }
+
+    /// 手动设置下次复习时间（不经过 SM2）
+    fn handle_reschedule_mode(&mut self, key: KeyEvent) -> Result<Action> {
+        match key.code {
+            KeyCode::Esc => {
+                self.mode = Mode::Normal;
+                self.reschedule_input.clear();
+            }
+            KeyCode::Enter => match parse_review_date(&self.reschedule_input) {
+                Ok(next_review) => {
+                    if let Some((Word { id: Some(word_id), .. }, Some(_))) =
+                        self.word_list.get(self.selected_index)
+                    {
+                        self.db.set_next_review(*word_id, next_review)?;
+                        self.refresh()?;
+                    }
+                    self.mode = Mode::Normal;
+                    self.reschedule_input.clear();
+                }
+                Err(message) => self.reschedule_error = Some(message),
+            },
+            KeyCode::Char(c) if c.is_ascii_digit() || c == '+' || c == '-' => {
+                if self.reschedule_input.len() < 10 {
+                    self.reschedule_input.push(c);
+                }
+                self.reschedule_error = None;
+            }
+            KeyCode::Backspace => {
+                self.reschedule_input.pop();
+                self.reschedule_error = None;
+            }
+            _ => {}
+        }
+        Ok(Action::None)
+    }
+
+    fn handle_goto_mode(&mut self, key: KeyEvent) -> Result<Action> {
+        match key.code {
+            KeyCode::Esc => {
+                self.mode = Mode::Normal;
+                self.goto_input.clear();
+            }
+            KeyCode::Enter => {
+                let spelling = self.goto_input.trim().to_string();
+                self.mode = Mode::Normal;
+                self.goto_input.clear();
+                if !spelling.is_empty() {
+                    self.go_to_spelling(&spelling)?;
+                }
+            }
+            KeyCode::Char(c) if self.goto_input.chars().count() < 64 => {
+                self.goto_input.push(c);
+            }
+            KeyCode::Backspace => {
+                self.goto_input.pop();
+            }
+            _ => {}
+        }
+        Ok(Action::None)
+    }
+
     fn handle_insert_mode(&mut self, key: KeyEvent) -> Result<Action> {
         match key.code {
-            KeyCode::Tab | KeyCode::Esc => {
+            code if code == KeyCode::Esc || is_search_focus_key(self.focus_key, code) => {
                 // Exit insert mode and clear search if empty
                 self.mode = Mode::Normal;
                 if self.search_input.value.is_empty() {
-                    self.word_list = self.db.get_all_words()?;
+                    let results = self.run_search()?;
+                    self.expanded_variants.clear();
                     self.selected_index = 0;
+                    self.set_results(results);
                 }
                 Ok(Action::None)
             }
@@ -473,6 +1310,10 @@ impl DictionaryComponent {
 }
 
 impl Component for DictionaryComponent {
+    fn is_typing(&self) -> bool {
+        self.mode != Mode::Normal
+    }
+
     fn handle_key(&mut self, key: KeyEvent) -> Result<Action> {
         // 如果浮窗打开，处理浮窗的键位
         if self.show_popup {
@@ -490,13 +1331,30 @@ impl Component for DictionaryComponent {
                     self.popup.scroll_up();
                     Ok(Action::None)
                 }
+                KeyCode::Tab => {
+                    self.popup.next_section();
+                    Ok(Action::None)
+                }
+                KeyCode::BackTab => {
+                    self.popup.prev_section();
+                    Ok(Action::None)
+                }
                 _ => Ok(Action::None),
             }
+        } else if let Some((_, dialog)) = &self.add_confirm {
+            if let Some(add) = dialog.handle_key(key) {
+                self.resolve_add_confirm(add)?;
+            }
+            Ok(Action::None)
+        } else if self.self_test.is_some() {
+            self.handle_self_test(key)
         } else {
             // Normal mode vs Insert mode
             match self.mode {
                 Mode::Normal => self.handle_normal_mode(key),
                 Mode::Insert => self.handle_insert_mode(key),
+                Mode::Reschedule => self.handle_reschedule_mode(key),
+                Mode::GoTo => self.handle_goto_mode(key),
             }
         }
     }
@@ -506,6 +1364,8 @@ impl Component for DictionaryComponent {
         if self.searching {
             self.loading_frame = self.loading_frame.wrapping_add(1);
         }
+        self.load_family();
+        self.load_dates();
         
         frame.render_widget(Theme::block_default(), area);
 
@@ -513,6 +1373,7 @@ impl Component for DictionaryComponent {
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(3),  // Search input
+                Constraint::Length(if self.show_tag_legend { 1 } else { 0 }), // Tag legend / filter bar
                 Constraint::Min(10),    // Word table
                 Constraint::Length(20), // Selected word detail (increased from 8 to 20)
             ])
@@ -521,8 +1382,9 @@ impl Component for DictionaryComponent {
 
         // Search input with mode indicator
         let mode_indicator = match self.mode {
-            Mode::Normal => "[Tab to open]",
-            Mode::Insert => "[Enter to search]",
+            Mode::Normal => format!("[{} to open]", self.focus_key.open_hint()),
+            Mode::Insert | Mode::GoTo => "[Enter to search]".to_string(),
+            Mode::Reschedule => "[Enter to reschedule]".to_string(),
         };
         
         let loading_animation = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
@@ -541,12 +1403,12 @@ impl Component for DictionaryComponent {
         
         let search_widget = Paragraph::new(if self.search_input.value.is_empty() {
             if self.mode == Mode::Insert {
-                "Type and press Enter to search..."
+                "Type and press Enter to search...".to_string()
             } else {
-                "Press Tab to open search..."
+                format!("Press {} to open search...", self.focus_key.open_hint())
             }
         } else {
-            &self.search_input.value
+            self.search_input.value.clone()
         })
         .block(search_block)
         .style(if self.search_input.value.is_empty() {
@@ -557,16 +1419,43 @@ impl Component for DictionaryComponent {
         
         frame.render_widget(search_widget, layout[0]);
 
+        // The reschedule prompt temporarily replaces the search bar
+        if self.mode == Mode::Reschedule {
+            let title = match self.reschedule_error {
+                Some(error) => format!(" 下次复习 (+N 天 / YYYY-MM-DD) - {} ", error),
+                None => " 下次复习 (+N 天 / YYYY-MM-DD) - Enter 确认 | Esc 取消 ".to_string(),
+            };
+            let prompt = Paragraph::new(format!("{}_", self.reschedule_input))
+                .block(Theme::block_accent().title(title))
+                .style(Theme::text_warning());
+            frame.render_widget(prompt, layout[0]);
+        }
+        if self.mode == Mode::GoTo {
+            let prompt = Paragraph::new(format!(":{}_", self.goto_input))
+                .block(Theme::block_accent().title(" 跳转到单词（完整拼写）- Enter 跳转 | Esc 取消 "))
+                .style(Theme::text_warning());
+            frame.render_widget(prompt, layout[0]);
+        }
+
+        if self.show_tag_legend {
+            frame.render_widget(Paragraph::new(self.tag_legend_line()), layout[1]);
+        }
+
         // Word table with scrollbar
-        let page = self.selected_index / LIST_LIMIT;
+        // Page size follows the terminal height: borders (2) + header (1) are not rows
+        self.page_size = (layout[2].height.saturating_sub(3) as usize).max(1);
+        let page = self.selected_index / self.page_size;
+        self.table_state.select(Some(self.selected_index % self.page_size));
         let items_len = self.word_list.len();
+        let now = Utc::now();
 
         let rows: Vec<Row> = self
             .word_list
             .iter()
-            .skip(page * LIST_LIMIT)
-            .take(LIST_LIMIT)
-            .map(|(word, log)| {
+            .enumerate()
+            .skip(page * self.page_size)
+            .take(self.page_size)
+            .map(|(index, (word, log))| {
                 let status_symbol = if let Some(log) = log {
                     match log.status {
                         LearningStatus::New => "◯",
@@ -599,41 +1488,95 @@ impl Component for DictionaryComponent {
                     "-".to_string()
                 };
 
-                Row::new(vec![
-                    Cell::from(Span::styled(status_symbol, Theme::text_normal().fg(status_color))),
-                    Cell::from(Span::styled(&word.spelling, Theme::text_title())),
-                    Cell::from(Span::styled(phonetic, Theme::text_secondary())),
-                    Cell::from(interval),
-                ])
+                // Ease only means something once a word has been reviewed
+                let ease = match log {
+                    Some(log) if log.status != LearningStatus::New => Span::styled(
+                        ease_bar(log.e_factor),
+                        Theme::text_normal().fg(Theme::ease_color(log.e_factor)),
+                    ),
+                    _ => Span::styled("-", Theme::text_secondary()),
+                };
+
+                let due = match log {
+                    Some(log) => {
+                        let (label, overdue) = due_label(log.next_review, now, self.overdue_grace);
+                        let style = if overdue { Theme::text_accent() } else { Theme::text_normal() };
+                        Span::styled(label, style)
+                    }
+                    None => Span::styled("-", Theme::text_secondary()),
+                };
+
+                let mut cells = Vec::with_capacity(self.columns.len());
+                for column in &self.columns {
+                    cells.push(match column {
+                        DictColumn::Status => Cell::from(Span::styled(status_symbol, Theme::text_normal().fg(status_color))),
+                        DictColumn::Word => Cell::from(self.spelling_cell(index, word)),
+                        DictColumn::Phonetic => Cell::from(Span::styled(phonetic.clone(), Theme::text_secondary())),
+                        DictColumn::Ease => Cell::from(ease.clone()),
+                        DictColumn::Due => Cell::from(due.clone()),
+                        DictColumn::Interval => Cell::from(interval.clone()),
+                    });
+                }
+                Row::new(cells)
             })
             .collect();
 
-        let table = Table::new(
-            rows,
-            [
-                Constraint::Length(3),  // Status
-                Constraint::Length(20), // Word
-                Constraint::Length(20), // Phonetic
-                Constraint::Min(10),    // Interval
-            ],
-        )
+        // The last visible column takes the remaining width
+        let widths: Vec<Constraint> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| match column {
+                _ if i + 1 == self.columns.len() => Constraint::Min(10),
+                DictColumn::Status => Constraint::Length(3),
+                DictColumn::Word | DictColumn::Phonetic => Constraint::Length(20),
+                DictColumn::Ease => Constraint::Length(7), // Ease factor bar
+                DictColumn::Due => Constraint::Length(8),  // Days until next review
+                DictColumn::Interval => Constraint::Min(10),
+            })
+            .collect();
+        let table = Table::new(rows, widths)
         .header(
-            Row::new(vec![
-                Cell::from(Span::styled("", Theme::text_warning())),
-                Cell::from(Span::styled("Word", Theme::text_warning())),
-                Cell::from(Span::styled("Phonetic", Theme::text_warning())),
-                Cell::from(Span::styled("Interval", Theme::text_warning())),
-            ])
+            Row::new(
+                self.columns
+                    .iter()
+                    .map(|column| Cell::from(Span::styled(column.header(), Theme::text_warning())))
+                    .collect::<Vec<_>>(),
+            )
             .style(Theme::text_warning())
         )
         .block(
             Theme::block_default()
-                .title(format!(" Dictionary ({} words) ", items_len))
+                .title({
+                    let mut title = format!(" Dictionary ({} words", items_len);
+                    if self.band_filter {
+                        title.push_str(&format!(", 词频 {}", self.freq_band.label()));
+                    }
+                    if !self.tag_filter.is_empty() {
+                        let names: Vec<&str> = self
+                            .present_tags
+                            .iter()
+                            .filter(|tag| self.tag_filter.contains(*tag))
+                            .map(|tag| tag_display_name(tag))
+                            .collect();
+                        title.push_str(&format!(", 标签 {}", names.join("+")));
+                    }
+                    if self.hidden_mastered > 0 {
+                        title.push_str(&format!(", 已隐藏 {} 个已掌握", self.hidden_mastered));
+                    }
+                    title.push_str(") ");
+                    title
+                })
                 .title_bottom(
                     if items_len > 0 {
                         let help = match self.mode {
-                            Mode::Normal => "Tab:Search | j/k:↑↓ | Enter:Detail | q:Quit",
-                            Mode::Insert => "Tab:Exit | Enter:Search | Type to input",
+                            Mode::Normal => format!(
+                                "{}:Search | j/k:↑↓ | Enter:{} | q:Quit",
+                                self.focus_key.open_hint(),
+                                self.enter_action.hint()
+                            ),
+                            Mode::Insert => format!("{}:Exit | Enter:Search | Type to input", self.focus_key.close_hint()),
+                            Mode::Reschedule | Mode::GoTo => "Enter:Confirm | Esc:Cancel".to_string(),
                         };
                         Line::from(vec![
                             Span::raw("| "),
@@ -653,14 +1596,14 @@ impl Component for DictionaryComponent {
         )
         .row_highlight_style(Theme::text_success());
 
-        frame.render_stateful_widget(table, layout[1], &mut self.table_state);
+        frame.render_stateful_widget(table, layout[2], &mut self.table_state);
 
         // Scrollbar
         frame.render_stateful_widget(
             Scrollbar::new(ScrollbarOrientation::VerticalRight)
                 .begin_symbol(Some("↑"))
                 .end_symbol(Some("↓")),
-            layout[1].inner(Margin {
+            layout[2].inner(Margin {
                 vertical: 1,
                 horizontal: 0,
             }),
@@ -669,216 +1612,48 @@ impl Component for DictionaryComponent {
 
         // Selected word detail
         if let Some((word, log)) = self.word_list.get(self.selected_index) {
-            let mut detail_lines = vec![];
-            
-            // Word + Phonetic
-            let mut word_line_spans = vec![
-                Span::styled(
-                    &word.spelling,
-                    Theme::text_title()
-                        .add_modifier(Modifier::UNDERLINED),
+            let (detail_lines, detail_block) = match self.self_test {
+                Some(SelfTest::Hidden) => (
+                    self.build_self_test_lines(word),
+                    Theme::block_accent()
+                        .title(" 自测 - 回想释义 ")
+                        .title_bottom(" Space: 显示 | Esc: 退出 "),
                 ),
-            ];
-            if let Some(phonetic) = &word.phonetic {
-                word_line_spans.push(Span::raw("  "));
-                word_line_spans.push(Span::styled(
-                    format!("[ {} ]", phonetic),
-                    Theme::text_secondary(),
-                ));
-            }
-            detail_lines.push(Line::from(word_line_spans));
-            detail_lines.push(Line::from(""));
-            
-            // POS + Collins + Oxford
-            let mut meta_spans = vec![];
-            if let Some(pos) = &word.pos {
-                if !pos.is_empty() {
-                    let pos_display = parse_pos(pos);
-                    if !pos_display.is_empty() {
-                        meta_spans.push(Span::styled(
-                            pos_display,
-                            Theme::text_warning(),
-                        ));
-                    }
-                }
-            }
-            if word.collins > 0 {
-                if !meta_spans.is_empty() {
-                    meta_spans.push(Span::raw("  |  "));
-                }
-                meta_spans.push(Span::styled(
-                    format!("柯林斯 {}", "★".repeat(word.collins as usize)),
-                    Theme::text_info(),
-                ));
-            }
-            if word.oxford {
-                if !meta_spans.is_empty() {
-                    meta_spans.push(Span::raw("  |  "));
-                }
-                meta_spans.push(Span::styled(
-                    "牛津3000",
-                    Theme::text_success(),
-                ));
-            }
-            if !meta_spans.is_empty() {
-                detail_lines.push(Line::from(meta_spans));
-                detail_lines.push(Line::from(""));
-            }
-            
-            // Tags (考试标签)
-            if let Some(tag) = &word.tag {
-                if !tag.is_empty() {
-                    let tags: Vec<&str> = tag.split_whitespace().collect();
-                    let tag_display: Vec<String> = tags.iter().map(|t| {
-                        match *t {
-                            "zk" => "中考",
-                            "gk" => "高考",
-                            "cet4" => "CET-4",
-                            "cet6" => "CET-6",
-                            "ky" => "考研",
-                            "toefl" => "TOEFL",
-                            "ielts" => "IELTS",
-                            "gre" => "GRE",
-                            _ => t,
-                        }.to_string()
-                    }).collect();
-                    detail_lines.push(Line::from(vec![
-                        Span::styled(
-                            "考试: ",
-                            Theme::text_secondary(),
-                        ),
-                        Span::styled(
-                            tag_display.join(" · "),
-                            Theme::text_info(),
-                        ),
-                    ]));
-                    detail_lines.push(Line::from(""));
-                }
-            }
-            
-            // Chinese Translation
-            if let Some(translation) = &word.translation {
-                detail_lines.push(Line::from(Span::styled(
-                    "━━━ 中文释义 ━━━",
-                    Theme::text_title(),
-                )));
-                for line in translation.lines() {
-                    if !line.trim().is_empty() {
-                        detail_lines.push(Line::from(format!("  {}", line)));
-                    }
-                }
-                detail_lines.push(Line::from(""));
-            }
-            
-            // English Definition
-            detail_lines.push(Line::from(Span::styled(
-                "━━━ English Definition ━━━",
-                Theme::text_warning(),
-            )));
-            for line in word.definition.lines() {
-                if !line.trim().is_empty() {
-                    detail_lines.push(Line::from(format!("  {}", line)));
-                }
-            }
-            detail_lines.push(Line::from(""));
-            
-            // Exchange (词形变化)
-            if let Some(exchange) = &word.exchange {
-                if !exchange.is_empty() {
-                    detail_lines.push(Line::from(Span::styled(
-                        "━━━ 词形变化 ━━━",
-                        Theme::text_accent(),
-                    )));
-                    
-                    let exchange_map = parse_exchange(exchange);
-                    let order = ["0", "p", "d", "i", "3", "s", "r", "t", "1"];
-                    
-                    for key in &order {
-                        if let Some(value) = exchange_map.get(*key) {
-                            detail_lines.push(Line::from(vec![
-                                Span::styled(
-                                    format!("  {} ", exchange_type_name(key)),
-                                    Theme::text_secondary(),
-                                ),
-                                Span::styled(
-                                    value.clone(),
-                                    Theme::text_title().add_modifier(Modifier::ITALIC),
-                                ),
-                            ]));
-                        }
-                    }
-                    detail_lines.push(Line::from(""));
-                }
-            }
-            
-            // Frequency (词频)
-            let mut freq_info = vec![];
-            if let Some(bnc) = word.bnc {
-                freq_info.push(format!("BNC: {}", bnc));
-            }
-            if let Some(frq) = word.frq {
-                freq_info.push(format!("当代: {}", frq));
-            }
-            if !freq_info.is_empty() {
-                detail_lines.push(Line::from(vec![
-                    Span::styled(
-                        "词频: ",
-                        Theme::text_secondary(),
-                    ),
-                    Span::styled(
-                        freq_info.join(" | "),
-                        Theme::text_secondary().add_modifier(Modifier::ITALIC),
-                    ),
-                ]));
-                detail_lines.push(Line::from(""));
-            }
-
-            // Learning status
-            if let Some(log) = log {
-                detail_lines.push(Line::from(Span::styled(
-                    "━━━ 学习状态 ━━━",
-                    Theme::text_success(),
-                )));
-                detail_lines.push(Line::from(vec![
-                    Span::styled(
-                        "状态: ",
-                        Theme::text_secondary(),
-                    ),
-                    Span::styled(
-                        format!("{:?}", log.status),
-                        match log.status {
-                            LearningStatus::New => Theme::text_secondary(),
-                            LearningStatus::Learning => Theme::text_warning(),
-                            LearningStatus::Mastered => Theme::text_success(),
-                        },
-                    ),
-                ]));
-                detail_lines.push(Line::from(vec![
-                    Span::styled(
-                        format!("复习次数: {} | 间隔: {} 天 | 记忆因子: {:.2}", 
-                            log.repetition, log.interval, log.e_factor),
-                        Theme::text_secondary(),
-                    ),
-                ]));
-            }
+                Some(SelfTest::Revealed) => (
+                    self.build_detail_lines(word, log).0,
+                    Theme::block_accent()
+                        .title(" 自测 - 答案 ")
+                        .title_bottom(" 1-4: 记录为复习 | Esc: 不记录退出 "),
+                ),
+                None => (
+                    self.build_detail_lines(word, log).0,
+                    Theme::block_default().title(match self.hl_keys {
+                        DictHlKeys::Unbound => " Detail (Ctrl-d/u, </>: scroll) ",
+                        DictHlKeys::ScrollDetail => " Detail (h/l, Ctrl-d/u: scroll) ",
+                    }),
+                ),
+            };
 
             let detail_content_height = detail_lines.len() as u16;
             let detail = Paragraph::new(detail_lines)
-                .block(
-                    Theme::block_default()
-                        .title(" Detail (h/l: scroll) ")
-                )
-                .wrap(Wrap { trim: true })
+                .block(detail_block.clone())
+                .wrap(Wrap { trim: true });
+            self.detail_max_scroll = max_scroll(&detail, layout[3]);
+            self.detail_scroll = self.detail_scroll.min(self.detail_max_scroll);
+            let indicator =
+                scroll_indicator(self.detail_scroll, content_height(&detail, layout[3]), layout[3].height);
+            let detail = detail
+                .block(detail_block.title(Line::from(indicator).right_aligned()))
                 .scroll((self.detail_scroll, 0));
-            frame.render_widget(detail, layout[2]);
+            frame.render_widget(detail, layout[3]);
             
             // Detail scrollbar
-            if detail_content_height > layout[2].height {
+            if detail_content_height > layout[3].height {
                 frame.render_stateful_widget(
                     Scrollbar::new(ScrollbarOrientation::VerticalRight)
                         .begin_symbol(Some("↑"))
                         .end_symbol(Some("↓")),
-                    layout[2].inner(Margin {
+                    layout[3].inner(Margin {
                         vertical: 1,
                         horizontal: 0,
                     }),
@@ -891,9 +1666,13 @@ impl Component for DictionaryComponent {
         // 渲染浮窗（如果打开）
         if self.show_popup {
             if let Some((word, log)) = self.word_list.get(self.selected_index) {
-                let popup_lines = self.build_detail_lines(word, log);
-                self.popup.render(frame, area, popup_lines);
+                let (popup_lines, sections) = self.build_detail_lines(word, log);
+                self.popup.render_with_sections(frame, area, popup_lines, &sections);
             }
         }
+
+        if let Some((_, dialog)) = &self.add_confirm {
+            dialog.render(frame, area);
+        }
     }
 }
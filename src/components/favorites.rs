@@ -1,4 +1,5 @@
 use super::{Action, Component, Screen};
+use crate::components::common::word_preview_lines;
 use crate::db::Database;
 use crate::models::Word;
 use crate::theme::Theme;
@@ -51,6 +52,13 @@ impl FavoritesComponent {
         Ok(())
     }
 
+    /// Select a word (global search jump); unknown ids leave the selection alone
+    pub fn select_word(&mut self, word_id: i64) {
+        if let Some(index) = self.words.iter().position(|word| word.id == Some(word_id)) {
+            self.list_state.select(Some(index));
+        }
+    }
+
     fn next(&mut self) {
         if self.words.is_empty() {
             return;
@@ -202,27 +210,7 @@ impl Component for FavoritesComponent {
         // Word detail
         if let Some(idx) = self.list_state.selected() {
             if let Some(word) = self.words.get(idx) {
-                let mut detail_lines = vec![
-                    Line::from(vec![
-                        Span::styled(&word.spelling, Theme::text_title()),
-                    ]),
-                ];
-
-                if let Some(phonetic) = &word.phonetic {
-                    detail_lines.push(Line::from(vec![
-                        Span::styled(format!("[{}]", phonetic), Theme::text_secondary()),
-                    ]));
-                }
-
-                detail_lines.push(Line::from(""));
-
-                if let Some(translation) = &word.translation {
-                    for line in translation.lines().take(5) {
-                        detail_lines.push(Line::from(line));
-                    }
-                }
-
-                let detail = Paragraph::new(detail_lines)
+                let detail = Paragraph::new(word_preview_lines(word))
                     .block(Theme::block_accent_with_title(" 详情 "))
                     .style(Theme::text_normal());
                 frame.render_widget(detail, chunks[1]);
@@ -1,7 +1,7 @@
 use super::{Action, Component, Screen};
-use crate::components::common::Popup;
+use crate::components::common::{definition_lines, format_translation, push_section_gap, Popup};
 use crate::db::Database;
-use crate::models::Word;
+use crate::models::{parse_exchange, tag_display_name, Word};
 use crate::theme::Theme;
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent};
@@ -12,18 +12,6 @@ use ratatui::{
     widgets::{List, ListItem, ListState, Scrollbar, ScrollbarOrientation, ScrollbarState},
     Frame,
 };
-use std::collections::HashMap;
-
-/// Parse exchange field
-fn parse_exchange(exchange: &str) -> HashMap<&str, String> {
-    let mut result = HashMap::new();
-    for part in exchange.split('/') {
-        if let Some((key, value)) = part.split_once(':') {
-            result.insert(key, value.to_string());
-        }
-    }
-    result
-}
 
 /// Get exchange type name
 fn exchange_type_name(key: &str) -> &str {
@@ -63,22 +51,37 @@ pub struct HistoryComponent {
     selected_index: usize,
     show_popup: bool,
     popup: Popup,
+    clean_definitions: bool, // Tidy ECDICT markup in English definitions (setting)
 }
 
 impl HistoryComponent {
     pub fn new(db: Database) -> Result<Self> {
         let history_list = db.get_recent_reviews(100)?;
+        let clean_definitions = db.get_clean_definitions()?;
         Ok(Self {
             history_list,
             selected_index: 0,
             show_popup: false,
             popup: Popup::new("历史记录详情".to_string()),
+            clean_definitions,
         })
     }
 
+    /// 选中该词最近一次复习并打开详情浮窗（全局搜索跳转）；不在最近记录里时返回 false
+    pub fn open_word(&mut self, word_id: i64) -> bool {
+        let Some(index) = self.history_list.iter().position(|(word, _, _)| word.id == Some(word_id)) else {
+            return false;
+        };
+        self.selected_index = index;
+        self.show_popup = true;
+        self.popup.reset_scroll();
+        true
+    }
+
     /// 生成历史记录详情内容
-    fn build_history_detail<'a>(&self, word: &'a Word, reviewed_at: &str, quality: u8) -> Vec<Line<'a>> {
+    fn build_history_detail<'a>(&self, word: &'a Word, reviewed_at: &str, quality: u8) -> (Vec<Line<'a>>, Vec<usize>) {
         let mut lines = vec![];
+        let mut sections = vec![]; // Header line of each ━━━ section, for popup jumps
 
         // 复习时间和评分
         let time_str = if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(reviewed_at) {
@@ -121,7 +124,7 @@ impl HistoryComponent {
         let mut word_line_spans = vec![
             Span::styled(
                 &word.spelling,
-                Theme::text_title()
+                Theme::text_headword()
                     .add_modifier(Modifier::UNDERLINED),
             ),
         ];
@@ -133,7 +136,7 @@ impl HistoryComponent {
             ));
         }
         lines.push(Line::from(word_line_spans));
-        lines.push(Line::from(""));
+        push_section_gap(&mut lines);
 
         // POS + Collins + Oxford
         let mut meta_spans = vec![];
@@ -161,57 +164,47 @@ impl HistoryComponent {
         }
         if !meta_spans.is_empty() {
             lines.push(Line::from(meta_spans));
-            lines.push(Line::from(""));
+            push_section_gap(&mut lines);
         }
 
         // Tags
         if let Some(tag) = &word.tag {
             if !tag.is_empty() {
-                let tags: Vec<&str> = tag.split_whitespace().collect();
-                let tag_display: Vec<String> = tags.iter().map(|t| {
-                    match *t {
-                        "zk" => "中考", "gk" => "高考", "cet4" => "CET-4", "cet6" => "CET-6",
-                        "ky" => "考研", "toefl" => "TOEFL", "ielts" => "IELTS", "gre" => "GRE",
-                        _ => t,
-                    }.to_string()
-                }).collect();
+                let tag_display: Vec<&str> = tag.split_whitespace().map(tag_display_name).collect();
                 lines.push(Line::from(vec![
                     Span::styled("考试: ", Theme::text_secondary()),
                     Span::styled(tag_display.join(" · "), Theme::text_info()),
                 ]));
-                lines.push(Line::from(""));
+                push_section_gap(&mut lines);
             }
         }
 
         // Chinese Translation
         if let Some(translation) = &word.translation {
+            sections.push(lines.len());
             lines.push(Line::from(Span::styled(
                 "━━━ 中文释义 ━━━",
                 Theme::text_title(),
             )));
-            for line in translation.lines() {
-                if !line.trim().is_empty() {
-                    lines.push(Line::from(format!("  {}", line)));
-                }
-            }
-            lines.push(Line::from(""));
+            lines.extend(format_translation(translation));
+            push_section_gap(&mut lines);
         }
 
         // English Definition
+        sections.push(lines.len());
         lines.push(Line::from(Span::styled(
             "━━━ English Definition ━━━",
             Theme::text_warning(),
         )));
-        for line in word.definition.lines() {
-            if !line.trim().is_empty() {
-                lines.push(Line::from(format!("  {}", line)));
-            }
+        for line in definition_lines(&word.definition, self.clean_definitions) {
+            lines.push(Line::from(format!("  {}", line)));
         }
-        lines.push(Line::from(""));
+        push_section_gap(&mut lines);
 
         // Exchange
         if let Some(exchange) = &word.exchange {
             if !exchange.is_empty() {
+                sections.push(lines.len());
                 lines.push(Line::from(Span::styled(
                     "━━━ 词形变化 ━━━",
                     Theme::text_accent(),
@@ -232,7 +225,7 @@ impl HistoryComponent {
                         ]));
                     }
                 }
-                lines.push(Line::from(""));
+                push_section_gap(&mut lines);
             }
         }
 
@@ -250,7 +243,7 @@ impl HistoryComponent {
             ]));
         }
 
-        lines
+        (lines, sections)
     }
 }
 
@@ -272,6 +265,14 @@ impl Component for HistoryComponent {
                     self.popup.scroll_up();
                     Ok(Action::None)
                 }
+                KeyCode::Tab => {
+                    self.popup.next_section();
+                    Ok(Action::None)
+                }
+                KeyCode::BackTab => {
+                    self.popup.prev_section();
+                    Ok(Action::None)
+                }
                 _ => Ok(Action::None),
             }
         } else {
@@ -408,8 +409,8 @@ impl Component for HistoryComponent {
         // 渲染浮窗（如果打开）
         if self.show_popup {
             if let Some((word, reviewed_at, quality)) = self.history_list.get(self.selected_index) {
-                let popup_lines = self.build_history_detail(word, reviewed_at, *quality);
-                self.popup.render(frame, area, popup_lines);
+                let (popup_lines, sections) = self.build_history_detail(word, reviewed_at, *quality);
+                self.popup.render_with_sections(frame, area, popup_lines, &sections);
             }
         }
     }
@@ -6,8 +6,11 @@ pub mod statistics;
 pub mod wordbook;
 pub mod favorites;
 pub mod settings;
+pub mod search;
+pub mod summary;
 pub mod common;
 
+use crate::models::WordOrder;
 use anyhow::Result;
 use crossterm::event::KeyEvent;
 use ratatui::{layout::Rect, Frame};
@@ -16,8 +19,14 @@ use ratatui::{layout::Rect, Frame};
 #[derive(Debug, Clone)]
 pub enum Action {
     NavigateTo(Screen),
-    StartWordbookReview(String, bool), // (tag, shuffle)
+    StartWordbookReview(String, WordOrder, bool), // (tag, order, due_only)
+    StartOverdueReview, // Due review, most overdue word first
+    StartForgottenReview, // Words recently rated Forgot, listed on the dashboard
+    StartSingleReview(i64), // One-card review of a dictionary word (word_id)
+    StartBrowse, // Browse the due words without rating (浏览模式)
+    StartWordbookBrowse(String, WordOrder, bool), // Browse a wordbook without rating: (tag, order, due_only)
     ToggleFavorite(i64), // word_id
+    OpenSearchHit(SearchSource, i64, String), // Global search hit: (where, word_id, spelling)
     Quit,
     None,
 }
@@ -32,6 +41,16 @@ pub enum Screen {
     Wordbook,
     Favorites,
     Settings,
+    Search,
+    Summary,
+}
+
+/// Where a global search hit was found (decides which screen opens it)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SearchSource {
+    Learned,
+    History,
+    Favorites,
 }
 
 /// Component trait for all UI components
@@ -41,4 +60,12 @@ pub trait Component {
 
     /// Render the component
     fn view(&mut self, frame: &mut Frame, area: Rect);
+
+    /// Called on every Tick event (animations etc.); most components ignore it
+    fn on_tick(&mut self) {}
+
+    /// Whether a text field has focus, so global hotkeys must not fire
+    fn is_typing(&self) -> bool {
+        false
+    }
 }
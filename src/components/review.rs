@@ -1,23 +1,14 @@
 use super::{Action, Component, Screen};
-use crate::components::common::ProgressBar;
-use crate::db::Database;
-use crate::models::{LearningLog, Word};
+use crate::components::common::{content_height, definition_lines, format_translation, max_scroll, push_section_gap, scroll_indicator, ConfirmDialog, Popup, ProgressBar, SearchInput};
+use crate::components::dictionary::word_entry_lines;
+use crate::db::{primary_tag, word_has_tag, Database, CHECKPOINT_EVERY_REVIEWS, NEW_WORDS_BATCH, RECENTLY_FORGOTTEN_LIMIT};
+use crate::models::{parse_exchange, tag_display_name, LearningLog, LearningMix, LearningStatus, CardFrontField, MasteredReview, MasteryRule, MixOrder, ReviewDirection, Word, WordOrder};
 use crate::sm2;
 use crate::theme::Theme;
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent};
-use std::collections::HashMap;
-
-/// Parse exchange field into a readable format
-fn parse_exchange(exchange: &str) -> HashMap<&str, String> {
-    let mut result = HashMap::new();
-    for part in exchange.split('/') {
-        if let Some((key, value)) = part.split_once(':') {
-            result.insert(key, value.to_string());
-        }
-    }
-    result
-}
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
 
 /// Get exchange type description
 fn exchange_type_name(key: &str) -> &str {
@@ -60,17 +51,29 @@ fn parse_pos(pos: &str) -> String {
         }
     }
     
-    if result.is_empty() {
-        String::new()
-    } else {
-        result.join(" / ")
+    cap_list(&result, MAX_HEADER_POS, " / ")
+}
+
+/// At most this many parts of speech / exam tags in the review header
+const MAX_HEADER_POS: usize = 3;
+const MAX_HEADER_TAGS: usize = 4;
+/// Review header height when it has the usual three lines
+const HEADER_MIN_HEIGHT: u16 = 5;
+
+/// Join the first `max` items and summarize the rest as "+N more"
+fn cap_list<S: AsRef<str>>(items: &[S], max: usize, sep: &str) -> String {
+    let shown: Vec<&str> = items.iter().take(max).map(|item| item.as_ref()).collect();
+    let mut text = shown.join(sep);
+    if items.len() > max {
+        text.push_str(&format!(" +{} more", items.len() - max));
     }
+    text
 }
 use ratatui::{
     layout::{Constraint, Direction, Layout, Margin, Rect},
-    style::Modifier,
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap},
+    widgets::{Block, Borders, Clear, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap},
     Frame,
 };
 
@@ -92,43 +95,308 @@ pub struct ReviewComponent {
     current_item: Option<(Word, LearningLog)>,
     pub state: ReviewState,
     total_count: usize,
+    due_total: Option<usize>, // Full due count when the session cap split the queue
     completed_count: usize,
+    new_completed: usize, // Of completed_count, cards from new_card_ids
     scroll: u16, // Scroll position for definition text
     exchange_scroll: u16, // Scroll position for exchange panel
+    max_scroll: u16,          // Scroll limits measured on the last render
+    max_exchange_scroll: u16,
     active_panel: ActivePanel, // Which panel is currently focused
-    wordbook_info: Option<(String, bool)>, // (tag, shuffle)
+    wordbook_info: Option<(String, WordOrder, bool)>, // (tag, order, due_only)
+    empty_message: Option<String>, // Feedback shown in the empty state
+    mastered_review: MasteredReview,
+    mastery: MasteryRule,
+    direction: ReviewDirection,
+    recognition_quality: Option<u8>, // Both directions: rating of the EnToZh pass of the current card
+    recognition_ms: i64, // Both directions: card timer when the EnToZh pass was rated
+    hints_used: usize, // Letters of the spelling revealed on the current card
+    animations: bool,
+    transition: Option<Instant>, // Start of the flip/advance transition, if one is running
+    answer_shown_at: Option<Instant>, // When the current card entered the Answer state
+    card_timer: CardTimer,
+    new_card_ids: HashSet<i64>, // Cards introduced as new words in a mixed session
+    warmup_ids: HashSet<i64>, // Well-known cards moved to the start of a due session
+    rated_ids: HashSet<i64>, // Words already rated this session; a repeat is not a first try
+    history_mode: &'static str, // ReviewMode::history_name() of the running session
+    tag_blocks: Vec<(Option<&'static str>, usize)>, // Grouped session: (primary tag, size) per block, display order
+    block_index: Option<usize>, // Block of the current card (warm-up and new cards don't count)
+    block_start: bool, // Current card opens a new block
+    lookup: Option<Lookup>, // Mini dictionary opened with `/`; the card underneath is untouched
+    confirm_quit: bool, // Setting: ask before q/Esc leaves a session with cards left
+    quit_confirm: Option<ConfirmDialog>, // Shown after q/Esc until answered
+    clean_definitions: bool, // Tidy ECDICT markup in English definitions (setting)
+    card_front: Vec<CardFrontField>, // Cues shown on the question side (setting)
+    auto_favorite_lapses: i64, // Favorite a word once its lapses exceed this (setting, 0 = off)
+    notice: Option<String>, // Toast for AppV2 to show (taken with take_notice)
+    browse_deck: Option<Vec<(Word, LearningLog)>>, // Browse mode: the whole set, refilled after each lap
+    browse_lap: usize, // Browse mode: current pass through the set, from 1
+}
+
+/// 复习中的迷你词典（`/`）：查一个词，显示第一条结果，关闭后回到当前卡片
+struct Lookup {
+    input: SearchInput,
+    typing: bool, // Query box has focus
+    result: Option<Word>, // Top search_words hit for the last query
+    searched: bool, // A query has run (distinguishes "no hits" from "not searched yet")
+    popup: Popup,
+}
+
+impl Lookup {
+    fn new() -> Self {
+        Self {
+            input: SearchInput::new(),
+            typing: true,
+            result: None,
+            searched: false,
+            popup: Popup::new("查词".to_string()),
+        }
+    }
+}
+
+/// Length of the flip/advance transition when animations are on
+const TRANSITION: Duration = Duration::from_millis(160);
+
+/// Ratings this soon after the answer appears are ignored (queued or held keys)
+const RATE_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// No key for this long pauses the card timer (stepping away is not response time)
+const IDLE_PAUSE_AFTER: Duration = Duration::from_secs(120);
+
+/// Time spent on the current card, excluding idle pauses
+struct CardTimer {
+    active: Duration, // Time banked before the current running stretch
+    running_since: Option<Instant>, // None while paused
+    last_activity: Instant,
+}
+
+impl CardTimer {
+    fn start() -> Self {
+        let now = Instant::now();
+        Self { active: Duration::ZERO, running_since: Some(now), last_activity: now }
+    }
+
+    /// 有按键：记录活动时间，暂停中则恢复计时
+    fn touch(&mut self) {
+        let now = Instant::now();
+        if self.running_since.is_none() {
+            self.running_since = Some(now);
+        }
+        self.last_activity = now;
+    }
+
+    /// 空闲超过阈值时暂停，只计到最后一次按键为止
+    fn pause_if_idle(&mut self) {
+        if let Some(since) = self.running_since {
+            if self.last_activity.elapsed() >= IDLE_PAUSE_AFTER {
+                self.active += self.last_activity.saturating_duration_since(since);
+                self.running_since = None;
+            }
+        }
+    }
+
+    /// 立即暂停（查词浮窗打开期间不算作答时间），下一次 touch 恢复
+    fn pause(&mut self) {
+        if let Some(since) = self.running_since.take() {
+            self.active += since.elapsed();
+        }
+    }
+
+    fn is_paused(&self) -> bool {
+        self.running_since.is_none()
+    }
+
+    fn elapsed_ms(&self) -> i64 {
+        let running = self.running_since.map_or(Duration::ZERO, |since| since.elapsed());
+        (self.active + running).as_millis() as i64
+    }
+}
+
+/// Never show the same word twice in one session: keep its first occurrence
+fn dedup_queue(queue: &mut Vec<(Word, LearningLog)>) {
+    let mut seen = HashSet::new();
+    queue.retain(|(_, log)| seen.insert(log.word_id));
+}
+
+/// 混合队列：按显示顺序每 `every` 个复习插入一个新词，某一类用完后剩下的直接接在后面
+fn interleave<T>(reviews: Vec<T>, new_words: Vec<T>, every: usize, order: MixOrder) -> Vec<T> {
+    let mut reviews = reviews.into_iter().peekable();
+    let mut new_words = new_words.into_iter().peekable();
+    let mut mixed = Vec::new();
+    if order == MixOrder::NewFirst {
+        mixed.extend(new_words.next());
+    }
+    while reviews.peek().is_some() || new_words.peek().is_some() {
+        mixed.extend(reviews.by_ref().take(every));
+        mixed.extend(new_words.next());
+    }
+    mixed
+}
+
+/// Warm-up cards need at least the starting ease (or Mastered status): no history of struggling
+const WARMUP_MIN_EASE: f64 = 2.5;
+
+/// Highest rating allowed once a spelling hint has been used
+const MAX_QUALITY_WITH_HINTS: u8 = 3;
+
+/// Spelling with the first `revealed` letters shown and the rest as "_"
+/// (non-letters such as spaces and hyphens are always shown)
+fn masked_spelling(spelling: &str, revealed: usize) -> String {
+    let mut letters_seen = 0;
+    spelling
+        .chars()
+        .map(|c| {
+            if !c.is_alphabetic() {
+                return c.to_string();
+            }
+            letters_seen += 1;
+            if letters_seen <= revealed { c.to_string() } else { "_".to_string() }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
 }
 
 impl ReviewComponent {
     pub fn new(db: Database) -> Self {
+        let mastered_review = db.get_mastered_review().unwrap_or(MasteredReview::Normal);
+        let mastery = db.get_mastery_rule().unwrap_or_default();
+        let direction = db.get_review_direction().unwrap_or(ReviewDirection::EnToZh);
+        let animations = db.get_animations().unwrap_or(false);
+        let confirm_quit = db.get_confirm_review_quit().unwrap_or(true);
+        let clean_definitions = db.get_clean_definitions().unwrap_or(true);
+        let card_front = db.get_card_front().unwrap_or_else(|_| CardFrontField::ALL.to_vec());
+        let auto_favorite_lapses = db.get_auto_favorite_lapses().unwrap_or(0);
         Self {
             db,
             review_queue: Vec::new(),
             current_item: None,
             state: ReviewState::Question,
             total_count: 0,
+            due_total: None,
             completed_count: 0,
+            new_completed: 0,
             scroll: 0,
             exchange_scroll: 0,
+            max_scroll: 0,
+            max_exchange_scroll: 0,
             active_panel: ActivePanel::Definition,
             wordbook_info: None,
+            empty_message: None,
+            mastered_review,
+            mastery,
+            direction,
+            recognition_quality: None,
+            recognition_ms: 0,
+            hints_used: 0,
+            animations,
+            transition: None,
+            answer_shown_at: None,
+            card_timer: CardTimer::start(),
+            new_card_ids: HashSet::new(),
+            warmup_ids: HashSet::new(),
+            rated_ids: HashSet::new(),
+            history_mode: "due",
+            tag_blocks: Vec::new(),
+            block_index: None,
+            block_start: false,
+            lookup: None,
+            confirm_quit,
+            quit_confirm: None,
+            clean_definitions,
+            card_front,
+            auto_favorite_lapses,
+            notice: None,
+            browse_deck: None,
+            browse_lap: 0,
         }
     }
 
     pub fn start_review(&mut self, mode: ReviewMode) -> Result<bool> {
+        self.start(mode, false)
+    }
+
+    /// `browse`: build the queue without writing anything (no new-word mix, no enrolling)
+    fn start(&mut self, mode: ReviewMode, browse: bool) -> Result<bool> {
+        self.new_card_ids.clear();
+        self.rated_ids.clear();
+        self.empty_message = None;
+        self.history_mode = mode.history_name();
         self.review_queue = match &mode {
-            ReviewMode::Due => self.db.get_due_reviews()?,
-            ReviewMode::Wordbook(tag, shuffle) => self.db.get_words_by_tag(tag, 100, *shuffle)?,
+            ReviewMode::Due => match self.db.get_learning_mix()? {
+                LearningMix::Every(every) if !browse => self.mixed_queue(every as usize)?,
+                _ => self.due_queue()?,
+            },
+            ReviewMode::Wordbook(tag, order, due_only) => {
+                // Frequency and dictionary order are meant to be followed: cards are popped from the end
+                let mut queue = if browse {
+                    self.db.browse_words_by_tag(tag, 100, *order, *due_only)?
+                } else {
+                    self.db.get_words_by_tag(tag, 100, *order, *due_only)?
+                };
+                queue.reverse();
+                queue
+            }
+            ReviewMode::New => {
+                // Highest priority first; cards are popped from the end
+                let mut queue = self.new_words()?;
+                queue.reverse();
+                queue
+            }
+            ReviewMode::Single(word_id) => self.db.get_word_for_review(*word_id)?.into_iter().collect(),
+            ReviewMode::Forgotten => {
+                // Most recent lapse first; cards are popped from the end
+                let mut queue: Vec<_> = self
+                    .db
+                    .get_recently_forgotten(RECENTLY_FORGOTTEN_LIMIT)?
+                    .into_iter()
+                    .map(|(word, log, _)| (word, log))
+                    .collect();
+                queue.reverse();
+                queue
+            }
+            ReviewMode::Overdue => self.due_queue()?,
         };
 
+        dedup_queue(&mut self.review_queue);
+
+        // 音标模式只考有音标的词（它们照常留在其他方向的复习里）
+        if self.direction == ReviewDirection::PhoneticToWord {
+            let before = self.review_queue.len();
+            self.review_queue
+                .retain(|(word, _)| word.phonetic.as_deref().is_some_and(|p| !p.trim().is_empty()));
+            self.new_card_ids.retain(|id| self.review_queue.iter().any(|(_, log)| log.word_id == *id));
+            if before > 0 && self.review_queue.is_empty() {
+                self.empty_message = Some("音标 → 词模式：这些单词都没有音标，可在设置里换个复习方向".to_string());
+            }
+        }
+
+        let grouped = matches!(mode, ReviewMode::Due | ReviewMode::Overdue) && self.db.get_group_by_wordbook()?;
+        if grouped {
+            self.group_by_primary_tag();
+        }
+        self.warmup_ids.clear();
+        if matches!(mode, ReviewMode::Due) {
+            if let Some(priority) = self.db.get_priority_wordbook()? {
+                self.move_tag_to_front(&priority.tag);
+            }
+            self.move_warmup_to_front(self.db.get_warmup_count()?.max(0) as usize);
+        }
+        self.due_total = None;
+        if matches!(mode, ReviewMode::Due | ReviewMode::Overdue) {
+            self.apply_session_cap(self.db.get_session_cap()?.max(0) as usize);
+        }
+        self.tag_blocks = if grouped { self.count_tag_blocks() } else { Vec::new() };
+        self.block_index = None;
+
         // Save wordbook info for display
         self.wordbook_info = match mode {
-            ReviewMode::Wordbook(tag, shuffle) => Some((tag, shuffle)),
+            ReviewMode::Wordbook(tag, order, due_only) => Some((tag, order, due_only)),
             _ => None,
         };
 
         self.total_count = self.review_queue.len();
         self.completed_count = 0;
+        self.new_completed = 0;
 
         if self.review_queue.is_empty() {
             return Ok(false);
@@ -138,12 +406,181 @@ impl ReviewComponent {
         Ok(true)
     }
 
+    /// 浏览模式（不计分）：卡片直接显示答案，Space 翻下一张，
+    /// 不评分、不改 SM-2、不写复习历史，看完一遍从头再来
+    pub fn start_browse(&mut self, mode: ReviewMode) -> Result<bool> {
+        if !self.start(mode, true)? {
+            return Ok(false);
+        }
+        // start_review 已经取出第一张，留一份完整的队列给下一遍
+        let mut deck = self.review_queue.clone();
+        deck.extend(self.current_item.clone());
+        self.browse_deck = Some(deck);
+        self.browse_lap = 1;
+        self.confirm_quit = false; // 没有进度可丢
+        self.show_answer();
+        Ok(true)
+    }
+
+    pub fn is_browsing(&self) -> bool {
+        self.browse_deck.is_some()
+    }
+
+    fn browse_next(&mut self) {
+        self.completed_count += 1;
+        if self.review_queue.is_empty() {
+            if let Some(deck) = &self.browse_deck {
+                self.review_queue = deck.clone();
+            }
+            self.completed_count = 0;
+            self.browse_lap += 1;
+            self.block_index = None;
+        }
+        self.next_card();
+        self.show_answer();
+    }
+
+    /// 每次复习上限：只留队列里最先出的 `cap` 张卡（队列从末尾出），其余仍然到期，
+    /// 留给下一次复习
+    fn apply_session_cap(&mut self, cap: usize) {
+        if cap == 0 || self.review_queue.len() <= cap {
+            return;
+        }
+        self.due_total = Some(self.review_queue.len());
+        let excess = self.review_queue.len() - cap;
+        self.review_queue.drain(..excess);
+        let kept: HashSet<i64> = self.review_queue.iter().map(|(_, log)| log.word_id).collect();
+        self.new_card_ids.retain(|word_id| kept.contains(word_id));
+    }
+
+    /// 限时优先单词本：到期复习词里属于 `tag` 的先出；只在复习词占的位置之间调换，
+    /// 混入的新词位置不变
+    fn move_tag_to_front(&mut self, tag: &str) {
+        let slots: Vec<usize> = (0..self.review_queue.len())
+            .filter(|&index| !self.new_card_ids.contains(&self.review_queue[index].1.word_id))
+            .collect();
+        let mut reviews: Vec<_> = slots.iter().map(|&index| self.review_queue[index].clone()).collect();
+        // Stable sort keeps the due order within each group; the queue pops from the end
+        reviews.sort_by_key(|(word, _)| word_has_tag(word, tag));
+        for (index, card) in slots.into_iter().zip(reviews) {
+            self.review_queue[index] = card;
+        }
+    }
+
+    /// 按单词本分组：复习词按主标签聚成连续的几组，组的先后按组里最先到期的词，
+    /// 组内保持原来的到期顺序；混入的新词位置不变
+    fn group_by_primary_tag(&mut self) {
+        let slots: Vec<usize> = (0..self.review_queue.len())
+            .filter(|&index| !self.new_card_ids.contains(&self.review_queue[index].1.word_id))
+            .collect();
+        // Rank groups by first appearance in display order (the queue pops from the end)
+        let mut ranks: HashMap<Option<&'static str>, usize> = HashMap::new();
+        for &index in slots.iter().rev() {
+            let next_rank = ranks.len();
+            ranks.entry(primary_tag(&self.review_queue[index].0)).or_insert(next_rank);
+        }
+        let mut reviews: Vec<_> = slots.iter().map(|&index| self.review_queue[index].clone()).collect();
+        reviews.sort_by_key(|(word, _)| std::cmp::Reverse(ranks[&primary_tag(word)]));
+        for (index, card) in slots.into_iter().zip(reviews) {
+            self.review_queue[index] = card;
+        }
+    }
+
+    /// Runs of review cards sharing a primary tag, in display order
+    fn count_tag_blocks(&self) -> Vec<(Option<&'static str>, usize)> {
+        let mut blocks: Vec<(Option<&'static str>, usize)> = Vec::new();
+        for (word, log) in self.review_queue.iter().rev() {
+            if self.new_card_ids.contains(&log.word_id) || self.warmup_ids.contains(&log.word_id) {
+                continue;
+            }
+            let tag = primary_tag(word);
+            match blocks.last_mut() {
+                Some((last, count)) if *last == tag => *count += 1,
+                _ => blocks.push((tag, 1)),
+            }
+        }
+        blocks
+    }
+
+    /// 热身：把队列里最熟的几个复习词（已掌握或 e_factor 不低于初始值）挪到最前面，
+    /// 它们照常评分和安排下次复习
+    fn move_warmup_to_front(&mut self, count: usize) {
+        let mut candidates: Vec<(usize, f64)> = self
+            .review_queue
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, log))| !self.new_card_ids.contains(&log.word_id) && log.status != LearningStatus::New)
+            .filter(|(_, (_, log))| log.status == LearningStatus::Mastered || log.e_factor >= WARMUP_MIN_EASE)
+            .map(|(index, (_, log))| (index, log.e_factor))
+            .collect();
+        candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let mut picked: Vec<usize> = candidates.into_iter().take(count).map(|(index, _)| index).collect();
+
+        // Remove from the back so earlier indices stay valid; the queue pops from the end,
+        // so the easiest card is pushed last
+        picked.sort_unstable();
+        let mut warmup: Vec<_> = picked.into_iter().rev().map(|index| self.review_queue.remove(index)).collect();
+        warmup.sort_by(|a, b| a.1.e_factor.total_cmp(&b.1.e_factor));
+        self.warmup_ids = warmup.iter().map(|(_, log)| log.word_id).collect();
+        self.review_queue.extend(warmup);
+    }
+
+    /// Due reviews in queue order: get_due_reviews is oldest-first and cards are
+    /// popped from the end, so flip it to present the most overdue word first
+    fn due_queue(&self) -> Result<Vec<(Word, LearningLog)>> {
+        let mut queue = self.db.get_due_reviews()?;
+        queue.reverse();
+        Ok(queue)
+    }
+
+    /// Due reviews with new words interspersed (queue order: cards are popped from the end)
+    fn mixed_queue(&mut self, every: usize) -> Result<Vec<(Word, LearningLog)>> {
+        let reviews = self.db.get_due_reviews()?;
+        let new_words = self.new_words()?;
+        self.new_card_ids = new_words.iter().map(|(_, log)| log.word_id).collect();
+
+        // Both lists are in display order: interleave, then flip into queue order
+        let mut queue = interleave(reviews, new_words, every, self.db.get_mix_order()?);
+        queue.reverse();
+        Ok(queue)
+    }
+
+    /// New words for this session in display order (highest priority first)
+    fn new_words(&self) -> Result<Vec<(Word, LearningLog)>> {
+        match self.new_word_limit()? {
+            0 => Ok(Vec::new()),
+            limit => self.db.get_new_words_to_learn(limit),
+        }
+    }
+
+    /// 新词数量：开启“积压时自动减少新词”后按复习预测收紧
+    fn new_word_limit(&self) -> Result<i64> {
+        if self.db.get_auto_limit_new_words()? {
+            Ok(self.db.suggest_new_word_budget()?.budget)
+        } else {
+            Ok(NEW_WORDS_BATCH)
+        }
+    }
+
     fn next_card(&mut self) {
         self.current_item = self.review_queue.pop();
+        self.block_start = false;
+        if let (false, Some((word, log))) = (self.tag_blocks.is_empty(), &self.current_item) {
+            let in_block = !self.new_card_ids.contains(&log.word_id) && !self.warmup_ids.contains(&log.word_id);
+            let current_tag = self.block_index.and_then(|index| self.tag_blocks.get(index)).map(|(tag, _)| *tag);
+            if in_block && current_tag != Some(primary_tag(word)) {
+                self.block_index = Some(self.block_index.map_or(0, |index| index + 1));
+                self.block_start = true;
+            }
+        }
+        self.recognition_quality = None;
         self.state = ReviewState::Question;
+        self.hints_used = 0;
         self.scroll = 0; // Reset scroll for new card
         self.exchange_scroll = 0;
         self.active_panel = ActivePanel::Definition;
+        self.card_timer = CardTimer::start();
+        self.start_transition();
     }
 
     fn show_answer(&mut self) {
@@ -151,48 +588,429 @@ impl ReviewComponent {
         self.scroll = 0; // Reset scroll when showing answer
         self.exchange_scroll = 0;
         self.active_panel = ActivePanel::Definition;
+        self.answer_shown_at = Some(Instant::now());
+        self.start_transition();
+    }
+
+    /// 刚翻到答案就到达的评分键视为误触（连按或按键排队）
+    fn rating_too_soon(&self) -> bool {
+        self.answer_shown_at.is_some_and(|shown| shown.elapsed() < RATE_DEBOUNCE)
+    }
+
+    /// 翻卡/换卡过渡：只影响渲染，按键照常处理
+    fn start_transition(&mut self) {
+        if self.animations {
+            self.transition = Some(Instant::now());
+        }
+    }
+
+    /// Transition progress in 0.0..1.0, None when no transition is running
+    fn transition_progress(&self) -> Option<f32> {
+        let start = self.transition?;
+        Some((start.elapsed().as_secs_f32() / TRANSITION.as_secs_f32()).min(1.0))
+    }
+
+    /// 中→英模式下每按一次 h 多显示一个字母
+    fn reveal_hint(&mut self) {
+        if let Some((word, _)) = &self.current_item {
+            let letters = word.spelling.chars().filter(|c| c.is_alphabetic()).count();
+            self.hints_used = (self.hints_used + 1).min(letters);
+        }
+    }
+
+    /// Direction of the card on screen; "both" shows EnToZh first, then ZhToEn
+    fn current_direction(&self) -> ReviewDirection {
+        match (self.direction, self.recognition_quality) {
+            (ReviewDirection::Both, None) => ReviewDirection::EnToZh,
+            (ReviewDirection::Both, Some(_)) => ReviewDirection::ZhToEn,
+            (direction, _) => direction,
+        }
+    }
+
+    /// 双向模式第一遍（认读）：只记下评分，同一张卡马上换成中→英再考一次
+    fn start_production_pass(&mut self, quality: u8) {
+        self.recognition_quality = Some(quality);
+        self.recognition_ms = self.card_timer.elapsed_ms();
+        self.state = ReviewState::Question;
+        self.hints_used = 0;
+        self.scroll = 0;
+        self.exchange_scroll = 0;
+        self.active_panel = ActivePanel::Definition;
+        self.start_transition();
     }
 
     fn submit_review(&mut self, quality: u8) -> Result<()> {
+        // Hints are scaffolding: a hinted card can be "Good" at best
+        let quality = if self.hints_used > 0 {
+            quality.min(MAX_QUALITY_WITH_HINTS)
+        } else {
+            quality
+        };
+        if self.direction == ReviewDirection::Both && self.recognition_quality.is_none() {
+            self.start_production_pass(quality);
+            return Ok(());
+        }
+        let production = quality;
+        // Both directions share one SM-2 schedule: the weaker pass decides
+        let quality = self.recognition_quality.map_or(quality, |first| first.min(quality));
         if let Some((word, mut log)) = self.current_item.take() {
             let word_id = word.id.unwrap();
-            sm2::process_review(&mut log, quality);
+            sm2::process_review(&mut log, quality, self.mastered_review, self.mastery);
             self.db.update_log(&log)?;
-            self.db.add_review_history(word_id, quality, &log)?;
+            let mode = if self.new_card_ids.contains(&word_id) { "new" } else { self.history_mode };
+            let elapsed_ms = self.card_timer.elapsed_ms();
+            // Both directions: one history row per pass with its own direction and rating
+            let history_ids = match self.recognition_quality {
+                Some(recognition) => self
+                    .db
+                    .add_both_review_history(
+                        word_id,
+                        [(recognition, self.recognition_ms), (production, elapsed_ms - self.recognition_ms)],
+                        &log,
+                        mode,
+                    )?
+                    .to_vec(),
+                None => vec![self.db.add_review_history(
+                    word_id,
+                    quality,
+                    &log,
+                    Some(elapsed_ms),
+                    Some(self.direction),
+                    mode,
+                )?],
+            };
+            if !self.rated_ids.insert(word_id) {
+                for history_id in history_ids {
+                    self.db.mark_retry(history_id)?;
+                }
+            }
+            if quality == 1 {
+                self.auto_favorite(&word)?;
+            }
 
             self.completed_count += 1;
+            if self.new_card_ids.contains(&word_id) {
+                self.new_completed += 1;
+            }
             
             // Update daily checkin after each review
-            let _ = self.db.update_daily_checkin();
+            let _ = self.db.record_checkin_if_goal_met();
+
+            if self.completed_count.is_multiple_of(CHECKPOINT_EVERY_REVIEWS) {
+                let _ = self.db.checkpoint();
+            }
             
             self.next_card();
         }
         Ok(())
     }
 
+    /// 忘记次数超过设置的阈值时自动收藏（已收藏的不动），并提示一下
+    fn auto_favorite(&mut self, word: &Word) -> Result<()> {
+        let Some(word_id) = word.id else {
+            return Ok(());
+        };
+        if self.auto_favorite_lapses == 0 || self.db.is_favorited(word_id)? {
+            return Ok(());
+        }
+        let lapses = self.db.get_lapses(word_id)?;
+        if lapses > self.auto_favorite_lapses {
+            self.db.toggle_favorite(word_id)?;
+            self.notice = Some(format!("⭐ {} 已忘记 {} 次，自动加入收藏", word.spelling, lapses));
+        }
+        Ok(())
+    }
+
+    /// Toast raised by the last key (auto-favorite), for AppV2's notification
+    pub fn take_notice(&mut self) -> Option<String> {
+        self.notice.take()
+    }
+
+    /// Mixed session progress split by origin: (reviews done, reviews, new done, new);
+    /// None when the session is all reviews or all new words
+    fn mix_progress(&self) -> Option<(usize, usize, usize, usize)> {
+        let new_total = self.new_card_ids.len();
+        if new_total == 0 || new_total >= self.total_count {
+            return None;
+        }
+        Some((
+            self.completed_count - self.new_completed,
+            self.total_count - new_total,
+            self.new_completed,
+            new_total,
+        ))
+    }
+
+    /// A session is complete once at least one card was answered and the queue ran out.
+    /// An empty queue from the start stays on screen as the empty state instead.
     pub fn is_complete(&self) -> bool {
-        self.current_item.is_none()
+        self.current_item.is_none() && self.completed_count > 0
+    }
+
+    pub fn completed_count(&self) -> usize {
+        self.completed_count
+    }
+
+    /// Start a new-words session; when there is none, `empty_message` says why
+    pub fn start_new_words(&mut self) -> Result<bool> {
+        if self.start_review(ReviewMode::New)? {
+            return Ok(true);
+        }
+        if self.empty_message.is_none() {
+            self.empty_message = Some(if self.db.get_skip_new_words_today()? {
+                "今天已暂停学新词（主页按 p 恢复）".to_string()
+            } else if self.new_word_limit()? == 0 {
+                "积压较多，已暂停学新词，先把到期的复习做完".to_string()
+            } else {
+                "没有可学习的新单词，试试从单词本开始".to_string()
+            });
+        }
+        Ok(false)
+    }
+
+    /// Why the last start_review came up empty, if it has a specific reason
+    pub fn empty_message(&self) -> Option<&str> {
+        self.empty_message.as_deref()
+    }
+
+    /// Whether a card is on screen (false while showing the empty state)
+    pub fn has_card(&self) -> bool {
+        self.current_item.is_some()
+    }
+
+    /// 中→英模式的提问阶段（底栏显示提示键）
+    pub fn wants_hint_key(&self) -> bool {
+        self.current_direction() == ReviewDirection::ZhToEn && self.state == ReviewState::Question && self.has_card()
+    }
+
+    /// q/Esc 离开复习：开启了确认且还有未复习的卡片时先弹确认框
+    /// （单卡复习没有后续顺序可丢，直接返回）
+    fn request_quit(&mut self) -> Action {
+        let remaining = self.total_count.saturating_sub(self.completed_count);
+        if self.confirm_quit && self.total_count > 1 && remaining > 0 {
+            self.quit_confirm = Some(ConfirmDialog::new(
+                "退出复习".to_string(),
+                format!("还有 {} 个未复习，确定退出？", remaining),
+            ));
+            return Action::None;
+        }
+        Action::NavigateTo(Screen::Dashboard)
+    }
+
+    /// 查词浮窗里的按键；浮窗开着时卡片、队列和评分都不动
+    fn handle_lookup_key(&mut self, key: KeyEvent) -> Result<()> {
+        let Some(lookup) = &mut self.lookup else {
+            return Ok(());
+        };
+        if lookup.typing {
+            match key.code {
+                KeyCode::Esc => {
+                    if lookup.result.is_some() {
+                        lookup.typing = false;
+                    } else {
+                        self.lookup = None;
+                    }
+                }
+                KeyCode::Enter => {
+                    let query = lookup.input.value.trim();
+                    if !query.is_empty() {
+                        lookup.result = self.db.search_words(query)?.into_iter().next().map(|(word, _)| word);
+                        lookup.searched = true;
+                        if let Some(word) = &lookup.result {
+                            lookup.popup = Popup::new(format!("查词: {}", word.spelling));
+                            lookup.typing = false;
+                        }
+                    }
+                }
+                _ => {
+                    lookup.input.handle_key(key);
+                }
+            }
+            return Ok(());
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => self.lookup = None,
+            KeyCode::Char('/') | KeyCode::Char('i') => {
+                lookup.input.value.clear();
+                lookup.typing = true;
+            }
+            KeyCode::Char('j') | KeyCode::Down => lookup.popup.scroll_down(),
+            KeyCode::Char('k') | KeyCode::Up => lookup.popup.scroll_up(),
+            KeyCode::Tab => lookup.popup.next_section(),
+            KeyCode::BackTab => lookup.popup.prev_section(),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn render_lookup(&mut self, frame: &mut Frame, area: Rect) {
+        let clean_definitions = self.clean_definitions;
+        let Some(lookup) = &mut self.lookup else {
+            return;
+        };
+        if let (false, Some(word)) = (lookup.typing, &lookup.result) {
+            let (lines, sections) = word_entry_lines(word, clean_definitions);
+            lookup.popup.render_with_sections(frame, area, lines, &sections);
+            return;
+        }
+
+        let width = area.width.saturating_sub(4).min(60);
+        let box_area = Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + area.height / 3,
+            width,
+            height: 4.min(area.height),
+        };
+        frame.render_widget(Clear, box_area);
+        let message = if lookup.searched && lookup.result.is_none() {
+            Line::from(Span::styled("没有找到", Theme::text_secondary()))
+        } else {
+            Line::from(Span::styled("Enter 查询 · Esc 返回卡片", Theme::text_secondary()))
+        };
+        let input = Paragraph::new(vec![
+            Line::from(Span::styled(format!("{}▏", lookup.input.value), Theme::text_warning())),
+            message,
+        ])
+        .block(Theme::block_warning().title(" 🔍 查词 "));
+        frame.render_widget(input, box_area);
+    }
+
+    fn handle_empty_key(&mut self, key: KeyEvent) -> Result<Action> {
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => Ok(Action::NavigateTo(Screen::Dashboard)),
+            KeyCode::Char('w') => Ok(Action::NavigateTo(Screen::Wordbook)),
+            KeyCode::Char('d') => Ok(Action::NavigateTo(Screen::Dictionary)),
+            KeyCode::Char('n') => {
+                self.start_new_words()?;
+                Ok(Action::None)
+            }
+            _ => Ok(Action::None),
+        }
+    }
+
+    fn render_empty_state(&self, frame: &mut Frame, area: Rect) {
+        let key_line = |key: &'static str, desc: &'static str, color| {
+            Line::from(vec![
+                Span::styled(format!(" {} ", key), Theme::text_normal().bg(color)),
+                Span::styled(format!("  {}", desc), Theme::text_normal()),
+            ])
+        };
+
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                "🎉 当前没有需要复习的单词",
+                Theme::text_success().add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+            Line::from(Span::styled("接下来可以：", Theme::text_secondary())),
+            Line::from(""),
+            key_line("n", "学习新单词", Theme::PRIMARY),
+            Line::from(""),
+            key_line("w", "打开单词本", Theme::SUCCESS),
+            Line::from(""),
+            key_line("d", "查词典", Theme::WARNING),
+            Line::from(""),
+            key_line("q", "返回主页", Theme::INFO),
+        ];
+
+        if let Some(message) = &self.empty_message {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(message.clone(), Theme::text_warning())));
+        }
+
+        let widget = Paragraph::new(lines)
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(Theme::block_with_title(" Review "));
+        frame.render_widget(widget, area);
     }
 }
 
 pub enum ReviewMode {
     Due,
-    Wordbook(String, bool), // (tag, shuffle)
+    Wordbook(String, WordOrder, bool), // (tag, order, due_only)
+    New, // Unlearned words, highest quality first
+    Overdue, // Due words, most overdue presented first
+    Single(i64), // One word picked in the dictionary (word_id)
+    Forgotten, // Words recently rated Forgot, due or not (dashboard `F`)
+}
+
+impl ReviewMode {
+    /// Stored in review_history.mode
+    pub fn history_name(&self) -> &'static str {
+        match self {
+            ReviewMode::Due => "due",
+            ReviewMode::Wordbook(..) => "wordbook",
+            ReviewMode::New => "new",
+            ReviewMode::Overdue => "overdue",
+            ReviewMode::Single(_) => "single",
+            ReviewMode::Forgotten => "forgotten",
+        }
+    }
 }
 
 impl Component for ReviewComponent {
     fn handle_key(&mut self, key: KeyEvent) -> Result<Action> {
+        if !self.has_card() {
+            return self.handle_empty_key(key);
+        }
+        if let Some(dialog) = &self.quit_confirm {
+            return Ok(match dialog.handle_key(key) {
+                Some(true) => {
+                    self.quit_confirm = None;
+                    Action::NavigateTo(Screen::Dashboard)
+                }
+                Some(false) => {
+                    self.quit_confirm = None;
+                    Action::None
+                }
+                None => Action::None,
+            });
+        }
+        if self.lookup.is_some() {
+            self.handle_lookup_key(key)?;
+            return Ok(Action::None);
+        }
+        self.card_timer.touch();
+
+        // /: 查词浮窗（迷你词典），关闭后回到当前卡片
+        if key.code == KeyCode::Char('/') {
+            self.card_timer.pause();
+            self.lookup = Some(Lookup::new());
+            return Ok(Action::None);
+        }
+
+        // f: 收藏/取消收藏当前单词（提问和答案阶段都可用，不影响评分）
+        if key.code == KeyCode::Char('f') {
+            if let Some((Word { id: Some(word_id), .. }, _)) = &self.current_item {
+                return Ok(Action::ToggleFavorite(*word_id));
+            }
+        }
+
         match self.state {
             ReviewState::Question => match key.code {
-                KeyCode::Char('q') | KeyCode::Esc => Ok(Action::NavigateTo(Screen::Dashboard)),
+                KeyCode::Char('q') | KeyCode::Esc => Ok(self.request_quit()),
                 KeyCode::Char(' ') | KeyCode::Enter => {
                     self.show_answer();
                     Ok(Action::None)
                 }
+                KeyCode::Char('h') if self.current_direction() == ReviewDirection::ZhToEn => {
+                    self.reveal_hint();
+                    Ok(Action::None)
+                }
                 _ => Ok(Action::None),
             },
             ReviewState::Answer => match key.code {
-                KeyCode::Char('q') | KeyCode::Esc => Ok(Action::NavigateTo(Screen::Dashboard)),
+                KeyCode::Char('q') | KeyCode::Esc => Ok(self.request_quit()),
+                // Browse mode: Space/Enter go to the next card, rating keys do nothing
+                KeyCode::Char(' ') | KeyCode::Enter if self.is_browsing() => {
+                    self.browse_next();
+                    Ok(Action::None)
+                }
+                KeyCode::Char('1'..='4') if self.is_browsing() => Ok(Action::None),
+                // Space/Enter only reveal; they never rate
+                KeyCode::Char(' ') | KeyCode::Enter => Ok(Action::None),
+                KeyCode::Char('1'..='4') if self.rating_too_soon() => Ok(Action::None),
                 KeyCode::Char('f') => {
                     if let Some((word, _)) = &self.current_item {
                         if let Some(word_id) = word.id {
@@ -203,8 +1021,13 @@ impl Component for ReviewComponent {
                 }
                 KeyCode::Char('j') | KeyCode::Down => {
                     match self.active_panel {
-                        ActivePanel::Definition => self.scroll = self.scroll.saturating_add(1),
-                        ActivePanel::Exchange => self.exchange_scroll = self.exchange_scroll.saturating_add(1),
+                        ActivePanel::Definition => {
+                            self.scroll = self.scroll.saturating_add(1).min(self.max_scroll)
+                        }
+                        ActivePanel::Exchange => {
+                            self.exchange_scroll =
+                                self.exchange_scroll.saturating_add(1).min(self.max_exchange_scroll)
+                        }
                     }
                     Ok(Action::None)
                 }
@@ -215,45 +1038,44 @@ impl Component for ReviewComponent {
                     }
                     Ok(Action::None)
                 }
+                // J/K scroll both panels together; the shorter one stops at its end,
+                // so the longer one keeps going. Each panel keeps its own position.
+                KeyCode::Char('J') => {
+                    self.scroll = self.scroll.saturating_add(1).min(self.max_scroll);
+                    self.exchange_scroll = self.exchange_scroll.saturating_add(1).min(self.max_exchange_scroll);
+                    Ok(Action::None)
+                }
+                KeyCode::Char('K') => {
+                    self.scroll = self.scroll.saturating_sub(1);
+                    self.exchange_scroll = self.exchange_scroll.saturating_sub(1);
+                    Ok(Action::None)
+                }
+                // g: both panels back to the top, focus unchanged
+                KeyCode::Char('g') => {
+                    self.scroll = 0;
+                    self.exchange_scroll = 0;
+                    Ok(Action::None)
+                }
                 KeyCode::Char('h') | KeyCode::Left => {
                     self.active_panel = ActivePanel::Definition;
                     Ok(Action::None)
                 }
-                KeyCode::Char('l') | KeyCode::Right | KeyCode::Tab => {
+                KeyCode::Char('l') | KeyCode::Right => {
                     self.active_panel = ActivePanel::Exchange;
                     Ok(Action::None)
                 }
-                KeyCode::Char('1') => {
-                    self.submit_review(1)?;
-                    if self.is_complete() {
-                        Ok(Action::NavigateTo(Screen::Dashboard))
-                    } else {
-                        Ok(Action::None)
-                    }
-                }
-                KeyCode::Char('2') => {
-                    self.submit_review(2)?;
-                    if self.is_complete() {
-                        Ok(Action::NavigateTo(Screen::Dashboard))
-                    } else {
-                        Ok(Action::None)
-                    }
-                }
-                KeyCode::Char('3') => {
-                    self.submit_review(3)?;
-                    if self.is_complete() {
-                        Ok(Action::NavigateTo(Screen::Dashboard))
-                    } else {
-                        Ok(Action::None)
-                    }
+                // Tab switches focus back and forth, as it does everywhere else
+                KeyCode::Tab | KeyCode::BackTab => {
+                    self.active_panel = match self.active_panel {
+                        ActivePanel::Definition => ActivePanel::Exchange,
+                        ActivePanel::Exchange => ActivePanel::Definition,
+                    };
+                    Ok(Action::None)
                 }
-                KeyCode::Char('4') => {
-                    self.submit_review(4)?;
-                    if self.is_complete() {
-                        Ok(Action::NavigateTo(Screen::Dashboard))
-                    } else {
-                        Ok(Action::None)
-                    }
+                // After the last card AppV2 decides where to go (setting "复习完成后")
+                KeyCode::Char(c @ '1'..='4') => {
+                    self.submit_review(c as u8 - b'0')?;
+                    Ok(Action::None)
                 }
                 _ => Ok(Action::None),
             },
@@ -266,65 +1088,33 @@ impl Component for ReviewComponent {
             let inner_area = block.inner(area);
             frame.render_widget(block, area);
 
-            let layout = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([
-                    Constraint::Length(3),      // Progress bar + Wordbook info
-                    Constraint::Length(5),      // Word + Phonetic + Metadata
-                    Constraint::Min(10),        // Definition (scrollable)
-                ])
-                .split(inner_area);
-
-            // Progress bar + Wordbook info
-            let progress_label = if let Some((tag, shuffle)) = &self.wordbook_info {
-                // Generate wordbook icon (first letter)
-                let icon = tag.chars().next().unwrap_or('W').to_uppercase().to_string();
-                let tag_display = tag.split_whitespace()
-                    .map(|t| match t {
-                        "zk" => "中考",
-                        "gk" => "高考",
-                        "cet4" => "CET-4",
-                        "cet6" => "CET-6",
-                        "ky" => "考研",
-                        "toefl" => "TOEFL",
-                        "ielts" => "IELTS",
-                        "gre" => "GRE",
-                        _ => t,
-                    })
-                    .collect::<Vec<_>>()
-                    .join(" · ");
-                let mode_icon = if *shuffle { "🔀" } else { "📚" };
-                format!(
-                    "📖 [{icon}] {tag_display} {mode_icon}  |  Progress: {}/{} ({})",
-                    self.completed_count,
-                    self.total_count,
-                    self.total_count - self.completed_count
-                )
-            } else {
-                format!(
-                    "Progress: {}/{} (Remaining: {})",
-                    self.completed_count,
-                    self.total_count,
-                    self.total_count - self.completed_count
-                )
-            };
-            let progress_bar = ProgressBar::new(self.completed_count, self.total_count)
-                .with_label(progress_label)
-                .with_color(Theme::PRIMARY);
-            progress_bar.render(frame, layout[0]);
-
             // Word Header (Word + Phonetic + Metadata in one compact area)
             let mut header_lines = vec![];
             
-            // Line 1: Word + Phonetic
-            let mut word_line_spans = vec![
+            // Line 1: Word + Phonetic (spelling is the answer in 中→英 mode)
+            let recall_spelling = matches!(self.current_direction(), ReviewDirection::ZhToEn | ReviewDirection::PhoneticToWord)
+                && self.state == ReviewState::Question;
+            // 问题面只显示设置里勾选的提示，答案面全部显示
+            let shows = |field| self.state != ReviewState::Question || self.card_front.contains(&field);
+            let mut word_line_spans = vec![if recall_spelling {
+                Span::styled(
+                    masked_spelling(&word.spelling, self.hints_used),
+                    Theme::text_headword().add_modifier(Modifier::BOLD),
+                )
+            } else {
                 Span::styled(
                     &word.spelling,
-                    Theme::text_title()
+                    Theme::text_headword()
                         .add_modifier(Modifier::UNDERLINED),
-                ),
-            ];
-            if let Some(phonetic) = &word.phonetic {
+                )
+            }];
+            if self.hints_used > 0 {
+                word_line_spans.push(Span::styled(
+                    format!("  💡 提示 {} 次，最高评 {} 分", self.hints_used, MAX_QUALITY_WITH_HINTS),
+                    Theme::text_warning(),
+                ));
+            }
+            if let (false, true, Some(phonetic)) = (recall_spelling, shows(CardFrontField::Phonetic), &word.phonetic) {
                 word_line_spans.push(Span::raw("  "));
                 word_line_spans.push(Span::styled(
                     format!("[ {} ]", phonetic),
@@ -335,7 +1125,7 @@ impl Component for ReviewComponent {
             
             // Line 2: POS + Collins + Oxford
             let mut meta_spans = vec![];
-            if let Some(pos) = &word.pos {
+            if let (true, Some(pos)) = (shows(CardFrontField::Pos), &word.pos) {
                 if !pos.is_empty() {
                     let pos_display = parse_pos(pos);
                     if !pos_display.is_empty() {
@@ -346,7 +1136,8 @@ impl Component for ReviewComponent {
                     }
                 }
             }
-            if word.collins > 0 {
+            let ratings = shows(CardFrontField::Ratings);
+            if ratings && word.collins > 0 {
                 if !meta_spans.is_empty() {
                     meta_spans.push(Span::raw("  |  "));
                 }
@@ -355,7 +1146,7 @@ impl Component for ReviewComponent {
                     Theme::text_info(),
                 ));
             }
-            if word.oxford {
+            if ratings && word.oxford {
                 if !meta_spans.is_empty() {
                     meta_spans.push(Span::raw("  |  "));
                 }
@@ -369,29 +1160,16 @@ impl Component for ReviewComponent {
             }
             
             // Line 3: Tags
-            if let Some(tag) = &word.tag {
+            if let (true, Some(tag)) = (shows(CardFrontField::Tags), &word.tag) {
                 if !tag.is_empty() {
-                    let tags: Vec<&str> = tag.split_whitespace().collect();
-                    let tag_display: Vec<String> = tags.iter().map(|t| {
-                        match *t {
-                            "zk" => "中考",
-                            "gk" => "高考",
-                            "cet4" => "CET-4",
-                            "cet6" => "CET-6",
-                            "ky" => "考研",
-                            "toefl" => "TOEFL",
-                            "ielts" => "IELTS",
-                            "gre" => "GRE",
-                            _ => t,
-                        }.to_string()
-                    }).collect();
+                    let tag_display: Vec<&str> = tag.split_whitespace().map(tag_display_name).collect();
                     header_lines.push(Line::from(vec![
                         Span::styled(
                             "考试: ",
                             Theme::text_secondary(),
                         ),
                         Span::styled(
-                            tag_display.join(" · "),
+                            cap_list(&tag_display, MAX_HEADER_TAGS, " · "),
                             Theme::text_info(),
                         ),
                     ]));
@@ -400,7 +1178,110 @@ impl Component for ReviewComponent {
             
             let header = Paragraph::new(header_lines)
                 .alignment(ratatui::layout::Alignment::Center)
+                .wrap(Wrap { trim: true })
                 .block(Block::default().borders(Borders::NONE));
+            // 按内容算头部高度：最少 5 行，最多留给释义区 10 行
+            let header_max = inner_area.height.saturating_sub(3 + 10).max(HEADER_MIN_HEIGHT);
+            let header_height = (header.line_count(inner_area.width) as u16 + 2)
+                .clamp(HEADER_MIN_HEIGHT, header_max);
+
+            let layout = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(3),      // Progress bar + Wordbook info
+                    Constraint::Length(header_height), // Word + Phonetic + Metadata
+                    Constraint::Min(10),        // Definition (scrollable)
+                ])
+                .split(inner_area);
+
+            // Progress bar + Wordbook info
+            let progress_label = if let Some((tag, order, due_only)) = &self.wordbook_info {
+                // Generate wordbook icon (first letter)
+                let icon = tag.chars().next().unwrap_or('W').to_uppercase().to_string();
+                let tag_display = tag.split_whitespace()
+                    .map(tag_display_name)
+                    .collect::<Vec<_>>()
+                    .join(" · ");
+                let mode_icon = order.icon();
+                let due_icon = if *due_only { " ⏰" } else { "" };
+                format!(
+                    "📖 [{icon}] {tag_display} {mode_icon}{due_icon}  |  Progress: {}/{} ({})",
+                    self.completed_count,
+                    self.total_count,
+                    self.total_count - self.completed_count
+                )
+            } else if let Some((review_done, review_total, new_done, new_total)) = self.mix_progress() {
+                let split = format!("复习 {review_done}/{review_total} · 新词 {new_done}/{new_total}");
+                match self.due_total {
+                    Some(due_total) => format!("{split} · 今日共 {due_total}"),
+                    None => split,
+                }
+            } else if let Some(due_total) = self.due_total {
+                format!(
+                    "本次 {}/{} · 今日共 {} (Remaining: {})",
+                    self.completed_count,
+                    self.total_count,
+                    due_total,
+                    self.total_count - self.completed_count
+                )
+            } else {
+                format!(
+                    "Progress: {}/{} (Remaining: {})",
+                    self.completed_count,
+                    self.total_count,
+                    self.total_count - self.completed_count
+                )
+            };
+            // Mixed session: say whether this card is a new word or a review
+            let progress_label = if self.warmup_ids.contains(&word.id.unwrap_or_default()) {
+                format!("🔥 热身  |  {progress_label}")
+            } else if self.new_card_ids.is_empty() {
+                progress_label
+            } else if self.new_card_ids.contains(&word.id.unwrap_or_default()) {
+                format!("🆕 新词  |  {progress_label}")
+            } else {
+                format!("🔁 复习  |  {progress_label}")
+            };
+            // Grouped session: name the block; its first card announces it
+            let progress_label = match self.block_index.and_then(|index| Some((index, self.tag_blocks.get(index)?))) {
+                Some((index, &(tag, size))) if !self.warmup_ids.contains(&word.id.unwrap_or_default())
+                    && !self.new_card_ids.contains(&word.id.unwrap_or_default()) =>
+                {
+                    let name = tag.map_or("其他", tag_display_name);
+                    if self.block_start {
+                        format!("▶ 新的一组：{name}（{size} 个，第 {}/{} 组）  |  {progress_label}", index + 1, self.tag_blocks.len())
+                    } else {
+                        format!("📚 {name} 组 {}/{}  |  {progress_label}", index + 1, self.tag_blocks.len())
+                    }
+                }
+                _ => progress_label,
+            };
+            let progress_label = match (self.direction, self.recognition_quality) {
+                (ReviewDirection::Both, None) => format!("英→中 1/2  |  {progress_label}"),
+                (ReviewDirection::Both, Some(_)) => format!("中→英 2/2  |  {progress_label}"),
+                _ => progress_label,
+            };
+            let progress_label = if self.is_browsing() {
+                format!(
+                    "👀 浏览模式（不计分）  |  {}/{} · 第 {} 遍",
+                    self.completed_count + 1,
+                    self.total_count,
+                    self.browse_lap
+                )
+            } else {
+                progress_label
+            };
+            let progress_label = if self.card_timer.is_paused() {
+                format!("{progress_label}  |  ⏸ 已暂停")
+            } else {
+                progress_label
+            };
+            let shown = if self.is_browsing() { self.completed_count + 1 } else { self.completed_count };
+            let progress_bar = ProgressBar::new(shown, self.total_count)
+                .with_label(progress_label)
+                .with_color(Theme::PRIMARY);
+            progress_bar.render(frame, layout[0]);
+
             frame.render_widget(header, layout[1]);
 
             // Quality indicator (always shown, in bottom-right corner)
@@ -422,6 +1303,44 @@ impl Component for ReviewComponent {
 
             // Definition
             match self.state {
+                ReviewState::Question if self.current_direction() == ReviewDirection::ZhToEn => {
+                    // The meaning is the prompt; recall the spelling
+                    let mut prompt_lines = vec![Line::from(Span::styled(
+                        "━━━ 中文释义 ━━━",
+                        Theme::text_title(),
+                    ))];
+                    prompt_lines.extend(format_translation(word.translation.as_deref().unwrap_or("")));
+                    prompt_lines.push(Line::from(""));
+                    prompt_lines.push(Line::from(Span::styled(
+                        "Press <h> for a letter hint, <Space> to show the word",
+                        Theme::text_secondary(),
+                    )));
+                    let prompt = Paragraph::new(prompt_lines)
+                        .alignment(ratatui::layout::Alignment::Center)
+                        .wrap(Wrap { trim: true });
+                    self.max_scroll = max_scroll(&prompt, layout[2]);
+                    self.scroll = self.scroll.min(self.max_scroll);
+                    let prompt = prompt.scroll((self.scroll, 0));
+                    frame.render_widget(prompt, layout[2]);
+                }
+                ReviewState::Question if self.current_direction() == ReviewDirection::PhoneticToWord => {
+                    // The phonetic is the prompt; recall spelling and meaning
+                    let prompt = Paragraph::new(vec![
+                        Line::from(Span::styled("━━━ 音标 ━━━", Theme::text_title())),
+                        Line::from(Span::styled(
+                            format!("[ {} ]", word.phonetic.as_deref().unwrap_or("")),
+                            Theme::text_accent().add_modifier(Modifier::BOLD),
+                        )),
+                        Line::from(""),
+                        Line::from(Span::styled(
+                            "Press <Space> to show the word and meaning",
+                            Theme::text_secondary(),
+                        )),
+                    ])
+                    .alignment(ratatui::layout::Alignment::Center)
+                    .wrap(Wrap { trim: true });
+                    frame.render_widget(prompt, layout[2]);
+                }
                 ReviewState::Question => {
                     let hint = Paragraph::new("Press <Space> to show definition")
                         .alignment(ratatui::layout::Alignment::Center)
@@ -448,12 +1367,17 @@ impl Component for ReviewComponent {
                             Theme::text_title(),
                         )));
                         
-                        for line in translation.lines() {
-                            if !line.trim().is_empty() {
-                                left_lines.push(Line::from(format!("  {}", line)));
-                            }
+                        left_lines.extend(format_translation(translation));
+                        push_section_gap(&mut left_lines);
+                    }
+
+                    // Personal glossary note
+                    if let Some(note) = word.glossary.as_ref().and_then(|g| g.note.as_ref()) {
+                        left_lines.push(Line::from(Span::styled("━━━ 📒 个人词汇表 ━━━", Theme::text_info())));
+                        for line in note.lines().filter(|line| !line.trim().is_empty()) {
+                            left_lines.push(Line::from(format!("  {}", line)));
                         }
-                        left_lines.push(Line::from(""));
+                        push_section_gap(&mut left_lines);
                     }
                     
                     // English Definition (bottom)
@@ -462,10 +1386,8 @@ impl Component for ReviewComponent {
                         Theme::text_warning(),
                     )));
                     
-                    for line in word.definition.lines() {
-                        if !line.trim().is_empty() {
-                            left_lines.push(Line::from(format!("  {}", line)));
-                        }
+                    for line in definition_lines(&word.definition, self.clean_definitions) {
+                        left_lines.push(Line::from(format!("  {}", line)));
                     }
                     
                     // Frequency info at bottom
@@ -504,8 +1426,17 @@ impl Component for ReviewComponent {
                     let left_text = Paragraph::new(left_lines)
                         .wrap(Wrap { trim: true })
                         .alignment(ratatui::layout::Alignment::Left)
-                        .scroll((self.scroll, 0))
-                        .block(left_block);
+                        .block(left_block.clone());
+                    self.max_scroll = max_scroll(&left_text, def_layout[0]);
+                    self.scroll = self.scroll.min(self.max_scroll);
+                    let indicator = scroll_indicator(
+                        self.scroll,
+                        content_height(&left_text, def_layout[0]),
+                        def_layout[0].height,
+                    );
+                    let left_text = left_text
+                        .block(left_block.title(Line::from(indicator).right_aligned()))
+                        .scroll((self.scroll, 0));
                     frame.render_widget(left_text, def_layout[0]);
 
                     // Left scrollbar
@@ -577,8 +1508,17 @@ impl Component for ReviewComponent {
                     let right_text = Paragraph::new(right_lines)
                         .wrap(Wrap { trim: true })
                         .alignment(ratatui::layout::Alignment::Left)
-                        .scroll((self.exchange_scroll, 0))
-                        .block(right_block);
+                        .block(right_block.clone());
+                    self.max_exchange_scroll = max_scroll(&right_text, def_layout[1]);
+                    self.exchange_scroll = self.exchange_scroll.min(self.max_exchange_scroll);
+                    let indicator = scroll_indicator(
+                        self.exchange_scroll,
+                        content_height(&right_text, def_layout[1]),
+                        def_layout[1].height,
+                    );
+                    let right_text = right_text
+                        .block(right_block.title(Line::from(indicator).right_aligned()))
+                        .scroll((self.exchange_scroll, 0));
                     frame.render_widget(right_text, def_layout[1]);
 
                     // Right scrollbar
@@ -597,11 +1537,222 @@ impl Component for ReviewComponent {
                     }
                 }
             }
+            // Transition: the card is revealed top-down, rows not reached yet stay dimmed
+            if let Some(progress) = self.transition_progress() {
+                let card_top = layout[1].y;
+                let card_height = inner_area.bottom().saturating_sub(card_top);
+                let revealed = (card_height as f32 * progress).round() as u16;
+                let dimmed = Rect {
+                    x: inner_area.x,
+                    y: card_top + revealed,
+                    width: inner_area.width,
+                    height: card_height - revealed,
+                };
+                frame.buffer_mut().set_style(dimmed, Style::new().add_modifier(Modifier::DIM));
+            }
+            self.render_lookup(frame, area);
+            if let Some(dialog) = &self.quit_confirm {
+                dialog.render(frame, area);
+            }
         } else {
-            let msg = Paragraph::new("No words to review!")
-                .alignment(ratatui::layout::Alignment::Center)
-                .block(Theme::block_with_title(" Review "));
-            frame.render_widget(msg, area);
+            self.render_empty_state(frame, area);
         }
     }
+
+    fn on_tick(&mut self) {
+        if self.current_item.is_some() {
+            self.card_timer.pause_if_idle();
+        }
+        if self.transition_progress().is_some_and(|p| p >= 1.0) {
+            self.transition = None;
+        }
+    }
+
+    fn is_typing(&self) -> bool {
+        self.lookup.as_ref().is_some_and(|lookup| lookup.typing)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Words in the learning log, each due `hours` hours ago
+    fn db_with_due(words: &[(&str, i64)]) -> Database {
+        Database::with_due_words(words).unwrap()
+    }
+
+    /// Spellings in the order the session presents them
+    fn presented(review: &mut ReviewComponent) -> Vec<String> {
+        let mut order = Vec::new();
+        while let Some((word, _)) = &review.current_item {
+            order.push(word.spelling.clone());
+            review.next_card();
+        }
+        order
+    }
+
+    #[test]
+    fn due_review_presents_most_overdue_first() {
+        let mut review = ReviewComponent::new(db_with_due(&[("recent", 1), ("oldest", 72), ("middle", 24)]));
+        assert!(review.start_review(ReviewMode::Due).unwrap());
+        assert_eq!(presented(&mut review), ["oldest", "middle", "recent"]);
+    }
+
+    #[test]
+    fn new_review_presents_highest_priority_first() {
+        let db = Database::in_memory().unwrap();
+        for (word, collins) in [("low", 1), ("high", 5), ("mid", 3)] {
+            db.add_test_word(word, "cet4", 0, collins).unwrap();
+        }
+        let mut review = ReviewComponent::new(db);
+        assert!(review.start_review(ReviewMode::New).unwrap());
+        assert_eq!(presented(&mut review), ["high", "mid", "low"]);
+    }
+
+    #[test]
+    fn mixed_review_keeps_both_priority_orders() {
+        let db = db_with_due(&[("recent", 1), ("oldest", 72)]);
+        for (word, collins) in [("low", 1), ("high", 5)] {
+            db.add_test_word(word, "cet4", 0, collins).unwrap();
+        }
+        db.set_learning_mix(LearningMix::Every(1)).unwrap();
+        db.set_mix_order(MixOrder::ReviewsFirst).unwrap();
+        let mut review = ReviewComponent::new(db);
+        assert!(review.start_review(ReviewMode::Due).unwrap());
+        assert_eq!(presented(&mut review), ["oldest", "high", "recent", "low"]);
+    }
+
+    /// Every learning_log and review_history row, to compare before and after a session
+    fn progress_snapshot(db: &Database) -> (Vec<String>, i64) {
+        let logs = db
+            .learn_conn()
+            .prepare("SELECT word_id || ' ' || status || ' ' || next_review FROM learning_log ORDER BY word_id")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap();
+        let history = db.learn_conn().query_row("SELECT COUNT(*) FROM review_history", [], |row| row.get(0)).unwrap();
+        (logs, history)
+    }
+
+    #[test]
+    fn browse_leaves_progress_untouched() {
+        let db = db_with_due(&[("due", 2)]);
+        db.add_test_word("unlearned", "cet4", 1, 5).unwrap();
+        db.set_learning_mix(LearningMix::Every(1)).unwrap();
+        let before = progress_snapshot(&db);
+        let mut review = ReviewComponent::new(db);
+
+        assert!(review.start_browse(ReviewMode::Due).unwrap());
+        assert_eq!(review.total_count, 1, "no new words mixed into a due browse");
+        for _ in 0..3 {
+            review.browse_next();
+        }
+        assert!(review.start_browse(ReviewMode::Wordbook("cet4".to_string(), WordOrder::Frequency, false)).unwrap());
+        assert_eq!(review.total_count, 2, "unlearned wordbook words are shown");
+        for _ in 0..5 {
+            review.browse_next();
+        }
+        assert_eq!(progress_snapshot(&review.db), before);
+    }
+
+    #[test]
+    fn overdue_review_presents_most_overdue_first() {
+        let mut review = ReviewComponent::new(db_with_due(&[("middle", 24), ("recent", 1), ("oldest", 72)]));
+        assert!(review.start_review(ReviewMode::Overdue).unwrap());
+        assert_eq!(presented(&mut review), ["oldest", "middle", "recent"]);
+    }
+
+    /// Dictionary order (id) differs from frequency order (oxford, then collins)
+    fn wordbook_db() -> Database {
+        let db = Database::in_memory().unwrap();
+        db.add_test_word("rare", "cet4", 0, 1).unwrap();
+        db.add_test_word("common", "cet4", 1, 5).unwrap();
+        db.add_test_word("usual", "cet4", 1, 3).unwrap();
+        db
+    }
+
+    #[test]
+    fn wordbook_dictionary_order_follows_the_dictionary() {
+        let mut review = ReviewComponent::new(wordbook_db());
+        assert!(review.start_review(ReviewMode::Wordbook("cet4".to_string(), WordOrder::Dictionary, false)).unwrap());
+        assert_eq!(presented(&mut review), ["rare", "common", "usual"]);
+    }
+
+    #[test]
+    fn wordbook_frequency_order_presents_most_common_first() {
+        let mut review = ReviewComponent::new(wordbook_db());
+        assert!(review.start_review(ReviewMode::Wordbook("cet4".to_string(), WordOrder::Frequency, false)).unwrap());
+        assert_eq!(presented(&mut review), ["common", "usual", "rare"]);
+    }
+
+    #[test]
+    fn both_directions_record_one_row_per_pass() {
+        let db = db_with_due(&[("word", 1)]);
+        db.set_review_direction(ReviewDirection::Both).unwrap();
+        let mut review = ReviewComponent::new(db);
+        assert!(review.start_review(ReviewMode::Due).unwrap());
+        review.submit_review(4).unwrap(); // 英→中
+        review.submit_review(1).unwrap(); // 中→英
+        let rows: Vec<(String, u8)> = review
+            .db
+            .learn_conn()
+            .prepare("SELECT direction, quality FROM review_history ORDER BY id")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap();
+        assert_eq!(rows, [("en_to_zh".to_string(), 4), ("zh_to_en".to_string(), 1)]);
+        // SM-2 and the lapse count see the weaker pass once
+        let repetition: i32 =
+            review.db.learn_conn().query_row("SELECT repetition FROM learning_log", [], |row| row.get(0)).unwrap();
+        assert_eq!(repetition, 0);
+        assert_eq!(review.db.get_lapses(1).unwrap(), 1);
+    }
+
+    fn cards(prefix: char, count: usize) -> Vec<String> {
+        (1..=count).map(|i| format!("{prefix}{i}")).collect()
+    }
+
+    #[test]
+    fn interleave_reviews_first() {
+        assert_eq!(interleave(cards('r', 3), cards('n', 1), 1, MixOrder::ReviewsFirst), ["r1", "n1", "r2", "r3"]);
+        assert_eq!(
+            interleave(cards('r', 5), cards('n', 4), 2, MixOrder::ReviewsFirst),
+            ["r1", "r2", "n1", "r3", "r4", "n2", "r5", "n3", "n4"]
+        );
+    }
+
+    #[test]
+    fn interleave_new_first() {
+        assert_eq!(interleave(cards('r', 1), cards('n', 3), 1, MixOrder::NewFirst), ["n1", "r1", "n2", "n3"]);
+        assert_eq!(
+            interleave(cards('r', 4), cards('n', 2), 3, MixOrder::NewFirst),
+            ["n1", "r1", "r2", "r3", "n2", "r4"]
+        );
+    }
+
+    #[test]
+    fn interleave_with_one_list_empty_keeps_the_other() {
+        assert_eq!(interleave(cards('r', 2), Vec::new(), 3, MixOrder::NewFirst), ["r1", "r2"]);
+        assert_eq!(interleave(Vec::new(), cards('n', 2), 1, MixOrder::ReviewsFirst), ["n1", "n2"]);
+    }
+
+    #[test]
+    fn dedup_queue_keeps_the_first_occurrence_of_a_word() {
+        let db = db_with_due(&[("first", 1), ("second", 2)]);
+        let mut queue = db.get_due_reviews().unwrap();
+        let duplicate = queue[0].clone();
+        queue.push(duplicate);
+        queue.swap(1, 2);
+        let spellings = |queue: &[(Word, LearningLog)]| queue.iter().map(|(w, _)| w.spelling.clone()).collect::<Vec<_>>();
+        let before = spellings(&queue);
+        dedup_queue(&mut queue);
+        assert_eq!(queue.len(), 2);
+        assert_eq!(spellings(&queue), [before[0].clone(), before[2].clone()]);
+    }
 }
+
@@ -0,0 +1,206 @@
+use super::{Action, Component, Screen, SearchSource};
+use crate::components::common::{is_search_focus_key, word_preview_lines, SearchInput};
+use crate::db::Database;
+use crate::models::{GlobalSearchHits, SearchFocusKey, Word};
+use crate::theme::Theme;
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+/// 全局搜索（`/`）：在学过的单词、复习历史和收藏里找词，Enter 跳到对应页面的详情
+pub struct SearchComponent {
+    db: Database,
+    search_input: SearchInput,
+    typing: bool, // Search box has focus
+    focus_key: SearchFocusKey, // Extra key toggling the search box besides i and / (setting)
+    hits: Vec<(SearchSource, Word, String)>, // Flattened groups; String is the row note
+    selected_index: usize,
+    list_state: ListState,
+    searched: bool, // A query has run (distinguishes "no hits" from "not searched yet")
+}
+
+impl SearchSource {
+    fn group_title(self) -> &'static str {
+        match self {
+            SearchSource::Learned => "📚 学过的单词",
+            SearchSource::History => "🕘 复习历史",
+            SearchSource::Favorites => "⭐ 收藏",
+        }
+    }
+}
+
+impl SearchComponent {
+    pub fn new(db: Database) -> Self {
+        let focus_key = db.get_search_focus_key().unwrap_or(SearchFocusKey::Tab);
+        Self {
+            db,
+            search_input: SearchInput::new(),
+            typing: true,
+            focus_key,
+            hits: Vec::new(),
+            selected_index: 0,
+            list_state: ListState::default(),
+            searched: false,
+        }
+    }
+
+    /// Search box toggle key, for the footer
+    pub fn search_focus_key(&self) -> SearchFocusKey {
+        self.focus_key
+    }
+
+    fn run_search(&mut self) -> Result<()> {
+        let GlobalSearchHits { learned, history, favorites } = self.db.global_search(&self.search_input.value)?;
+        self.hits = learned
+            .into_iter()
+            .map(|(word, log)| (SearchSource::Learned, word, format!("{:?} · 间隔 {} 天", log.status, log.interval)))
+            .chain(history.into_iter().map(|(word, reviewed_at, quality)| {
+                let when = DateTime::parse_from_rfc3339(&reviewed_at)
+                    .map(|t| t.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or(reviewed_at);
+                (SearchSource::History, word, format!("{} · 评分 {}", when, quality))
+            }))
+            .chain(favorites.into_iter().map(|word| (SearchSource::Favorites, word, String::new())))
+            .collect();
+        self.selected_index = 0;
+        self.searched = true;
+        Ok(())
+    }
+
+    /// Group header rows come before each source's first hit
+    fn list_index(&self, hit_index: usize) -> usize {
+        let headers = self.hits[..=hit_index]
+            .iter()
+            .enumerate()
+            .filter(|(i, (source, _, _))| *i == 0 || self.hits[i - 1].0 != *source)
+            .count();
+        hit_index + headers
+    }
+}
+
+impl Component for SearchComponent {
+    fn handle_key(&mut self, key: KeyEvent) -> Result<Action> {
+        if self.typing {
+            match key.code {
+                KeyCode::Esc => {
+                    if self.searched {
+                        self.typing = false;
+                        return Ok(Action::None);
+                    }
+                    return Ok(Action::NavigateTo(Screen::Dashboard));
+                }
+                code if is_search_focus_key(self.focus_key, code) => self.typing = false,
+                KeyCode::Enter => {
+                    if !self.search_input.value.trim().is_empty() {
+                        self.run_search()?;
+                        self.typing = false;
+                    }
+                }
+                _ => {
+                    self.search_input.handle_key(key);
+                }
+            }
+            return Ok(Action::None);
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => Ok(Action::NavigateTo(Screen::Dashboard)),
+            code if matches!(code, KeyCode::Char('/') | KeyCode::Char('i')) || is_search_focus_key(self.focus_key, code) => {
+                self.typing = true;
+                Ok(Action::None)
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected_index = (self.selected_index + 1).min(self.hits.len().saturating_sub(1));
+                Ok(Action::None)
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected_index = self.selected_index.saturating_sub(1);
+                Ok(Action::None)
+            }
+            KeyCode::Enter => match self.hits.get(self.selected_index) {
+                Some((source, Word { id: Some(word_id), spelling, .. }, _)) => {
+                    Ok(Action::OpenSearchHit(*source, *word_id, spelling.clone()))
+                }
+                _ => Ok(Action::None),
+            },
+            _ => Ok(Action::None),
+        }
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),  // Search input
+                Constraint::Min(5),     // Grouped hits
+                Constraint::Length(10), // Preview
+            ])
+            .margin(1)
+            .split(area);
+
+        let (search_block, search_text) = if self.typing {
+            (Theme::block_warning().title(" 🔍 全局搜索 [Enter 搜索] "), format!("{}▏", self.search_input.value))
+        } else {
+            (Theme::block_default().title(" 🔍 全局搜索 [/ 编辑] "), self.search_input.value.clone())
+        };
+        let search_widget = Paragraph::new(if search_text.trim_end_matches('▏').is_empty() {
+            "拼写或中文释义（只查学过的单词、复习历史和收藏）".to_string()
+        } else {
+            search_text
+        })
+        .block(search_block)
+        .style(if self.search_input.value.is_empty() { Theme::text_secondary() } else { Theme::text_warning() });
+        frame.render_widget(search_widget, layout[0]);
+
+        if self.hits.is_empty() {
+            let message = if self.searched { "没有找到（词典全库搜索请用 Dictionary 页面）" } else { "" };
+            let empty = Paragraph::new(message)
+                .alignment(ratatui::layout::Alignment::Center)
+                .block(Theme::block_with_title(" 结果 "))
+                .style(Theme::text_secondary());
+            frame.render_widget(empty, layout[1]);
+            return;
+        }
+
+        let mut items: Vec<ListItem> = Vec::new();
+        for (i, (source, word, note)) in self.hits.iter().enumerate() {
+            if i == 0 || self.hits[i - 1].0 != *source {
+                let count = self.hits.iter().filter(|(s, _, _)| s == source).count();
+                items.push(ListItem::new(Line::from(Span::styled(
+                    format!("{} ({})", source.group_title(), count),
+                    Theme::text_success(),
+                ))));
+            }
+            items.push(ListItem::new(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(word.spelling.as_str(), Theme::text_title()),
+                Span::raw("  "),
+                Span::styled(note.as_str(), Theme::text_secondary()),
+            ])));
+        }
+        self.list_state.select(Some(self.list_index(self.selected_index)));
+        let list = List::new(items)
+            .block(Theme::block_default().title(format!(" 结果 ({}) ", self.hits.len())))
+            .highlight_style(Theme::text_title().bg(Theme::PRIMARY).add_modifier(Modifier::BOLD))
+            .highlight_symbol("▶ ");
+        frame.render_stateful_widget(list, layout[1], &mut self.list_state);
+
+        if let Some((_, word, _)) = self.hits.get(self.selected_index) {
+            let preview = Paragraph::new(word_preview_lines(word))
+                .block(Theme::block_accent_with_title(" 详情 "))
+                .style(Theme::text_normal());
+            frame.render_widget(preview, layout[2]);
+        }
+    }
+
+    fn is_typing(&self) -> bool {
+        self.typing
+    }
+}
@@ -1,6 +1,10 @@
 use super::{Action, Component, Screen};
-use crate::db::Database;
-use crate::theme::Theme;
+use crate::db::{Database, AUTO_FAVORITE_LAPSES, DAY_ROLLOVER_HOURS, DEFAULT_PRUNE_KEEP_DAYS, MASTERY_CONSECUTIVE_GOOD, MASTERY_MIN_REPETITIONS, OVERDUE_GRACE_HOURS, REMINDER_HOURS, SESSION_CAPS, WARMUP_COUNTS};
+use crate::glossary::GlossarySource;
+use crate::i18n::{self, t, Lang};
+use crate::components::common::{content_height, cycle, max_scroll, scroll_indicator, ConfirmDialog};
+use crate::models::{CardFrontField, DictColumn, DictEnterAction, DictHlKeys, FreqBand, LearningMix, LearningStatus, MasteredReview, MasteryRule, MixOrder, ReviewDirection, SearchFocusKey, SessionComplete};
+use crate::theme::{BorderDensity, Theme};
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
@@ -10,10 +14,153 @@ use ratatui::{
     widgets::{Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap},
     Frame,
 };
+use std::path::Path;
+
+/// Rating name as on the review footer
+fn quality_name(quality: u8) -> &'static str {
+    match quality {
+        1 => t("key.hard"),
+        2 => t("key.difficult"),
+        3 => t("key.good"),
+        _ => t("key.easy"),
+    }
+}
+
+/// 可调整的设置项（按显示顺序）
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SettingItem {
+    DailyGoal,
+    MasteredReview,
+    MasteryMinRepetition,
+    MasteryConsecutiveGood,
+    AutoFavoriteLapses,
+    ReviewDirection,
+    Animations,
+    StartInReview,
+    ExitSummary,
+    ConfirmReviewQuit,
+    SessionComplete,
+    AutoLimitNewWords,
+    SkipNewToday,
+    SkipProperNouns,
+    FreqBand,
+    LearningMix,
+    MixOrder,
+    Warmup,
+    SessionCap,
+    OverdueGrace,
+    GroupByWordbook,
+    StudyReminder,
+    DayRollover,
+    DictHlKeys,
+    DictEnterAction,
+    SearchFocusKey,
+    DictColumns,
+    CardFront,
+    HideMasteredInDict,
+    RememberDictPosition,
+    CleanDefinitions,
+    DisplayEmphasis,
+    BorderDensity,
+    Glossary,
+    Language,
+    ResetProgress,
+    PruneHistory,
+    AdvancedActions,
+    BulkReview,
+}
+
+/// Longest glossary path accepted in the Settings input
+const MAX_PATH_LEN: usize = 255;
+
+/// Statuses that can be reset in bulk (New words have nothing to reset)
+const RESETTABLE_STATUSES: [LearningStatus; 2] = [LearningStatus::Learning, LearningStatus::Mastered];
+
+/// Ratings the bulk review can apply (same 1-4 scale as the review keys)
+const BULK_QUALITIES: [u8; 4] = [1, 2, 3, 4];
+
+const SETTING_ITEMS: [SettingItem; 39] = [
+    SettingItem::DailyGoal,
+    SettingItem::StudyReminder,
+    SettingItem::DayRollover,
+    SettingItem::StartInReview,
+    SettingItem::ExitSummary,
+    SettingItem::AutoLimitNewWords,
+    SettingItem::OverdueGrace,
+    SettingItem::SkipNewToday,
+    SettingItem::SkipProperNouns,
+    SettingItem::FreqBand,
+    SettingItem::LearningMix,
+    SettingItem::MixOrder,
+    SettingItem::Warmup,
+    SettingItem::SessionCap,
+    SettingItem::GroupByWordbook,
+    SettingItem::MasteryMinRepetition,
+    SettingItem::MasteryConsecutiveGood,
+    SettingItem::MasteredReview,
+    SettingItem::AutoFavoriteLapses,
+    SettingItem::ReviewDirection,
+    SettingItem::CardFront,
+    SettingItem::ConfirmReviewQuit,
+    SettingItem::SessionComplete,
+    SettingItem::Animations,
+    SettingItem::DictHlKeys,
+    SettingItem::DictEnterAction,
+    SettingItem::SearchFocusKey,
+    SettingItem::DictColumns,
+    SettingItem::HideMasteredInDict,
+    SettingItem::RememberDictPosition,
+    SettingItem::CleanDefinitions,
+    SettingItem::DisplayEmphasis,
+    SettingItem::BorderDensity,
+    SettingItem::Glossary,
+    SettingItem::Language,
+    SettingItem::ResetProgress,
+    SettingItem::PruneHistory,
+    SettingItem::AdvancedActions,
+    SettingItem::BulkReview,
+];
 
 pub struct SettingsComponent {
     db: Database,
     daily_goal: i64,
+    mastered_review: MasteredReview,
+    mastery: MasteryRule,
+    review_direction: ReviewDirection,
+    card_front: Vec<CardFrontField>,
+    auto_limit_new_words: bool,
+    skip_new_today: bool,
+    animations: bool,
+    start_in_review: bool,
+    exit_summary: bool,
+    confirm_review_quit: bool,
+    session_complete: SessionComplete,
+    skip_proper_nouns: bool,
+    freq_band: FreqBand,
+    learning_mix: LearningMix,
+    mix_order: MixOrder,
+    warmup_count: i64,
+    session_cap: i64,
+    overdue_grace_hours: i64,
+    auto_favorite_lapses: i64,
+    group_by_wordbook: bool,
+    reminder_hour: Option<u32>,
+    day_rollover_hour: u32,
+    dict_hl_keys: DictHlKeys,
+    dict_enter_action: DictEnterAction,
+    search_focus_key: SearchFocusKey,
+    dict_columns: Vec<DictColumn>,
+    hide_mastered_in_dict: bool,
+    remember_dict_position: bool,
+    clean_definitions: bool,
+    glossary_path: String, // Empty = no personal glossary
+    column_cursor: usize, // Index into DictColumn::ALL picked with ←/→
+    card_front_cursor: usize, // Index into CardFrontField::ALL picked with ←/→
+    reset_status: LearningStatus, // Status targeted by the reset action
+    advanced_actions: bool, // Unlocks BulkReview
+    bulk_quality: u8,       // Rating applied by BulkReview
+    confirm: Option<ConfirmDialog>,
+    selected: usize, // Index into SETTING_ITEMS
     editing: bool,
     input_buffer: String,
     message: Option<String>,
@@ -23,9 +170,77 @@ pub struct SettingsComponent {
 impl SettingsComponent {
     pub fn new(db: Database) -> Result<Self> {
         let daily_goal = db.get_daily_goal()?;
+        let mastered_review = db.get_mastered_review()?;
+        let mastery = db.get_mastery_rule()?;
+        let review_direction = db.get_review_direction()?;
+        let card_front = db.get_card_front()?;
+        let auto_limit_new_words = db.get_auto_limit_new_words()?;
+        let skip_new_today = db.get_skip_new_words_today()?;
+        let animations = db.get_animations()?;
+        let start_in_review = db.get_start_in_review()?;
+        let exit_summary = db.get_exit_summary()?;
+        let confirm_review_quit = db.get_confirm_review_quit()?;
+        let session_complete = db.get_on_session_complete()?;
+        let skip_proper_nouns = db.get_skip_proper_nouns()?;
+        let freq_band = db.get_freq_band()?;
+        let learning_mix = db.get_learning_mix()?;
+        let mix_order = db.get_mix_order()?;
+        let warmup_count = db.get_warmup_count()?;
+        let session_cap = db.get_session_cap()?;
+        let overdue_grace_hours = db.get_overdue_grace_hours()?;
+        let auto_favorite_lapses = db.get_auto_favorite_lapses()?;
+        let group_by_wordbook = db.get_group_by_wordbook()?;
+        let reminder_hour = db.get_reminder_hour()?;
+        let day_rollover_hour = db.get_day_rollover_hour()?;
+        let dict_hl_keys = db.get_dict_hl_keys()?;
+        let dict_enter_action = db.get_dict_enter_action()?;
+        let search_focus_key = db.get_search_focus_key()?;
+        let dict_columns = db.get_dict_columns()?;
+        let hide_mastered_in_dict = db.get_hide_mastered_in_dict()?;
+        let remember_dict_position = db.get_remember_dict_position()?;
+        let clean_definitions = db.get_clean_definitions()?;
+        let glossary_path = db.get_glossary_path()?.unwrap_or_default();
+        let advanced_actions = db.get_advanced_actions()?;
         Ok(Self {
             db,
             daily_goal,
+            mastered_review,
+            mastery,
+            review_direction,
+            card_front,
+            auto_limit_new_words,
+            skip_new_today,
+            animations,
+            start_in_review,
+            exit_summary,
+            confirm_review_quit,
+            session_complete,
+            skip_proper_nouns,
+            freq_band,
+            learning_mix,
+            mix_order,
+            warmup_count,
+            session_cap,
+            overdue_grace_hours,
+            auto_favorite_lapses,
+            group_by_wordbook,
+            reminder_hour,
+            day_rollover_hour,
+            dict_hl_keys,
+            dict_enter_action,
+            search_focus_key,
+            dict_columns,
+            hide_mastered_in_dict,
+            remember_dict_position,
+            clean_definitions,
+            glossary_path,
+            column_cursor: 0,
+            card_front_cursor: 0,
+            reset_status: LearningStatus::Learning,
+            advanced_actions,
+            bulk_quality: 3,
+            confirm: None,
+            selected: 0,
             editing: false,
             input_buffer: String::new(),
             message: None,
@@ -33,9 +248,316 @@ impl SettingsComponent {
         })
     }
 
+    fn selected_item(&self) -> SettingItem {
+        SETTING_ITEMS[self.selected]
+    }
+
+    /// e/Enter: 数字项进入编辑，选项类直接切换到下一个值
+    fn activate_selected(&mut self) -> Result<()> {
+        match self.selected_item() {
+            SettingItem::DailyGoal | SettingItem::Glossary => self.start_editing(),
+            SettingItem::MasteredReview
+            | SettingItem::MasteryMinRepetition
+            | SettingItem::MasteryConsecutiveGood
+            | SettingItem::AutoLimitNewWords
+            | SettingItem::SkipNewToday
+            | SettingItem::Animations
+            | SettingItem::StartInReview
+            | SettingItem::ExitSummary
+            | SettingItem::ConfirmReviewQuit
+            | SettingItem::SessionComplete
+            | SettingItem::SkipProperNouns
+            | SettingItem::FreqBand
+            | SettingItem::LearningMix
+            | SettingItem::MixOrder
+            | SettingItem::Warmup
+            | SettingItem::SessionCap
+            | SettingItem::OverdueGrace
+            | SettingItem::AutoFavoriteLapses
+            | SettingItem::GroupByWordbook
+            | SettingItem::StudyReminder
+            | SettingItem::DayRollover
+            | SettingItem::ReviewDirection
+            | SettingItem::DictHlKeys
+            | SettingItem::DictEnterAction
+            | SettingItem::SearchFocusKey
+            | SettingItem::HideMasteredInDict
+            | SettingItem::RememberDictPosition
+            | SettingItem::CleanDefinitions
+            | SettingItem::DisplayEmphasis
+            | SettingItem::BorderDensity
+            | SettingItem::AdvancedActions
+            | SettingItem::Language => self.cycle_selected(true)?,
+            SettingItem::DictColumns => self.toggle_column()?,
+            SettingItem::CardFront => self.toggle_card_front()?,
+            SettingItem::ResetProgress => self.request_reset()?,
+            SettingItem::PruneHistory => self.request_prune()?,
+            SettingItem::BulkReview => self.request_bulk_review()?,
+        }
+        Ok(())
+    }
+
+    /// Enter: 显示/隐藏 ←/→ 选中的词典列（单词列始终显示）
+    fn toggle_column(&mut self) -> Result<()> {
+        let column = DictColumn::ALL[self.column_cursor];
+        if column == DictColumn::Word {
+            self.message = Some("单词列不能隐藏".to_string());
+            return Ok(());
+        }
+        if let Some(index) = self.dict_columns.iter().position(|c| *c == column) {
+            self.dict_columns.remove(index);
+        } else {
+            self.dict_columns.push(column);
+        }
+        self.db.set_dict_columns(&self.dict_columns)?;
+        self.dict_columns = self.db.get_dict_columns()?; // Back in display order
+        self.message = Some("✓ Settings saved successfully!".to_string());
+        Ok(())
+    }
+
+    /// Enter: 在卡片正面显示/隐藏 ←/→ 选中的提示（拼写或释义本身始终显示）
+    fn toggle_card_front(&mut self) -> Result<()> {
+        let field = CardFrontField::ALL[self.card_front_cursor];
+        if let Some(index) = self.card_front.iter().position(|f| *f == field) {
+            self.card_front.remove(index);
+        } else {
+            self.card_front.push(field);
+        }
+        self.db.set_card_front(&self.card_front)?;
+        self.card_front = self.db.get_card_front()?; // Back in display order
+        self.message = Some("✓ Settings saved successfully!".to_string());
+        Ok(())
+    }
+
+    /// ←/→: 在选项类设置的可选值之间切换并立即保存
+    fn cycle_selected(&mut self, forward: bool) -> Result<()> {
+        match self.selected_item() {
+            SettingItem::DailyGoal | SettingItem::Glossary | SettingItem::PruneHistory => return Ok(()),
+            SettingItem::DictColumns => {
+                // 只移动光标，Enter 才切换显示
+                let indices: Vec<usize> = (0..DictColumn::ALL.len()).collect();
+                self.column_cursor = cycle(&indices, self.column_cursor, forward);
+                return Ok(());
+            }
+            SettingItem::CardFront => {
+                let indices: Vec<usize> = (0..CardFrontField::ALL.len()).collect();
+                self.card_front_cursor = cycle(&indices, self.card_front_cursor, forward);
+                return Ok(());
+            }
+            SettingItem::ResetProgress => {
+                // 只是选择目标状态，不写入数据库
+                self.reset_status = cycle(&RESETTABLE_STATUSES, self.reset_status, forward);
+                return Ok(());
+            }
+            SettingItem::BulkReview => {
+                // 同上，只选评分
+                self.bulk_quality = cycle(&BULK_QUALITIES, self.bulk_quality, forward);
+                return Ok(());
+            }
+            SettingItem::AdvancedActions => {
+                self.advanced_actions = !self.advanced_actions;
+                self.db.set_advanced_actions(self.advanced_actions)?;
+            }
+            SettingItem::MasteredReview => {
+                self.mastered_review = cycle(&MasteredReview::PRESETS, self.mastered_review, forward);
+                self.db.set_mastered_review(self.mastered_review)?;
+            }
+            SettingItem::MasteryMinRepetition => {
+                self.mastery.min_repetition = cycle(&MASTERY_MIN_REPETITIONS, self.mastery.min_repetition, forward);
+                self.db.set_mastery_rule(self.mastery)?;
+            }
+            SettingItem::MasteryConsecutiveGood => {
+                self.mastery.consecutive_good = cycle(&MASTERY_CONSECUTIVE_GOOD, self.mastery.consecutive_good, forward);
+                self.db.set_mastery_rule(self.mastery)?;
+            }
+            SettingItem::ReviewDirection => {
+                self.review_direction = cycle(&ReviewDirection::ALL, self.review_direction, forward);
+                self.db.set_review_direction(self.review_direction)?;
+            }
+            SettingItem::AutoLimitNewWords => {
+                self.auto_limit_new_words = !self.auto_limit_new_words;
+                self.db.set_auto_limit_new_words(self.auto_limit_new_words)?;
+            }
+            SettingItem::SkipNewToday => {
+                self.skip_new_today = !self.skip_new_today;
+                self.db.set_skip_new_words_today(self.skip_new_today)?;
+            }
+            SettingItem::LearningMix => {
+                self.learning_mix = cycle(&LearningMix::PRESETS, self.learning_mix, forward);
+                self.db.set_learning_mix(self.learning_mix)?;
+            }
+            SettingItem::MixOrder => {
+                self.mix_order = cycle(&MixOrder::ALL, self.mix_order, forward);
+                self.db.set_mix_order(self.mix_order)?;
+            }
+            SettingItem::Warmup => {
+                self.warmup_count = cycle(&WARMUP_COUNTS, self.warmup_count, forward);
+                self.db.set_warmup_count(self.warmup_count)?;
+            }
+            SettingItem::SessionCap => {
+                self.session_cap = cycle(&SESSION_CAPS, self.session_cap, forward);
+                self.db.set_session_cap(self.session_cap)?;
+            }
+            SettingItem::OverdueGrace => {
+                self.overdue_grace_hours = cycle(&OVERDUE_GRACE_HOURS, self.overdue_grace_hours, forward);
+                self.db.set_overdue_grace_hours(self.overdue_grace_hours)?;
+            }
+            SettingItem::AutoFavoriteLapses => {
+                self.auto_favorite_lapses = cycle(&AUTO_FAVORITE_LAPSES, self.auto_favorite_lapses, forward);
+                self.db.set_auto_favorite_lapses(self.auto_favorite_lapses)?;
+            }
+            SettingItem::GroupByWordbook => {
+                self.group_by_wordbook = !self.group_by_wordbook;
+                self.db.set_group_by_wordbook(self.group_by_wordbook)?;
+            }
+            SettingItem::StudyReminder => {
+                self.reminder_hour = cycle(&REMINDER_HOURS, self.reminder_hour, forward);
+                self.db.set_reminder_hour(self.reminder_hour)?;
+            }
+            SettingItem::DayRollover => {
+                self.day_rollover_hour = cycle(&DAY_ROLLOVER_HOURS, self.day_rollover_hour, forward);
+                self.db.set_day_rollover_hour(self.day_rollover_hour)?;
+            }
+            SettingItem::FreqBand => {
+                self.freq_band = cycle(&FreqBand::PRESETS, self.freq_band, forward);
+                self.db.set_freq_band(self.freq_band)?;
+            }
+            SettingItem::SkipProperNouns => {
+                self.skip_proper_nouns = !self.skip_proper_nouns;
+                self.db.set_skip_proper_nouns(self.skip_proper_nouns)?;
+            }
+            SettingItem::StartInReview => {
+                self.start_in_review = !self.start_in_review;
+                self.db.set_start_in_review(self.start_in_review)?;
+            }
+            SettingItem::ExitSummary => {
+                self.exit_summary = !self.exit_summary;
+                self.db.set_exit_summary(self.exit_summary)?;
+            }
+            SettingItem::ConfirmReviewQuit => {
+                self.confirm_review_quit = !self.confirm_review_quit;
+                self.db.set_confirm_review_quit(self.confirm_review_quit)?;
+            }
+            SettingItem::SessionComplete => {
+                self.session_complete = cycle(&SessionComplete::ALL, self.session_complete, forward);
+                self.db.set_on_session_complete(self.session_complete)?;
+            }
+            SettingItem::Animations => {
+                self.animations = !self.animations;
+                self.db.set_animations(self.animations)?;
+            }
+            SettingItem::DictHlKeys => {
+                self.dict_hl_keys = cycle(&DictHlKeys::ALL, self.dict_hl_keys, forward);
+                self.db.set_dict_hl_keys(self.dict_hl_keys)?;
+            }
+            SettingItem::DictEnterAction => {
+                self.dict_enter_action = cycle(&DictEnterAction::ALL, self.dict_enter_action, forward);
+                self.db.set_dict_enter_action(self.dict_enter_action)?;
+            }
+            SettingItem::SearchFocusKey => {
+                self.search_focus_key = cycle(&SearchFocusKey::ALL, self.search_focus_key, forward);
+                self.db.set_search_focus_key(self.search_focus_key)?;
+            }
+            SettingItem::HideMasteredInDict => {
+                self.hide_mastered_in_dict = !self.hide_mastered_in_dict;
+                self.db.set_hide_mastered_in_dict(self.hide_mastered_in_dict)?;
+            }
+            SettingItem::RememberDictPosition => {
+                self.remember_dict_position = !self.remember_dict_position;
+                self.db.set_remember_dict_position(self.remember_dict_position)?;
+            }
+            SettingItem::CleanDefinitions => {
+                self.clean_definitions = !self.clean_definitions;
+                self.db.set_clean_definitions(self.clean_definitions)?;
+            }
+            SettingItem::DisplayEmphasis => {
+                let enabled = !Theme::emphasis();
+                self.db.set_display_emphasis(enabled)?;
+                Theme::set_emphasis(enabled);
+            }
+            SettingItem::BorderDensity => {
+                let density = cycle(&BorderDensity::ALL, Theme::border_density(), forward);
+                self.db.set_border_density(density)?;
+                Theme::set_border_density(density);
+            }
+            SettingItem::Language => {
+                let lang = cycle(&Lang::ALL, i18n::lang(), forward);
+                self.db.set_language(lang)?;
+                i18n::set_lang(lang);
+            }
+        }
+        self.message = Some("✓ Settings saved successfully!".to_string());
+        Ok(())
+    }
+
+    fn status_name(status: LearningStatus) -> &'static str {
+        match status {
+            LearningStatus::New => "新单词",
+            LearningStatus::Learning => "学习中",
+            LearningStatus::Mastered => "已掌握",
+        }
+    }
+
+    fn request_reset(&mut self) -> Result<()> {
+        let (_, learning, mastered) = self.db.learning_status_counts()?;
+        let count = match self.reset_status {
+            LearningStatus::Mastered => mastered,
+            _ => learning,
+        };
+        self.confirm = Some(ConfirmDialog::new(
+            "重置学习进度".to_string(),
+            format!(
+                "将 {} 个「{}」单词重置为新单词？复习间隔和熟练度会清零（复习历史保留）",
+                count,
+                Self::status_name(self.reset_status)
+            ),
+        ));
+        Ok(())
+    }
+
+    fn request_prune(&mut self) -> Result<()> {
+        let count = self.db.count_history_older_than(DEFAULT_PRUNE_KEEP_DAYS)?;
+        if count == 0 {
+            self.message = Some(format!("没有超过 {} 天的复习记录，无需清理", DEFAULT_PRUNE_KEEP_DAYS));
+            return Ok(());
+        }
+        self.confirm = Some(ConfirmDialog::new(
+            "清理复习历史".to_string(),
+            format!(
+                "删除 {} 条 {} 天前的复习记录？每天的复习总数会保留，图表不受影响",
+                count, DEFAULT_PRUNE_KEEP_DAYS
+            ),
+        ));
+        Ok(())
+    }
+
+    fn request_bulk_review(&mut self) -> Result<()> {
+        if !self.advanced_actions {
+            self.message = Some("请先开启上面的「高级操作」".to_string());
+            return Ok(());
+        }
+        let count = self.db.get_due_reviews()?.len();
+        if count == 0 {
+            self.message = Some("现在没有到期的单词".to_string());
+            return Ok(());
+        }
+        self.confirm = Some(ConfirmDialog::new(
+            "批量复习到期单词".to_string(),
+            format!(
+                "把 {} 个到期单词都记为「{}」？每个词按 SM-2 重新排期并写入复习历史，无法撤销",
+                count,
+                quality_name(self.bulk_quality)
+            ),
+        ));
+        Ok(())
+    }
+
     fn start_editing(&mut self) {
         self.editing = true;
-        self.input_buffer = self.daily_goal.to_string();
+        self.input_buffer = match self.selected_item() {
+            SettingItem::Glossary => self.glossary_path.clone(),
+            _ => self.daily_goal.to_string(),
+        };
         self.message = None;
     }
 
@@ -46,6 +568,9 @@ impl SettingsComponent {
     }
 
     fn save_setting(&mut self) -> Result<()> {
+        if self.selected_item() == SettingItem::Glossary {
+            return self.save_glossary_path();
+        }
         if let Ok(goal) = self.input_buffer.parse::<i64>() {
             if goal > 0 && goal <= 1000 {
                 self.db.set_daily_goal(goal)?;
@@ -63,10 +588,486 @@ impl SettingsComponent {
             Ok(())
         }
     }
+
+    /// 先试读一遍词汇表，读不了就留在编辑状态显示原因，不保存
+    fn save_glossary_path(&mut self) -> Result<()> {
+        let path = self.input_buffer.trim().to_string();
+        if path.is_empty() {
+            self.message = Some("✓ 已关闭个人词汇表".to_string());
+        } else {
+            match GlossarySource::load(Path::new(&path)) {
+                Ok(glossary) => {
+                    self.message = Some(format!("✓ 已加载个人词汇表（{} 条）", glossary.entry_count()));
+                }
+                Err(e) => {
+                    self.message = Some(format!("Error: {:#}", e));
+                    return Ok(());
+                }
+            }
+        }
+        self.db.set_glossary_path(&path)?;
+        self.glossary_path = path;
+        self.editing = false;
+        self.input_buffer.clear();
+        Ok(())
+    }
+
+    /// 单个设置项的显示：(图标, 名称, 当前值, 说明)
+    fn item_display(&self, item: SettingItem) -> (&'static str, &'static str, String, &'static str) {
+        match item {
+            SettingItem::DailyGoal => (
+                "📊",
+                "每日复习目标",
+                format!("{} 个/天", self.daily_goal),
+                "完成每日目标可在日历上获得打卡标记",
+            ),
+            SettingItem::MasteryMinRepetition => (
+                "🎯",
+                "掌握所需复习次数",
+                if self.mastery.min_repetition == 0 { "不限".to_string() } else { format!("至少 {} 次", self.mastery.min_repetition) },
+                "间隔超过 21 天且连续答对（SM-2 的 repetition）达到这个次数才算掌握；正常复习约 10 次就会超过 21 天",
+            ),
+            SettingItem::MasteryConsecutiveGood => (
+                "✅",
+                "掌握所需连续答对",
+                if self.mastery.consecutive_good == 0 { "不限".to_string() } else { format!("连续 {} 次", self.mastery.consecutive_good) },
+                "评 3 分以上算答对，评 1/2 分或取消掌握后重新计数，防止取消掌握后答对一次又被判为掌握",
+            ),
+            SettingItem::MasteredReview => (
+                "🏆",
+                "已掌握单词复习",
+                self.mastered_review.label(),
+                match self.mastered_review {
+                    MasteredReview::Normal => "已掌握的单词到期后照常复习",
+                    MasteredReview::Reduced(_) => "已掌握单词的复习间隔被放大，复习次数更少",
+                    MasteredReview::Never => "已掌握的单词不再复习（词典中按 u 取消掌握）",
+                },
+            ),
+            SettingItem::AutoFavoriteLapses => (
+                "⭐",
+                "反复忘记自动收藏",
+                if self.auto_favorite_lapses == 0 {
+                    "关闭".to_string()
+                } else {
+                    format!("忘记超过 {} 次", self.auto_favorite_lapses)
+                },
+                "复习时评“忘记”的次数超过这个数，自动加入收藏并提示，方便集中攻克；已收藏的不变",
+            ),
+            SettingItem::ReviewDirection => (
+                "🔁",
+                "复习方向",
+                self.review_direction.label().to_string(),
+                match self.review_direction {
+                    ReviewDirection::EnToZh => "复习时显示单词，回忆中文释义",
+                    ReviewDirection::ZhToEn => "复习时显示释义，回忆拼写（h 逐字母提示，用过提示最高评 3 分）",
+                    ReviewDirection::Both => "每个单词先认读再拼写，各评一次分；按较低的分数安排下次复习，两遍各记一条复习记录",
+                    ReviewDirection::PhoneticToWord => "复习时只显示音标，回忆拼写和释义；没有音标的单词不出现在这种复习里",
+                },
+            ),
+            SettingItem::CardFront => (
+                "🃏",
+                "卡片正面",
+                CardFrontField::ALL
+                    .iter()
+                    .enumerate()
+                    .map(|(i, field)| {
+                        let mark = if self.card_front.contains(field) { "✓" } else { "✗" };
+                        if i == self.card_front_cursor {
+                            format!("[{}{}]", mark, field.label())
+                        } else {
+                            format!("{}{}", mark, field.label())
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                "←/→ 选择，Enter 显示/隐藏复习问题面的提示；翻到答案后全部显示。音标只在英→中时出现在问题面",
+            ),
+            SettingItem::AutoLimitNewWords => (
+                "⚖️ ",
+                "积压时自动减少新词",
+                if self.auto_limit_new_words { "开启" } else { "关闭" }.to_string(),
+                if self.auto_limit_new_words {
+                    "未来 7 天待复习超过每日目标时，按 n 学新词的数量随积压自动减少"
+                } else {
+                    "积压时只在主页提示，学新词数量不变"
+                },
+            ),
+            SettingItem::OverdueGrace => (
+                "⏳",
+                "逾期宽限",
+                if self.overdue_grace_hours == 0 {
+                    "无".to_string()
+                } else {
+                    format!("{} 小时", self.overdue_grace_hours)
+                },
+                "到期后过了这么久还没复习才算逾期（词典里突出显示、主页积压提示里计数）；到期的单词照常可以复习",
+            ),
+            SettingItem::SkipNewToday => (
+                "⏸ ",
+                "今日暂停学新词",
+                if self.skip_new_today { "开启" } else { "关闭" }.to_string(),
+                if self.skip_new_today {
+                    "今天只复习：学新词和复习中穿插新词都不出新词，明天自动恢复（主页 p 也可切换）"
+                } else {
+                    "按每日新词数量正常学新词"
+                },
+            ),
+            SettingItem::LearningMix => (
+                "🔀",
+                "复习中穿插新词",
+                self.learning_mix.label(),
+                match self.learning_mix {
+                    LearningMix::Off => "按 r 只复习到期单词，按 n 单独学新词",
+                    LearningMix::Every(_) => "按 r 的复习中按比例穿插新词，数量不超过每次学新词的上限；进度条分开显示“复习 a/b · 新词 c/d”",
+                },
+            ),
+            SettingItem::MixOrder => (
+                "↕️ ",
+                "穿插顺序",
+                self.mix_order.label().to_string(),
+                "只在开启“复习中穿插新词”时生效",
+            ),
+            SettingItem::Warmup => (
+                "🔥",
+                "复习热身",
+                if self.warmup_count == 0 { "关闭".to_string() } else { format!("先复习 {} 个熟词", self.warmup_count) },
+                "到期复习开头先放几个已掌握或记忆因子高的到期单词，照常评分和安排下次复习",
+            ),
+            SettingItem::SessionCap => (
+                "🧮",
+                "每次复习上限",
+                if self.session_cap == 0 { "不限".to_string() } else { format!("每次 {} 个", self.session_cap) },
+                "到期单词太多时每次只复习这么多，进度显示“本次 N · 今日共 M”，剩下的下次按 r 接着复习",
+            ),
+            SettingItem::GroupByWordbook => (
+                "📚",
+                "按单词本分组复习",
+                if self.group_by_wordbook { "开启" } else { "关闭" }.to_string(),
+                "到期复习时同一单词本（主标签）的词连着出现，每组开头提示组名和数量，组内仍按到期先后",
+            ),
+            SettingItem::StudyReminder => (
+                "⏰",
+                "学习提醒",
+                match self.reminder_hour {
+                    Some(h) => format!("{:02}:00 之后", h),
+                    None => "关闭".to_string(),
+                },
+                "过了这个时间今天还没复习，主页会提示一句（只在程序打开时显示，Esc 关闭当天提示）",
+            ),
+            SettingItem::DayRollover => (
+                "🌙",
+                "每天几点换日",
+                if self.day_rollover_hour == 0 {
+                    "午夜".to_string()
+                } else {
+                    format!("凌晨 {} 点", self.day_rollover_hour)
+                },
+                "这个时间之前的复习算前一天：今日复习数、打卡、连续天数和日历都按这个分界，适合熬夜学习",
+            ),
+            SettingItem::FreqBand => (
+                "📶",
+                "新词词频范围",
+                self.freq_band.label(),
+                match self.freq_band {
+                    FreqBand::All => "学新词不限词频（仍按常用程度排序）",
+                    FreqBand::Band(_, Some(_)) => "只从这个词频排名区间选新词；词典里按 b 可按同一范围筛选",
+                    FreqBand::Band(_, None) => "只选较少见的词（含没有词频数据的词），适合进阶",
+                },
+            ),
+            SettingItem::SkipProperNouns => (
+                "🔤",
+                "过滤专有名词和缩写",
+                if self.skip_proper_nouns { "开启" } else { "关闭" }.to_string(),
+                if self.skip_proper_nouns {
+                    "学新词时跳过全大写的缩写（NASA）和没有小写形式的大写词（London）"
+                } else {
+                    "学新词时专有名词和缩写也会被选中"
+                },
+            ),
+            SettingItem::StartInReview => (
+                "🚀",
+                "启动后直接复习",
+                if self.start_in_review { "开启" } else { "关闭" }.to_string(),
+                if self.start_in_review {
+                    "启动时有待复习单词就直接进入复习，没有则停在主页（也可用 --review 临时开启）"
+                } else {
+                    "启动时先显示主页（可用 --review 参数单次直接复习）"
+                },
+            ),
+            SettingItem::ExitSummary => (
+                "👋",
+                "退出时显示小结",
+                if self.exit_summary { "开启" } else { "关闭" }.to_string(),
+                if self.exit_summary {
+                    "退出后在终端打印今日复习、待复习、连续打卡和下次复习时间"
+                } else {
+                    "退出后终端保持干净"
+                },
+            ),
+            SettingItem::ConfirmReviewQuit => (
+                "🚪",
+                "退出复习前确认",
+                if self.confirm_review_quit { "开启" } else { "关闭" }.to_string(),
+                if self.confirm_review_quit {
+                    "复习中途按 q/Esc 时提示还剩几个未复习，按 y 才退出（已评分的单词都已保存）"
+                } else {
+                    "复习中途按 q/Esc 直接退出，剩下的单词下次再复习"
+                },
+            ),
+            SettingItem::SessionComplete => (
+                "🏁",
+                "复习完成后",
+                self.session_complete.label().to_string(),
+                match self.session_complete {
+                    SessionComplete::Dashboard => "最后一张卡片评分后回到主页，显示完成提示",
+                    SessionComplete::Summary => "最后一张卡片评分后打开总览（主页 o），看看整体进度",
+                    SessionComplete::LoopNew => "最后一张卡片评分后直接开始学新词；没有可学的新词时回到主页",
+                },
+            ),
+            SettingItem::Animations => (
+                "✨",
+                "翻卡动画",
+                if self.animations { "开启" } else { "关闭" }.to_string(),
+                if self.animations {
+                    "显示答案和切换单词时有一个很短的渐显过渡，不影响按键"
+                } else {
+                    "翻卡和切换单词立即显示（默认）"
+                },
+            ),
+            SettingItem::DictHlKeys => (
+                "⌨️ ",
+                "词典 h/l 键",
+                self.dict_hl_keys.label().to_string(),
+                match self.dict_hl_keys {
+                    DictHlKeys::Unbound => "h/l 不做任何事；详情用 Ctrl-d/Ctrl-u 或 </> 滚动",
+                    DictHlKeys::ScrollDetail => "h/l 逐行滚动详情（旧版行为），Ctrl-d/Ctrl-u 仍可用",
+                },
+            ),
+            SettingItem::DictEnterAction => (
+                "⌨️ ",
+                "词典 Enter 键",
+                self.dict_enter_action.label().to_string(),
+                match self.dict_enter_action {
+                    DictEnterAction::OpenDetail => "Enter 打开选中单词的详情浮窗",
+                    DictEnterAction::StartReview => "Enter 立即复习选中的单词（同 R），详情看右侧面板",
+                    DictEnterAction::AddToLearning => "Enter 把还没学的单词加入学习（已在学的不变）",
+                },
+            ),
+            SettingItem::SearchFocusKey => (
+                "⌨️ ",
+                "搜索框切换键",
+                self.search_focus_key.label().to_string(),
+                match self.search_focus_key {
+                    SearchFocusKey::Tab => "词典和全局搜索里 Tab 进出搜索框；复习和详情浮窗里 Tab 切换面板/小节",
+                    SearchFocusKey::F2 => "F2 进出搜索框，Tab 在词典和全局搜索里不做任何事",
+                    SearchFocusKey::Unbound => "只用 i 进入、Esc 退出搜索框，Tab 在词典和全局搜索里不做任何事",
+                },
+            ),
+            SettingItem::DictColumns => (
+                "📋",
+                "词典显示列",
+                DictColumn::ALL
+                    .iter()
+                    .enumerate()
+                    .map(|(i, column)| {
+                        let mark = if self.dict_columns.contains(column) { "✓" } else { "✗" };
+                        if i == self.column_cursor {
+                            format!("[{}{}]", mark, column.label())
+                        } else {
+                            format!("{}{}", mark, column.label())
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                "←/→ 选择列，Enter 显示/隐藏；终端较窄时可以隐藏音标等列",
+            ),
+            SettingItem::HideMasteredInDict => (
+                "🙈",
+                "词典隐藏已掌握",
+                if self.hide_mastered_in_dict { "开启" } else { "关闭" }.to_string(),
+                if self.hide_mastered_in_dict {
+                    "词典默认列表不显示已掌握的单词（标题显示隐藏数量，词典里按 m 临时显示）"
+                } else {
+                    "词典默认列表显示全部学过的单词"
+                },
+            ),
+            SettingItem::RememberDictPosition => (
+                "📌",
+                "记住词典位置",
+                if self.remember_dict_position { "开启" } else { "关闭" }.to_string(),
+                if self.remember_dict_position {
+                    "离开词典时记下搜索、选中的单词和详情滚动位置，再打开词典（包括下次启动）时恢复"
+                } else {
+                    "每次打开词典都从默认列表的第一个单词开始"
+                },
+            ),
+            SettingItem::CleanDefinitions => (
+                "🧹",
+                "整理英文释义",
+                if self.clean_definitions { "开启" } else { "关闭" }.to_string(),
+                if self.clean_definitions {
+                    "显示前去掉 ECDICT 英文释义里的 HTML 标签、字面 \\n 和多余空白，s./r. 显示为 adj./adv.；数据库不变"
+                } else {
+                    "英文释义按 ECDICT 原文显示"
+                },
+            ),
+            SettingItem::DisplayEmphasis => (
+                "🔍",
+                "突出显示单词和释义",
+                if Theme::emphasis() { "开启" } else { "关闭" }.to_string(),
+                if Theme::emphasis() {
+                    "单词和中文释义用白色粗体（高对比），详情各小节之间多空一行，适合看不清小字时使用"
+                } else {
+                    "单词和释义按主题配色显示"
+                },
+            ),
+            SettingItem::BorderDensity => (
+                "🔲",
+                "边框样式",
+                Theme::border_density().label().to_string(),
+                match Theme::border_density() {
+                    BorderDensity::Thick => "各面板用粗线框",
+                    BorderDensity::Plain | BorderDensity::Rounded => "细线框，看起来更轻",
+                    BorderDensity::None => "不画边框，标题单独占一行，小终端里能多显示几行内容",
+                },
+            ),
+            SettingItem::Language => (
+                "🌐",
+                t("settings.language"),
+                i18n::lang().label().to_string(),
+                t("settings.language_desc"),
+            ),
+            SettingItem::Glossary => (
+                "📒",
+                "个人词汇表",
+                if self.glossary_path.is_empty() { "未设置".to_string() } else { self.glossary_path.clone() },
+                "JSON 文件，按拼写给词条补充笔记或覆盖中文释义（Enter 编辑路径，清空后关闭）",
+            ),
+            SettingItem::ResetProgress => (
+                "♻️ ",
+                "重置学习进度",
+                format!("{}单词", Self::status_name(self.reset_status)),
+                "←/→ 选择状态，Enter 确认后批量重置为新单词",
+            ),
+            SettingItem::PruneHistory => (
+                "🧹",
+                "清理复习历史",
+                format!("保留 {} 天", DEFAULT_PRUNE_KEEP_DAYS),
+                "Enter 删除更早的逐条复习记录，数据库更小、统计更快（每日总数保留）",
+            ),
+            SettingItem::AdvancedActions => (
+                "🛠 ",
+                "高级操作",
+                if self.advanced_actions { "开启" } else { "关闭" }.to_string(),
+                "开启后才能使用下面的批量复习（用于离线学习或数据出错后对齐进度）",
+            ),
+            SettingItem::BulkReview => (
+                "⏩",
+                "批量复习到期单词",
+                format!("评为「{}」", quality_name(self.bulk_quality)),
+                if self.advanced_actions {
+                    "←/→ 选择评分，Enter 确认后把所有到期单词按这个评分复习一遍"
+                } else {
+                    "需要先开启「高级操作」"
+                },
+            ),
+        }
+    }
+
+    fn build_settings_lines(&self) -> Vec<Line<'_>> {
+        let mut lines = Vec::new();
+
+        for (index, item) in SETTING_ITEMS.iter().enumerate() {
+            let selected = index == self.selected;
+            let (icon, name, value, description) = self.item_display(*item);
+
+            let value_span = if selected && self.editing {
+                Span::styled(
+                    format!("{}_", self.input_buffer),
+                    Theme::text_warning().add_modifier(Modifier::UNDERLINED),
+                )
+            } else {
+                Span::styled(value, Theme::text_title())
+            };
+
+            lines.push(Line::from(vec![
+                Span::styled(if selected { ">> " } else { "   " }, Theme::text_success()),
+                Span::styled(format!("{} ", icon), Theme::text_warning()),
+                Span::styled(
+                    format!("{}: ", name),
+                    if selected {
+                        Theme::text_success().add_modifier(Modifier::BOLD)
+                    } else {
+                        Theme::text_normal()
+                    },
+                ),
+                value_span,
+            ]));
+            lines.push(Line::from(vec![
+                Span::raw("      "),
+                Span::styled(description, Theme::text_secondary()),
+            ]));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(if self.editing {
+            Line::from(vec![
+                Span::raw(match self.selected_item() {
+                    SettingItem::Glossary => "输入 JSON 文件路径（留空关闭）  ",
+                    _ => "输入目标 (1-1000)  ",
+                }),
+                Span::styled("Enter", Theme::text_success()),
+                Span::raw(" 保存 | "),
+                Span::styled("Esc", Theme::text_accent()),
+                Span::raw(" 取消"),
+            ])
+        } else {
+            Line::from(vec![
+                Span::styled("j/k", Theme::text_title()),
+                Span::raw(" 选择 | "),
+                Span::styled("e/Enter", Theme::text_warning()),
+                Span::raw(" 编辑/切换 | "),
+                Span::styled("←/→", Theme::text_warning()),
+                Span::raw(" 切换选项"),
+            ])
+        });
+
+        lines
+    }
 }
 
 impl Component for SettingsComponent {
+    fn is_typing(&self) -> bool {
+        self.editing
+    }
+
     fn handle_key(&mut self, key: KeyEvent) -> Result<Action> {
+        if let Some(dialog) = &self.confirm {
+            match dialog.handle_key(key) {
+                Some(true) => {
+                    self.confirm = None;
+                    // 对话框打开时选中项不会变，按选中项决定执行哪个操作
+                    match self.selected_item() {
+                        SettingItem::PruneHistory => {
+                            let removed = self.db.prune_history(DEFAULT_PRUNE_KEEP_DAYS)?;
+                            self.message = Some(format!("✓ 已清理 {} 条复习记录", removed));
+                        }
+                        SettingItem::BulkReview => {
+                            let count = self.db.bulk_review(self.bulk_quality)?;
+                            self.message = Some(format!("✓ 已批量复习 {} 个单词", count));
+                        }
+                        _ => {
+                            let count = self.db.reset_by_status(self.reset_status)?;
+                            self.message = Some(format!("✓ 已重置 {} 个单词", count));
+                        }
+                    }
+                }
+                Some(false) => self.confirm = None,
+                None => {}
+            }
+            return Ok(Action::None);
+        }
+
         if self.editing {
             match key.code {
                 KeyCode::Esc => {
@@ -77,6 +1078,12 @@ impl Component for SettingsComponent {
                     self.save_setting()?;
                     Ok(Action::None)
                 }
+                KeyCode::Char(c) if self.selected_item() == SettingItem::Glossary => {
+                    if self.input_buffer.chars().count() < MAX_PATH_LEN {
+                        self.input_buffer.push(c);
+                    }
+                    Ok(Action::None)
+                }
                 KeyCode::Char(c) if c.is_ascii_digit() => {
                     if self.input_buffer.len() < 4 {
                         self.input_buffer.push(c);
@@ -93,15 +1100,31 @@ impl Component for SettingsComponent {
             match key.code {
                 KeyCode::Char('q') | KeyCode::Esc => Ok(Action::NavigateTo(Screen::Dashboard)),
                 KeyCode::Char('e') | KeyCode::Enter => {
-                    self.start_editing();
+                    self.activate_selected()?;
+                    Ok(Action::None)
+                }
+                KeyCode::Char('l') | KeyCode::Right => {
+                    self.cycle_selected(true)?;
+                    Ok(Action::None)
+                }
+                KeyCode::Char('h') | KeyCode::Left => {
+                    self.cycle_selected(false)?;
                     Ok(Action::None)
                 }
                 KeyCode::Char('j') | KeyCode::Down => {
-                    self.scroll = self.scroll.saturating_add(1);
+                    self.selected = (self.selected + 1).min(SETTING_ITEMS.len() - 1);
                     Ok(Action::None)
                 }
                 KeyCode::Char('k') | KeyCode::Up => {
-                    self.scroll = self.scroll.saturating_sub(1);
+                    self.selected = self.selected.saturating_sub(1);
+                    Ok(Action::None)
+                }
+                KeyCode::PageDown => {
+                    self.scroll = self.scroll.saturating_add(5);
+                    Ok(Action::None)
+                }
+                KeyCode::PageUp => {
+                    self.scroll = self.scroll.saturating_sub(5);
                     Ok(Action::None)
                 }
                 _ => Ok(Action::None),
@@ -113,78 +1136,16 @@ impl Component for SettingsComponent {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(10), // Daily goal setting
+                Constraint::Length(SETTING_ITEMS.len() as u16 * 2 + 4), // Setting items
                 Constraint::Length(3),  // Message
                 Constraint::Min(10),    // Help & Rules with scroll
             ])
             .margin(2)
             .split(area);
 
-        // Daily goal setting
-        let goal_lines = if self.editing {
-            vec![
-                Line::from(vec![
-                    Span::styled("📊 ", Theme::text_warning()),
-                    Span::styled(
-                        "每日复习目标",
-                        Theme::text_title(),
-                    ),
-                ]),
-                Line::from(""),
-                Line::from(vec![
-                    Span::raw("输入目标 (1-1000): "),
-                    Span::styled(
-                        &self.input_buffer,
-                        Theme::text_warning()
-                            .add_modifier(Modifier::UNDERLINED),
-                    ),
-                    Span::styled("_", Theme::text_warning()),
-                ]),
-                Line::from(""),
-                Line::from(vec![
-                    Span::styled("Enter", Theme::text_success()),
-                    Span::raw(" 保存 | "),
-                    Span::styled("Esc", Theme::text_accent()),
-                    Span::raw(" 取消"),
-                ]),
-            ]
-        } else {
-            vec![
-                Line::from(vec![
-                    Span::styled("📊 ", Theme::text_warning()),
-                    Span::styled(
-                        "每日复习目标",
-                        Theme::text_title(),
-                    ),
-                ]),
-                Line::from(""),
-                Line::from(vec![
-                    Span::raw("当前目标: "),
-                    Span::styled(
-                        format!("{} 个/天", self.daily_goal),
-                        Theme::text_title(),
-                    ),
-                ]),
-                Line::from(""),
-                Line::from(vec![
-                    Span::raw("按 "),
-                    Span::styled(
-                        "'e'",
-                        Theme::text_warning(),
-                    ),
-                    Span::raw(" 编辑"),
-                ]),
-                Line::from(""),
-                Line::from(vec![
-                    Span::styled("💡 ", Theme::text_secondary()),
-                    Span::styled("完成每日目标可在日历上获得打卡标记", Theme::text_secondary()),
-                ]),
-            ]
-        };
-
-        let goal_widget = Paragraph::new(goal_lines)
+        let settings_widget = Paragraph::new(self.build_settings_lines())
             .block(Theme::block_with_title(" ⚙️  设置 "));
-        frame.render_widget(goal_widget, chunks[0]);
+        frame.render_widget(settings_widget, chunks[0]);
 
         // Message
         if let Some(msg) = &self.message {
@@ -320,9 +1281,18 @@ impl Component for SettingsComponent {
                 Span::raw(" 时，单词被标记为"),
                 Span::styled("\"已掌握\"", Theme::text_success()),
             ]),
-            Line::from("  • 已掌握的单词不会出现在待复习列表中"),
             Line::from("  • 如果评分选择1-2，将重新进入学习状态"),
             Line::from(""),
+            Line::from(vec![
+                Span::raw("  • 已掌握单词如何复习由"),
+                Span::styled("「已掌握单词复习」", Theme::text_title()),
+                Span::raw("设置决定："),
+            ]),
+            Line::from("    正常复习 — 到期照常出现，遗忘风险最低"),
+            Line::from("    降低频率 — 间隔乘以倍数，节省时间，但可能在下次复习前遗忘"),
+            Line::from("    不再复习 — 移出复习队列，最省时间，但长期不复习容易遗忘"),
+            Line::from("  • 在词典中选中已掌握的单词按 u 可取消掌握，立即重新复习"),
+            Line::from(""),
             Line::from(vec![
                 Span::styled("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━", Theme::text_secondary()),
             ]),
@@ -341,19 +1311,24 @@ impl Component for SettingsComponent {
             Line::from(""),
         ];
 
-        let content_height = help_lines.len() as u16;
-        let help_block = Theme::block_with_title(" 📖 学习指南 (j/k 或 ↑/↓ 滚动) ");
+        let help_line_count = help_lines.len() as u16;
+        let help_block = Theme::block_with_title(" 📖 学习指南 (PgUp/PgDn 滚动) ");
         let help_inner = help_block.inner(chunks[2]);
         
         let help_widget = Paragraph::new(help_lines)
             .wrap(Wrap { trim: false })
-            .scroll((self.scroll, 0))
-            .block(help_block)
+            .block(help_block.clone())
             .style(Theme::text_normal());
+        // PgDn 不会滚过最后一行
+        self.scroll = self.scroll.min(max_scroll(&help_widget, chunks[2]));
+        let indicator = scroll_indicator(self.scroll, content_height(&help_widget, chunks[2]), chunks[2].height);
+        let help_widget = help_widget
+            .block(help_block.title(Line::from(indicator).right_aligned()))
+            .scroll((self.scroll, 0));
         frame.render_widget(help_widget, chunks[2]);
 
         // Scrollbar
-        if content_height > help_inner.height {
+        if help_line_count > help_inner.height {
             frame.render_stateful_widget(
                 Scrollbar::new(ScrollbarOrientation::VerticalRight)
                     .begin_symbol(Some("↑"))
@@ -362,9 +1337,13 @@ impl Component for SettingsComponent {
                     vertical: 1,
                     horizontal: 0,
                 }),
-                &mut ScrollbarState::new(content_height as usize)
+                &mut ScrollbarState::new(help_line_count as usize)
                     .position(self.scroll as usize),
             );
         }
+
+        if let Some(dialog) = &self.confirm {
+            dialog.render(frame, area);
+        }
     }
 }
@@ -1,30 +1,148 @@
 use super::{Action, Component, Screen};
-use crate::db::Database;
+use crate::db::{Database, EASE_BUCKET_BOUNDS};
+use crate::models::{ReviewDirection, StatsGranularity};
 use crate::theme::Theme;
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     symbols,
-    text::Span,
+    text::{Line, Span},
     widgets::{Axis, Bar, BarChart, BarGroup, Chart, Dataset, GraphType, Paragraph},
     Frame,
 };
 
+/// Weeks shown by the weekly activity chart (keep the chart title in sync)
+const ACTIVITY_WEEKS: i64 = 8;
+
+/// Bar labels of the ease histogram, one per bucket of `EASE_BUCKET_BOUNDS`
+pub(crate) const EASE_LABELS: [&str; EASE_BUCKET_BOUNDS.len() + 1] = ["1.3-1.5", "1.5-1.8", "1.8-2.1", "2.1-2.5", "2.5+"];
+
 pub struct StatisticsComponent {
+    db: Database,
+    granularity: StatsGranularity, // Restored from settings
     interval_data: Vec<(i32, f64, i64)>, // interval, avg_quality, count
-    daily_data: Vec<(String, i64)>,      // date, count
+    daily_data: Vec<(String, i64)>,      // date (day or week start), count
+    ease_data: [u64; EASE_BUCKET_BOUNDS.len() + 1], // Reviewed words per e_factor bucket
+    direction_retention: Vec<(ReviewDirection, i64, i64)>, // direction, reviews, rated 3+
+    overall_retention: (i64, i64),   // reviews, rated 3+ (bulk reviews left out)
+    first_try_retention: (i64, i64), // Same, first attempt at a word per session only
+}
+
+/// "85%  (170/200)", or "—" before any review
+fn retention_value((reviews, recalled): (i64, i64)) -> Span<'static> {
+    if reviews == 0 {
+        return Span::styled("—", Theme::text_secondary());
+    }
+    Span::styled(
+        format!("{:>3.0}%  ({}/{})", recalled as f64 * 100.0 / reviews as f64, recalled, reviews),
+        Theme::text_title(),
+    )
 }
 
 impl StatisticsComponent {
     pub fn new(db: Database) -> Result<Self> {
+        let granularity = db.get_stats_granularity()?;
         let interval_data = db.get_review_stats_by_interval()?;
-        let daily_data = db.get_daily_review_counts(7)?; // 改为7天
-
-        Ok(Self {
+        let ease_data = db.get_ease_distribution()?;
+        let direction_retention = db.get_retention_by_direction()?;
+        let overall_retention = db.get_overall_retention()?;
+        let first_try_retention = db.get_first_try_retention()?;
+        let mut component = Self {
+            db,
+            granularity,
             interval_data,
-            daily_data,
-        })
+            daily_data: Vec::new(),
+            ease_data,
+            direction_retention,
+            overall_retention,
+            first_try_retention,
+        };
+        component.load_activity()?;
+        Ok(component)
+    }
+
+    fn load_activity(&mut self) -> Result<()> {
+        self.daily_data = match self.granularity {
+            StatsGranularity::Daily => self.db.get_daily_review_counts(7)?, // 改为7天
+            StatsGranularity::Weekly => self.db.get_weekly_review_counts(ACTIVITY_WEEKS)?,
+        };
+        Ok(())
+    }
+
+    /// 记忆因子分布：偏向左侧说明吃力的单词多
+    fn render_ease_distribution(&self, frame: &mut Frame, area: Rect) {
+        let title = " 🧮 Ease Factor (Reviewed Words) ";
+        if self.ease_data.iter().all(|&count| count == 0) {
+            let msg = Paragraph::new("No reviewed words yet.")
+                .alignment(ratatui::layout::Alignment::Center)
+                .block(Theme::block_default().title(title));
+            frame.render_widget(msg, area);
+            return;
+        }
+        let bars: Vec<Bar> = self
+            .ease_data
+            .iter()
+            .zip(EASE_LABELS)
+            .enumerate()
+            .map(|(i, (&count, label))| {
+                // The two lowest buckets are the struggling words
+                let style = if i < 2 { Theme::text_warning() } else { Theme::text_info() };
+                Bar::default()
+                    .value(count)
+                    .label(label.into())
+                    .style(style)
+                    .value_style(Theme::text_accent().add_modifier(ratatui::style::Modifier::BOLD))
+            })
+            .collect();
+        let bar_chart = BarChart::default()
+            .block(Theme::block_default().title(title))
+            .bar_width(7)
+            .bar_gap(1)
+            .data(BarGroup::default().bars(&bars));
+        frame.render_widget(bar_chart, area);
+    }
+
+    /// 按复习方向的保持率：认读（英→中）和拼写（中→英）分开看，找出哪一边更弱
+    fn render_direction_retention(&self, frame: &mut Frame, area: Rect) {
+        let mut lines = vec![
+            Line::from(vec![Span::styled("Overall   ", Theme::text_normal()), retention_value(self.overall_retention)]),
+            Line::from(vec![Span::styled("First try ", Theme::text_normal()), retention_value(self.first_try_retention)]),
+            Line::from(""),
+        ];
+        for direction in ReviewDirection::ALL {
+            let row = self.direction_retention.iter().find(|(d, _, _)| *d == direction);
+            let value = match row {
+                Some((_, reviews, recalled)) => retention_value((*reviews, *recalled)),
+                // The two basic directions are always listed so the comparison is visible
+                None if matches!(direction, ReviewDirection::EnToZh | ReviewDirection::ZhToEn) => {
+                    Span::styled("—", Theme::text_secondary())
+                }
+                None => continue,
+            };
+            // Pad by display width: the labels are CJK
+            let label = Span::raw(direction.short_label());
+            let pad = " ".repeat(10usize.saturating_sub(label.width()));
+            lines.push(Line::from(vec![
+                Span::styled(format!("{}{pad}", label.content), Theme::text_normal()),
+                value,
+            ]));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Rated 3+ / reviews, all history", Theme::text_secondary())));
+        lines.push(Line::from(Span::styled("First try: 1st rating per session", Theme::text_secondary())));
+        if self.direction_retention.iter().any(|(d, _, _)| *d == ReviewDirection::Both) {
+            lines.push(Line::from(Span::styled("双向: older records, one per card", Theme::text_secondary())));
+        }
+        let panel = Paragraph::new(lines).block(Theme::block_default().title(" 🎯 Retention "));
+        frame.render_widget(panel, area);
+    }
+
+    /// g: 按天 / 按周切换，并记住选择
+    fn toggle_granularity(&mut self) -> Result<()> {
+        self.granularity = self.granularity.toggle();
+        self.db.set_stats_granularity(self.granularity)?;
+        self.load_activity()
     }
 }
 
@@ -32,6 +150,10 @@ impl Component for StatisticsComponent {
     fn handle_key(&mut self, key: KeyEvent) -> Result<Action> {
         match key.code {
             KeyCode::Esc | KeyCode::Char('q') => Ok(Action::NavigateTo(Screen::Dashboard)),
+            KeyCode::Char('g') => {
+                self.toggle_granularity()?;
+                Ok(Action::None)
+            }
             _ => Ok(Action::None),
         }
     }
@@ -45,6 +167,20 @@ impl Component for StatisticsComponent {
             ])
             .margin(1)
             .split(area);
+        let bottom = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(62), // Review activity
+                Constraint::Percentage(38), // Ease distribution
+            ])
+            .split(layout[1]);
+        let top = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(68), // Forgetting curve
+                Constraint::Percentage(32), // Retention by direction
+            ])
+            .split(layout[0]);
 
         // Forgetting Curve Chart
         if !self.interval_data.is_empty() {
@@ -96,7 +232,7 @@ impl Component for StatisticsComponent {
                         .labels(vec![Span::raw("1.0"), Span::raw("2.5"), Span::raw("4.0")]),
                 );
 
-            frame.render_widget(chart, layout[0]);
+            frame.render_widget(chart, top[0]);
         } else {
             let msg = Paragraph::new(
                 "No review data available yet.\nComplete some reviews to see the forgetting curve!",
@@ -106,17 +242,22 @@ impl Component for StatisticsComponent {
                 Theme::block_default()
                     .title(" Forgetting Curve "),
             );
-            frame.render_widget(msg, layout[0]);
+            frame.render_widget(msg, top[0]);
         }
+        self.render_direction_retention(frame, top[1]);
 
-        // Daily Review Count Bar Chart (Last 7 Days)
+        // Review Count Bar Chart (last 7 days or last 8 weeks)
+        let activity_title = match self.granularity {
+            StatsGranularity::Daily => " 📊 Daily Review Activity (Last 7 Days) ",
+            StatsGranularity::Weekly => " 📊 Weekly Review Activity (Last 8 Weeks) ",
+        };
         if !self.daily_data.is_empty() {
             // Prepare bar chart data
             let bars: Vec<Bar> = self
                 .daily_data
                 .iter()
                 .map(|(date, count)| {
-                    // Extract day (MM-DD format)
+                    // Extract day (MM-DD format; the Monday of the week when weekly)
                     let label = if date.len() >= 10 {
                         &date[5..10] // Extract MM-DD
                     } else {
@@ -132,7 +273,7 @@ impl Component for StatisticsComponent {
 
             let bar_chart = BarChart::default()
                 .block(
-                    Theme::block_success_with_title(" 📊 Daily Review Activity (Last 7 Days) ")
+                    Theme::block_success_with_title(activity_title)
                 )
                 .bar_width(9)
                 .bar_gap(2)
@@ -140,16 +281,18 @@ impl Component for StatisticsComponent {
                 .value_style(Theme::text_normal())
                 .data(BarGroup::default().bars(&bars));
 
-            frame.render_widget(bar_chart, layout[1]);
+            frame.render_widget(bar_chart, bottom[0]);
         } else {
             let msg = Paragraph::new(
                 "No daily review data available yet.\nComplete some reviews to see your activity!",
             )
             .alignment(ratatui::layout::Alignment::Center)
             .block(
-                Theme::block_success_with_title(" 📊 Daily Review Activity ")
+                Theme::block_success_with_title(activity_title)
             );
-            frame.render_widget(msg, layout[1]);
+            frame.render_widget(msg, bottom[0]);
         }
+
+        self.render_ease_distribution(frame, bottom[1]);
     }
 }
@@ -0,0 +1,168 @@
+use super::statistics::EASE_LABELS;
+use super::{Action, Component, Screen};
+use crate::db::{Database, RETENTION_DAYS};
+use crate::models::{tag_display_name, DeckSummary};
+use crate::theme::Theme;
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+/// Width of the text bars (ease buckets, wordbook coverage)
+const BAR_WIDTH: usize = 20;
+
+/// 总览（主页 `o`）：把主页和统计页分散的数字汇总成一页卡片，j/k 按行滚动
+pub struct SummaryComponent {
+    summary: DeckSummary,
+    scroll: usize, // First card row shown
+}
+
+/// `filled` of `total` as a fixed-width █/░ bar
+fn text_bar(filled: usize, total: usize) -> String {
+    let cells = if total == 0 { 0 } else { (filled * BAR_WIDTH).div_ceil(total).min(BAR_WIDTH) };
+    format!("{}{}", "█".repeat(cells), "░".repeat(BAR_WIDTH - cells))
+}
+
+fn stat_line(label: &str, value: String) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(format!("{label}: "), Theme::text_secondary()),
+        Span::styled(value, Theme::text_title()),
+    ])
+}
+
+impl SummaryComponent {
+    pub fn new(db: Database) -> Result<Self> {
+        Ok(Self { summary: db.get_deck_summary()?, scroll: 0 })
+    }
+
+    /// Cards as (title, lines), laid out two per row in this order
+    fn cards(&self) -> Vec<(&'static str, Vec<Line<'static>>)> {
+        let s = &self.summary;
+        let learned = s.new + s.learning + s.mastered;
+
+        let status = vec![
+            stat_line("学过的单词", learned.to_string()),
+            stat_line("新词", s.new.to_string()),
+            stat_line("学习中", s.learning.to_string()),
+            stat_line("已掌握", s.mastered.to_string()),
+            stat_line("暂停复习", s.suspended.to_string()),
+            stat_line("收藏", s.favorites.to_string()),
+        ];
+
+        let due = vec![
+            stat_line("今天（含逾期）", s.due_today.to_string()),
+            stat_line("7 天内", s.due_week.to_string()),
+            stat_line("平均间隔", format!("{:.1} 天", s.avg_interval)),
+            stat_line("平均记忆因子", format!("{:.2}", s.avg_ease)),
+        ];
+
+        let retention = if s.recent_reviews == 0 {
+            "—（最近没有复习）".to_string()
+        } else {
+            format!(
+                "{:.0}%（{}/{} 次评 3 分以上）",
+                s.recent_recalled as f64 * 100.0 / s.recent_reviews as f64,
+                s.recent_recalled,
+                s.recent_reviews
+            )
+        };
+        let habit = vec![
+            stat_line("连续打卡", format!("{} 天", s.streak)),
+            stat_line(&format!("近 {RETENTION_DAYS} 天复习"), format!("{} 次", s.recent_reviews)),
+            stat_line("保持率", retention),
+        ];
+
+        let most = s.ease_buckets.iter().copied().max().unwrap_or(0) as usize;
+        let ease = if most == 0 {
+            vec![Line::from(Span::styled("还没有复习过的单词", Theme::text_secondary()))]
+        } else {
+            s.ease_buckets
+                .iter()
+                .zip(EASE_LABELS)
+                .enumerate()
+                .map(|(i, (&count, label))| {
+                    // The two lowest buckets are the struggling words, as in Statistics
+                    let style = if i < 2 { Theme::text_warning() } else { Theme::text_info() };
+                    Line::from(vec![
+                        Span::styled(format!("{label:<7} "), Theme::text_secondary()),
+                        Span::styled(text_bar(count as usize, most), style),
+                        Span::raw(format!(" {count}")),
+                    ])
+                })
+                .collect()
+        };
+
+        let coverage = if s.tag_coverage.is_empty() {
+            vec![Line::from(Span::styled("ECDICT 没有考试标签", Theme::text_secondary()))]
+        } else {
+            s.tag_coverage
+                .iter()
+                .map(|(tag, learned, total)| {
+                    // Pad by display width so CJK names (中考) line up with CET-4
+                    let name = Span::raw(tag_display_name(tag));
+                    let pad = " ".repeat(7usize.saturating_sub(name.width()));
+                    Line::from(vec![
+                        Span::styled(format!("{}{pad}", name.content), Theme::text_secondary()),
+                        Span::styled(text_bar(*learned, *total), Theme::text_success()),
+                        Span::raw(format!(
+                            " {learned}/{total} ({:.1}%)",
+                            *learned as f64 * 100.0 / (*total).max(1) as f64
+                        )),
+                    ])
+                })
+                .collect()
+        };
+
+        vec![
+            (" 📚 单词状态 ", status),
+            (" ⏰ 到期与间隔 ", due),
+            (" 🔥 打卡与保持率 ", habit),
+            (" 🧮 记忆因子分布 ", ease),
+            (" 🏷  单词本覆盖 ", coverage),
+        ]
+    }
+}
+
+impl Component for SummaryComponent {
+    fn handle_key(&mut self, key: KeyEvent) -> Result<Action> {
+        let rows = self.cards().len().div_ceil(2);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return Ok(Action::NavigateTo(Screen::Dashboard)),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll = (self.scroll + 1).min(rows.saturating_sub(1)),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            _ => {}
+        }
+        Ok(Action::None)
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let area = Rect {
+            x: area.x + 1,
+            y: area.y + 1,
+            width: area.width.saturating_sub(2),
+            height: area.height.saturating_sub(2),
+        };
+        let cards = self.cards();
+        let mut y = area.y;
+        for row in cards.chunks(2).skip(self.scroll) {
+            let height = row.iter().map(|(_, lines)| lines.len()).max().unwrap_or(0) as u16 + 2;
+            if y >= area.bottom() {
+                break;
+            }
+            let row_area = Rect { x: area.x, y, width: area.width, height: height.min(area.bottom() - y) };
+            let columns = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .split(row_area);
+            for ((title, lines), column) in row.iter().zip(columns.iter()) {
+                let card = Paragraph::new(lines.clone()).block(Theme::block_with_title(*title));
+                frame.render_widget(card, *column);
+            }
+            y += height;
+        }
+    }
+}
@@ -1,7 +1,10 @@
 use super::{Action, Component, Screen};
+use crate::components::common::{cycle, ConfirmDialog, Popup};
 use crate::db::Database;
+use crate::models::{tag_display_name, PriorityWordbook, WordOrder, WordbookOverlap};
 use crate::theme::Theme;
 use anyhow::Result;
+use chrono::{Duration, Local, NaiveDate};
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Margin, Rect},
@@ -12,30 +15,260 @@ use ratatui::{
 };
 
 pub struct WordbookComponent {
+    db: Database,
     wordbooks: Vec<(String, usize)>, // (tag, count)
     selected_index: usize,
-    shuffle_mode: bool,
+    order: WordOrder, // s cycles 顺序 / 乱序 / 词典序
+    due_only: bool, // Only the words of the book that are due, instead of the whole book
+    focus_tag: Option<String>, // Wordbook new words are drawn from first
+    priority: Option<PriorityWordbook>, // Time-boxed boost for new words and due reviews
+    priority_input: Option<String>,     // End date being typed after `p`
+    priority_error: Option<&'static str>,
+    compare_mark: Option<usize>, // First wordbook picked for comparison
+    overlap: Option<(String, String, WordbookOverlap)>, // (tag_a, tag_b, result)
+    popup: Popup,
+    pending: Option<(BulkAction, ConfirmDialog)>, // Bulk action waiting for confirmation
+    message: Option<String>,                      // Result of the last bulk action
+}
+
+/// Longest priority boost, in days
+const MAX_PRIORITY_DAYS: i64 = 365;
+
+/// Parse "+N" / "N" (days from today) or "YYYY-MM-DD" into the last day of the boost
+fn parse_priority_until(input: &str) -> Result<NaiveDate, &'static str> {
+    let input = input.trim();
+    let today = Local::now().date_naive();
+    let days = input.strip_prefix('+').unwrap_or(input);
+    let until = match days.parse::<i64>() {
+        Ok(days) => today + Duration::days(days),
+        Err(_) => NaiveDate::parse_from_str(input, "%Y-%m-%d").map_err(|_| "格式应为 +N 或 YYYY-MM-DD")?,
+    };
+    if until < today {
+        return Err("日期不能早于今天");
+    }
+    if (until - today).num_days() > MAX_PRIORITY_DAYS {
+        return Err("最多优先 365 天");
+    }
+    Ok(until)
+}
+
+/// 对整本单词本的批量操作
+enum BulkAction {
+    Suspend(String),
+    Resume(String),
 }
 
 impl WordbookComponent {
     pub fn new(db: Database) -> Result<Self> {
         let wordbooks = db.get_wordbooks()?;
+        let focus_tag = db.get_focus_tag()?;
+        let priority = db.get_priority_wordbook()?;
         Ok(Self {
+            db,
             wordbooks,
             selected_index: 0,
-            shuffle_mode: false,
+            order: WordOrder::Frequency,
+            due_only: false,
+            focus_tag,
+            priority,
+            priority_input: None,
+            priority_error: None,
+            compare_mark: None,
+            overlap: None,
+            popup: Popup::new("单词本对比".to_string()),
+            pending: None,
+            message: None,
         })
     }
 
-    fn toggle_shuffle(&mut self) {
-        self.shuffle_mode = !self.shuffle_mode;
+    /// x/X: 弹出确认框，暂停或恢复当前单词本中已学习的单词
+    fn request_bulk_action(&mut self, suspend: bool) {
+        let Some((tag, _)) = self.wordbooks.get(self.selected_index) else {
+            return;
+        };
+        let name = tag.split_whitespace().map(tag_display_name).collect::<Vec<_>>().join(" · ");
+        let (action, message) = if suspend {
+            (
+                BulkAction::Suspend(tag.clone()),
+                format!("暂停「{}」中所有已学习的单词？暂停后不再出现在复习中", name),
+            )
+        } else {
+            (
+                BulkAction::Resume(tag.clone()),
+                format!("恢复「{}」中被暂停的单词？到期后将重新出现在复习中", name),
+            )
+        };
+        self.pending = Some((action, ConfirmDialog::new("批量操作".to_string(), message)));
+    }
+
+    /// f: 设为/取消重点单词本（学新词时优先取这本书的词）
+    fn toggle_focus(&mut self) -> Result<()> {
+        let Some((tag, _)) = self.wordbooks.get(self.selected_index) else {
+            return Ok(());
+        };
+        self.focus_tag = if self.focus_tag.as_ref() == Some(tag) { None } else { Some(tag.clone()) };
+        self.db.set_focus_tag(self.focus_tag.as_deref())?;
+        self.message = Some(match &self.focus_tag {
+            Some(_) => "✓ 已设为重点单词本，学新词时优先从这本书取词".to_string(),
+            None => "✓ 已取消重点单词本，学新词按全库词频取词".to_string(),
+        });
+        Ok(())
+    }
+
+    /// p: 限时优先当前单词本（输入截止日期）；对已优先的单词本再按 p 取消
+    fn start_priority(&mut self) -> Result<()> {
+        let Some((tag, _)) = self.wordbooks.get(self.selected_index) else {
+            return Ok(());
+        };
+        if self.priority.as_ref().is_some_and(|priority| &priority.tag == tag) {
+            self.priority = None;
+            self.db.set_priority_wordbook(None)?;
+            self.message = Some("✓ 已取消限时优先".to_string());
+        } else {
+            self.priority_input = Some(String::new());
+            self.priority_error = None;
+        }
+        Ok(())
+    }
+
+    fn save_priority(&mut self, input: &str) -> Result<()> {
+        let Some((tag, _)) = self.wordbooks.get(self.selected_index) else {
+            return Ok(());
+        };
+        match parse_priority_until(input) {
+            Ok(until) => {
+                let priority = PriorityWordbook { tag: tag.clone(), until };
+                self.db.set_priority_wordbook(Some(&priority))?;
+                self.message = Some(format!(
+                    "✓ 到 {} 为止学新词和到期复习都先排这本书的词，之后自动恢复",
+                    until.format("%Y-%m-%d")
+                ));
+                self.priority = Some(priority);
+                self.priority_input = None;
+            }
+            Err(error) => self.priority_error = Some(error),
+        }
+        Ok(())
+    }
+
+    fn handle_priority_input(&mut self, key: KeyEvent) -> Result<()> {
+        let Some(input) = &mut self.priority_input else {
+            return Ok(());
+        };
+        match key.code {
+            KeyCode::Esc => self.priority_input = None,
+            KeyCode::Enter => {
+                let input = input.clone();
+                self.save_priority(&input)?;
+            }
+            KeyCode::Backspace => {
+                input.pop();
+                self.priority_error = None;
+            }
+            KeyCode::Char(c) if (c.is_ascii_digit() || c == '+' || c == '-') && input.len() < 10 => {
+                input.push(c);
+                self.priority_error = None;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn run_bulk_action(&mut self, action: BulkAction) -> Result<()> {
+        self.message = Some(match action {
+            BulkAction::Suspend(tag) => format!("✓ 已暂停 {} 个单词", self.db.suspend_by_tag(&tag)?),
+            BulkAction::Resume(tag) => format!("✓ 已恢复 {} 个单词", self.db.unsuspend_by_tag(&tag)?),
+        });
+        Ok(())
+    }
+
+    /// 第一次按 c 标记单词本，第二次按 c 与当前选中的单词本对比
+    fn mark_or_compare(&mut self) -> Result<()> {
+        match self.compare_mark.take() {
+            None => self.compare_mark = Some(self.selected_index),
+            Some(marked) if marked != self.selected_index => {
+                if let (Some((tag_a, _)), Some((tag_b, _))) = (
+                    self.wordbooks.get(marked),
+                    self.wordbooks.get(self.selected_index),
+                ) {
+                    let overlap = self.db.wordbook_overlap(tag_a, tag_b)?;
+                    self.overlap = Some((tag_a.clone(), tag_b.clone(), overlap));
+                    self.popup.reset_scroll();
+                }
+            }
+            // Pressing c on the marked book again cancels the mark
+            Some(_) => {}
+        }
+        Ok(())
+    }
+
+    fn build_overlap_lines(tag_a: &str, tag_b: &str, overlap: &WordbookOverlap) -> Vec<Line<'static>> {
+        let name_a = tag_display_name(tag_a).to_string();
+        let name_b = tag_display_name(tag_b).to_string();
+        let total_a = overlap.only_a + overlap.shared;
+        let total_b = overlap.only_b + overlap.shared;
+        let percent = |part: usize, total: usize| {
+            if total > 0 { part as f64 / total as f64 * 100.0 } else { 0.0 }
+        };
+
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled(format!("A: {}", name_a), Theme::text_title()),
+                Span::styled("  vs  ", Theme::text_secondary()),
+                Span::styled(format!("B: {}", name_b), Theme::text_title()),
+            ]),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled(format!("仅 {}: ", name_a), Theme::text_normal()),
+                Span::styled(format!("{}", overlap.only_a), Theme::text_warning()),
+                Span::styled(" 词", Theme::text_secondary()),
+            ]),
+            Line::from(vec![
+                Span::styled("共有: ", Theme::text_normal()),
+                Span::styled(format!("{}", overlap.shared), Theme::text_success()),
+                Span::styled(
+                    format!(
+                        " 词 (占 {} {:.1}%，占 {} {:.1}%)",
+                        name_a,
+                        percent(overlap.shared, total_a),
+                        name_b,
+                        percent(overlap.shared, total_b)
+                    ),
+                    Theme::text_secondary(),
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled(format!("仅 {}: ", name_b), Theme::text_normal()),
+                Span::styled(format!("{}", overlap.only_b), Theme::text_warning()),
+                Span::styled(" 词", Theme::text_secondary()),
+            ]),
+            Line::from(""),
+            Line::from(Span::styled("━━━ 共有词示例 ━━━", Theme::text_accent())),
+        ];
+
+        if overlap.shared_sample.is_empty() {
+            lines.push(Line::from(Span::styled("  (无)", Theme::text_secondary())));
+        } else {
+            for chunk in overlap.shared_sample.chunks(5) {
+                lines.push(Line::from(Span::styled(
+                    format!("  {}", chunk.join(", ")),
+                    Theme::text_normal(),
+                )));
+            }
+        }
+
+        lines
+    }
+
+    fn cycle_order(&mut self) {
+        self.order = cycle(&WordOrder::ALL, self.order, true);
     }
 
     fn select_wordbook(&self) -> Result<Action> {
         if let Some((tag, _count)) = self.wordbooks.get(self.selected_index) {
-            // 返回 Action，携带 tag 和 shuffle 信息
+            // 返回 Action，携带 tag 和单词顺序
             // 这里需要在 Action 枚举中添加新的变体
-            Ok(Action::StartWordbookReview(tag.clone(), self.shuffle_mode))
+            Ok(Action::StartWordbookReview(tag.clone(), self.order, self.due_only))
         } else {
             Ok(Action::None)
         }
@@ -44,11 +277,75 @@ impl WordbookComponent {
 
 impl Component for WordbookComponent {
     fn handle_key(&mut self, key: KeyEvent) -> Result<Action> {
+        if let Some((_, dialog)) = &self.pending {
+            match dialog.handle_key(key) {
+                Some(true) => {
+                    if let Some((action, _)) = self.pending.take() {
+                        self.run_bulk_action(action)?;
+                    }
+                }
+                Some(false) => self.pending = None,
+                None => {}
+            }
+            return Ok(Action::None);
+        }
+
+        if self.priority_input.is_some() {
+            self.handle_priority_input(key)?;
+            return Ok(Action::None);
+        }
+
+        // 对比结果浮窗打开时的键位
+        if self.overlap.is_some() {
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => {
+                    self.overlap = None;
+                    self.popup.reset_scroll();
+                }
+                KeyCode::Char('j') | KeyCode::Down => self.popup.scroll_down(),
+                KeyCode::Char('k') | KeyCode::Up => self.popup.scroll_up(),
+                _ => {}
+            }
+            return Ok(Action::None);
+        }
+
         match key.code {
+            KeyCode::Esc if self.compare_mark.is_some() => {
+                self.compare_mark = None;
+                Ok(Action::None)
+            }
             KeyCode::Esc | KeyCode::Char('q') => Ok(Action::NavigateTo(Screen::Dashboard)),
             KeyCode::Enter => self.select_wordbook(),
+            KeyCode::Char('b') => Ok(match self.wordbooks.get(self.selected_index) {
+                Some((tag, _)) => Action::StartWordbookBrowse(tag.clone(), self.order, self.due_only),
+                None => Action::None,
+            }),
             KeyCode::Char('s') => {
-                self.toggle_shuffle();
+                self.cycle_order();
+                Ok(Action::None)
+            }
+            KeyCode::Char('d') => {
+                self.due_only = !self.due_only;
+                Ok(Action::None)
+            }
+            KeyCode::Char('f') => {
+                self.toggle_focus()?;
+                Ok(Action::None)
+            }
+            KeyCode::Char('p') => {
+                self.start_priority()?;
+                Ok(Action::None)
+            }
+            KeyCode::Char('c') => {
+                self.mark_or_compare()?;
+                Ok(Action::None)
+            }
+            KeyCode::Char('x') => {
+                self.request_bulk_action(true);
+                Ok(Action::None)
+            }
+            KeyCode::Char('X') => {
+                self.request_bulk_action(false);
                 Ok(Action::None)
             }
             KeyCode::Down | KeyCode::Char('j') => {
@@ -94,24 +391,15 @@ impl Component for WordbookComponent {
         let items: Vec<ListItem> = self
             .wordbooks
             .iter()
-            .map(|(tag, count)| {
+            .enumerate()
+            .map(|(index, (tag, count))| {
                 // 解析 tag 并显示中文名称
                 let tag_display = tag.split_whitespace()
-                    .map(|t| match t {
-                        "zk" => "中考",
-                        "gk" => "高考",
-                        "cet4" => "CET-4",
-                        "cet6" => "CET-6",
-                        "ky" => "考研",
-                        "toefl" => "TOEFL",
-                        "ielts" => "IELTS",
-                        "gre" => "GRE",
-                        _ => t,
-                    })
+                    .map(tag_display_name)
                     .collect::<Vec<_>>()
                     .join(" · ");
 
-                let content = vec![
+                let mut content = vec![
                     Span::styled(
                         format!("  {}", tag_display),
                         Theme::text_title(),
@@ -122,16 +410,30 @@ impl Component for WordbookComponent {
                         Theme::text_secondary(),
                     ),
                 ];
+                if self.compare_mark == Some(index) {
+                    content.push(Span::styled("  [对比 A]", Theme::text_accent()));
+                }
+                if self.focus_tag.as_ref() == Some(tag) {
+                    content.push(Span::styled("  [重点]", Theme::text_warning()));
+                }
+                if let Some(priority) = self.priority.as_ref().filter(|priority| &priority.tag == tag) {
+                    content.push(Span::styled(
+                        format!("  [优先至 {}]", priority.until.format("%m-%d")),
+                        Theme::text_accent(),
+                    ));
+                }
 
                 ListItem::new(Line::from(content))
             })
             .collect();
 
         let list_title = format!(
-            " 选择单词本 ({}/{}) - {} ",
+            " 选择单词本 ({}/{}) - {} {} · {} ",
             self.selected_index + 1,
             self.wordbooks.len(),
-            if self.shuffle_mode { "🔀 乱序" } else { "📚 顺序" }
+            self.order.icon(),
+            self.order.label(),
+            if self.due_only { "⏰ 只复习到期" } else { "📖 整本学习" }
         );
 
         let list = List::new(items)
@@ -163,12 +465,16 @@ impl Component for WordbookComponent {
         }
 
         // Help text
-        let help_lines = vec![
+        let mut help_lines = vec![
             Line::from(vec![
                 Span::styled("Enter", Theme::text_success()),
                 Span::raw(" 开始复习  "),
                 Span::styled("s", Theme::text_warning()),
                 Span::raw(" 切换乱序/顺序  "),
+                Span::styled("d", Theme::text_warning()),
+                Span::raw(" 整本/只到期  "),
+                Span::styled("c", Theme::text_accent()),
+                Span::raw(if self.compare_mark.is_some() { " 与标记对比  " } else { " 标记对比  " }),
                 Span::styled("↑/↓ j/k", Theme::text_title()),
                 Span::raw(" 选择"),
             ]),
@@ -177,14 +483,46 @@ impl Component for WordbookComponent {
                 Span::raw(" 首/尾  "),
                 Span::styled("PageUp/Down", Theme::text_title()),
                 Span::raw(" 翻页  "),
+                Span::styled("f", Theme::text_warning()),
+                Span::raw(" 重点单词本  "),
+                Span::styled("p", Theme::text_warning()),
+                Span::raw(" 限时优先  "),
+                Span::styled("x/X", Theme::text_warning()),
+                Span::raw(" 暂停/恢复已学单词  "),
                 Span::styled("q", Theme::text_accent()),
                 Span::raw(" 返回"),
             ]),
         ];
+        if let Some(input) = &self.priority_input {
+            let hint = match self.priority_error {
+                Some(error) => format!("  {}", error),
+                None => "  +N 天 / YYYY-MM-DD - Enter 确认 | Esc 取消".to_string(),
+            };
+            help_lines.push(Line::from(vec![
+                Span::styled("优先到: ", Theme::text_normal()),
+                Span::styled(format!("{}_", input), Theme::text_warning()),
+                Span::styled(hint, Theme::text_secondary()),
+            ]));
+        } else if let Some(message) = &self.message {
+            help_lines.push(Line::from(Span::styled(message.as_str(), Theme::text_success())));
+        }
 
         let help = Paragraph::new(help_lines)
             .block(Theme::block_default().title(" 操作提示 "))
             .alignment(ratatui::layout::Alignment::Center);
         frame.render_widget(help, layout[1]);
+
+        if let Some((tag_a, tag_b, overlap)) = &self.overlap {
+            let lines = Self::build_overlap_lines(tag_a, tag_b, overlap);
+            self.popup.render(frame, area, lines);
+        }
+
+        if let Some((_, dialog)) = &self.pending {
+            dialog.render(frame, area);
+        }
+    }
+
+    fn is_typing(&self) -> bool {
+        self.priority_input.is_some()
     }
 }
@@ -1,20 +1,381 @@
-use anyhow::Result;
-use rusqlite::{params, Connection, OptionalExtension};
-use chrono::{DateTime, Utc};
-use crate::models::{Word, LearningLog, LearningStatus};
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, ErrorCode, OpenFlags, OptionalExtension};
+use chrono::{DateTime, NaiveDate, Utc};
+use crate::glossary::GlossarySource;
+use crate::i18n::Lang;
+use crate::theme::BorderDensity;
+use crate::logger;
+use crate::sm2;
+use crate::models::{parse_exchange, DeckSummary, EXAM_TAGS, Word, LearningLog, LearningStatus, Achievement, AchievementProgress, CalendarStyle, CardFrontField, DictColumn, DictVersion, DictEnterAction, DictHlKeys, DictPosition, FavoritesImport, FreqBand, GlobalSearchHits, LearningMix, MasteredReview, MasteryRule, MixOrder, MergeSummary, NewWordBudget, PriorityWordbook, ReviewDirection, SearchFocusKey, SessionComplete, StatsGranularity, UserDataBackup, UserDataImport, UserDataWord, WordbookOverlap, WordDates, WordOrder, MaturityCounts};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::path::{Path, PathBuf};
+
+/// ECDICT dictionary database path
+pub const ECDICT_DB: &str = "ecdict-sqlite-28/stardict.db";
+
+/// ECDICT release the app is written against (the 28 in `ECDICT_DB`)
+pub const ECDICT_VERSION: u32 = 28;
+
+/// Full `stardict` schema of that release, used to tell older/newer builds apart
+const ECDICT_COLUMNS: [&str; 15] = [
+    "id", "word", "sw", "phonetic", "definition", "translation", "pos", "collins",
+    "oxford", "tag", "bnc", "frq", "exchange", "detail", "audio",
+];
+
+/// Columns slimmed builds drop without being an older release (besides
+/// `OPTIONAL_DICT_COLUMNS`): the app never reads them, so they say nothing about the version
+const UNUSED_DICT_COLUMNS: [&str; 3] = ["sw", "detail", "audio"];
+
+/// 词典读不了且重连也失败（ECDICT 放在断开的移动/网络盘上）；所有 Database 共用，
+/// 界面据此显示横幅，之后任何一次词典查询成功就清除
+static DICT_UNAVAILABLE: AtomicBool = AtomicBool::new(false);
+
+pub fn dict_unavailable() -> bool {
+    DICT_UNAVAILABLE.load(Ordering::Relaxed)
+}
+
+/// ECDICT is still unreachable after `query_dict` reopened it; the main loop keeps
+/// running on this error (the glance line shows a banner) and stops on any other
+#[derive(Debug)]
+pub struct DictUnavailable(rusqlite::Error);
+
+impl std::fmt::Display for DictUnavailable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ECDICT unavailable: {}", self.0)
+    }
+}
+
+impl std::error::Error for DictUnavailable {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+/// Errors meaning the ECDICT file itself is gone or unreadable, not a bad query
+fn is_connection_error(err: &rusqlite::Error) -> bool {
+    matches!(
+        err.sqlite_error_code(),
+        Some(ErrorCode::SystemIoFailure | ErrorCode::DatabaseCorrupt | ErrorCode::NotADatabase | ErrorCode::CannotOpen)
+    )
+}
+
+/// Learning progress database path
+pub const PROGRESS_DB: &str = "lexrain_progress.db";
+
+/// Checkpoint the WAL after this many submitted reviews
+pub const CHECKPOINT_EVERY_REVIEWS: usize = 20;
+
+/// New words introduced per "learn new" session
+pub const NEW_WORDS_BATCH: i64 = 20;
+
+/// Default age of review history kept by `prune_history` (CLI and Settings)
+pub const DEFAULT_PRUNE_KEEP_DAYS: i64 = 365;
+
+/// Never prune newer history than this: covers the 8-week statistics chart and the calendar month
+pub const MIN_PRUNE_KEEP_DAYS: i64 = 90;
+
+/// Default look-back of the daily counts written by `export_stats_csv`
+pub const DEFAULT_STATS_CSV_DAYS: i64 = 365;
+
+/// Format version written by `export_user_data_json`
+pub const USER_DATA_VERSION: u32 = 1;
+
+/// Settings carried by the user data backup: preferences only. Machine-local paths
+/// (glossary_path), UI state (dict_position, stats_granularity) and one-day or
+/// time-limited switches (skip_new_words_on, priority_*) stay on this machine
+const BACKUP_SETTINGS: [&str; 36] = [
+    "advanced_actions", "animations", "auto_favorite_lapses", "auto_limit_new_words", "border_density",
+    "calendar_style", "card_front", "clean_definitions", "confirm_review_quit", "daily_goal",
+    "day_rollover_hour", "dict_columns", "dict_enter_action", "dict_hl_keys", "display_emphasis",
+    "exit_summary", "focus_tag", "freq_band", "group_by_wordbook", "hide_mastered_in_dict", "language",
+    "learning_mix", "mastered_review", "mastery_consecutive_good", "mastery_min_repetition", "mix_order",
+    "on_session_complete", "overdue_grace_hours", "remember_dict_position", "reminder_hour",
+    "review_direction", "search_focus_key", "session_cap", "skip_proper_nouns", "start_in_review",
+    "warmup_count",
+];
+
+/// Look-back window of the retention figure on the deck summary
+pub const RETENTION_DAYS: i64 = 30;
+
+/// Warm-up sizes offered in Settings (0 = off)
+pub const WARMUP_COUNTS: [i64; 4] = [0, 2, 3, 5];
+
+/// Lapse thresholds for auto-favoriting offered in Settings (0 = off)
+pub const AUTO_FAVORITE_LAPSES: [i64; 4] = [0, 3, 5, 8];
+
+/// Hours past `next_review` before a due word counts as overdue, offered in Settings
+pub const OVERDUE_GRACE_HOURS: [i64; 5] = [0, 6, 12, 24, 48];
+
+/// Hours after midnight at which a new study day starts, offered in Settings (0 = midnight)
+pub const DAY_ROLLOVER_HOURS: [u32; 5] = [0, 2, 3, 4, 5];
+
+/// Per-session caps on due reviews offered in Settings (0 = no cap)
+pub const SESSION_CAPS: [i64; 5] = [0, 20, 50, 100, 200];
+
+/// Study reminder hours offered in Settings (None = off)
+pub const REMINDER_HOURS: [Option<u32>; 7] = [None, Some(8), Some(12), Some(18), Some(20), Some(21), Some(22)];
+
+/// Auto-mastery minimums offered in Settings (0 = not required). Reaching a 21-day
+/// interval already takes about 10 successful reviews, so lower repetition minimums do nothing
+pub const MASTERY_MIN_REPETITIONS: [i32; 4] = [0, 12, 15, 20];
+pub const MASTERY_CONSECUTIVE_GOOD: [i32; 4] = [0, 2, 3, 5];
+
+/// Upper bounds of the ease-factor histogram buckets; the last bucket is open (2.5+)
+pub const EASE_BUCKET_BOUNDS: [f64; 4] = [1.5, 1.8, 2.1, 2.5];
+
+/// Days of upcoming reviews considered by the new-word budget
+const FORECAST_DAYS: i64 = 7;
+
+/// New words stop entirely once the forecast reaches this multiple of the goal
+const BACKLOG_CUTOFF_RATIO: f64 = 1.5;
+
+/// Number of shared words listed in a wordbook comparison
+const OVERLAP_SAMPLE_SIZE: usize = 30;
+
+/// Maximum number of stem-derived words shown in a word family
+const FAMILY_DERIVED_LIMIT: i64 = 8;
+
+/// Hits kept per group in the global search
+const GLOBAL_SEARCH_LIMIT: usize = 50;
+
+/// How far back the dashboard's "recently forgotten" list looks
+const RECENTLY_FORGOTTEN_DAYS: i64 = 14;
+
+/// Most words in the "recently forgotten" list and its focused review
+pub const RECENTLY_FORGOTTEN_LIMIT: usize = 50;
+
+/// 拆分复合标签 (分隔符: 空格, 逗号, 顿号, 中点)
+pub fn split_tags(tag_string: &str) -> impl Iterator<Item = &str> {
+    tag_string
+        .split([' ', ',', '、', '·'])
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+}
+
+/// 主标签：单词带的考试标签里最靠前的一个（按 EXAM_TAGS 顺序，中考在先）
+pub fn primary_tag(word: &Word) -> Option<&'static str> {
+    EXAM_TAGS.into_iter().find(|tag| word_has_tag(word, tag))
+}
+
+/// Whether one of `word`'s tags is exactly `tag` (same rule as `tag_match_sql`)
+pub fn word_has_tag(word: &Word, tag: &str) -> bool {
+    word.tag.as_deref().is_some_and(|tags| split_tags(tags).any(|t| t == tag))
+}
+
+/// 精确标签匹配：标签必须是独立的词（被分隔符包围或在开头/结尾）
+/// 相当于正则 (^|[· ,、])tag($|[· ,、])，`param` 是绑定 tag 的占位符（如 "?1"）
+fn tag_match_sql(param: &str) -> String {
+    let mut conditions = vec![format!("tag = {param}")];
+    for sep in [" ", "·", ",", "、"] {
+        conditions.push(format!("tag LIKE {param} || '{sep}%'"));
+        conditions.push(format!("tag LIKE '%{sep}' || {param}"));
+        conditions.push(format!("tag LIKE '%{sep}' || {param} || '{sep}%'"));
+    }
+    format!("({})", conditions.join(" OR "))
+}
+
+/// 自动选新词时跳过专有名词和缩写（设置“过滤专有名词和缩写”）：
+/// - 全大写的词（NASA、UK）
+/// - 带句点的缩写（e.g.、etc.、Mr.）
+/// - 首字母大写且词典里没有小写形式的词（London）
+///
+/// ECDICT 的 word 列是 COLLATE NOCASE，所以大小写比较要显式用 BINARY
+const PROPER_NOUN_FILTER: &str = "
+             AND NOT (word COLLATE BINARY = UPPER(word) AND word COLLATE BINARY <> LOWER(word))
+             AND word NOT LIKE '%.%'
+             AND NOT (SUBSTR(word, 1, 1) COLLATE BINARY <> LOWER(SUBSTR(word, 1, 1))
+                      AND NOT EXISTS (SELECT 1 FROM stardict AS variant
+                                      WHERE variant.word = LOWER(stardict.word) AND variant.id <> stardict.id))";
+
+/// 按卡计数的 review_history 行（今日完成数、每日/每周复习量、清理时的每日汇总）：
+/// 双向复习一张卡记两条、reviewed_at 相同，和英→中配对的那条中→英不再算一次
+const CARD_ROWS: &str = "NOT (direction = 'zh_to_en' AND EXISTS (
+                 SELECT 1 FROM review_history AS first_pass
+                 WHERE first_pass.word_id = review_history.word_id
+                   AND first_pass.reviewed_at = review_history.reviewed_at
+                   AND first_pass.direction = 'en_to_zh'))";
+
+/// SQL condition for a frequency band (see `FreqBand::contains`), prefixed with " AND "
+fn freq_band_sql(band: FreqBand) -> String {
+    const RANK: &str = "COALESCE(NULLIF(frq, 0), NULLIF(bnc, 0))";
+    match band {
+        FreqBand::All => String::new(),
+        FreqBand::Band(from, Some(to)) => format!(" AND {RANK} BETWEEN {from} AND {to}"),
+        FreqBand::Band(from, None) => format!(" AND ({RANK} IS NULL OR {RANK} >= {from})"),
+    }
+}
+
+/// Columns selected from `stardict` for every `Word` query, in `word_from_row` order
+const WORD_COLUMNS: &str =
+    "id, word, phonetic, definition, translation, pos, collins, oxford, tag, bnc, frq, exchange";
+
+/// `stardict` columns that slimmed or custom ECDICT builds may leave out;
+/// a missing one is selected as NULL (see `Database::dict_select`)
+const OPTIONAL_DICT_COLUMNS: [&str; 6] = ["collins", "oxford", "tag", "bnc", "frq", "exchange"];
+
+/// Map a `stardict` row selected with `WORD_COLUMNS` into a `Word`
+fn word_from_row(row: &rusqlite::Row) -> rusqlite::Result<Word> {
+    Ok(Word {
+        id: Some(row.get(0)?),
+        spelling: row.get(1)?,
+        phonetic: row.get(2)?,
+        definition: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+        translation: row.get(4)?,
+        pos: row.get(5)?,
+        collins: row.get::<_, Option<i32>>(6)?.unwrap_or(0),
+        oxford: row.get::<_, Option<i32>>(7)?.unwrap_or(0) > 0,
+        tag: row.get(8)?,
+        bnc: row.get(9)?,
+        frq: row.get(10)?,
+        exchange: row.get(11)?,
+        favorited: false, // Filled in by callers that need it
+        glossary: None,   // Filled in by `Database::apply_glossary`
+    })
+}
+
+/// Parse a stored `next_review` timestamp, logging rows that cannot be parsed
+/// instead of silently pretending they are due now.
+fn parse_next_review(word_id: i64, raw: &str) -> Option<DateTime<Utc>> {
+    match DateTime::parse_from_rfc3339(raw) {
+        Ok(dt) => Some(dt.with_timezone(&Utc)),
+        Err(e) => {
+            logger::warn(&format!(
+                "learning_log word_id={} has unparseable next_review {:?}: {}",
+                word_id, raw, e
+            ));
+            None
+        }
+    }
+}
+
+/// 学习日：本地时间往前推 `rollover_hour` 小时后的日期，凌晨 4 点换日时 1 点的复习算前一天
+///
+/// Works on the wall clock, like SQLite's `'localtime', '-N hours'` in `day_modifiers`,
+/// so a DST change during the night does not move the boundary off `rollover_hour`
+pub fn effective_date<Tz: chrono::TimeZone>(now: DateTime<Tz>, rollover_hour: u32) -> NaiveDate {
+    (now.naive_local() - chrono::Duration::hours(i64::from(rollover_hour))).date()
+}
 
 pub struct Database {
-    dict_conn: Connection,  // Read-only ECDICT database
+    dict_conn: RefCell<Connection>, // Read-only ECDICT database (replaced by reopen_dict)
     learn_conn: Connection, // Learning progress database
+    missing_dict_columns: Vec<&'static str>, // OPTIONAL_DICT_COLUMNS absent from this ECDICT build
+    word_columns: String, // WORD_COLUMNS with the missing ones selected as NULL
+    glossary: Option<GlossarySource>, // Personal glossary from Settings, merged into looked-up words
+    dict_version: DictVersion, // ECDICT build detected at startup
+}
+
+/// Release marker from a `metadata(key, value)` table, if the build ships one
+/// (the stock ECDICT release has none); the last number in the value is taken ("1.0.28" → 28)
+fn read_dict_version_marker(dict_conn: &Connection) -> Option<u32> {
+    let value: String = dict_conn
+        .query_row("SELECT value FROM metadata WHERE key = 'version'", [], |row| row.get(0))
+        .ok()?;
+    value.split(|c: char| !c.is_ascii_digit()).rfind(|s| !s.is_empty())?.parse().ok()
+}
+
+/// Tables and columns `initialize` creates or adds in lexrain_progress.db;
+/// `missing_progress_schema` reports the ones an older file still lacks
+const PROGRESS_SCHEMA: [(&str, &[&str]); 8] = [
+    (
+        "learning_log",
+        &[
+            "word_id", "repetition", "interval", "e_factor", "next_review", "status",
+            "added_at", "mastered_at", "consecutive_good", "lapses",
+        ],
+    ),
+    (
+        "review_history",
+        &[
+            "word_id", "reviewed_at", "quality", "repetition", "interval", "e_factor",
+            "response_ms", "direction", "mode", "first_try",
+        ],
+    ),
+    ("settings", &["key", "value"]),
+    ("daily_checkin", &["date", "completed_count", "goal", "achieved"]),
+    ("favorites", &["word_id", "added_at"]),
+    ("suspended", &["word_id", "suspended_at"]),
+    ("history_daily_summary", &["day", "reviews", "quality_sum"]),
+    ("achievements", &["id", "unlocked_at"]),
+];
+
+/// Column names of `table` (empty when the table does not exist)
+fn table_columns(conn: &Connection, table: &str) -> Result<HashSet<String>> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let names = stmt.query_map([], |row| row.get::<_, String>(1))?;
+    Ok(names.collect::<rusqlite::Result<HashSet<_>>>()?)
+}
+
+/// Optional columns missing from `stardict` and the detected ECDICT version;
+/// shared by `initialize` and the read-only `open_read_only`
+fn inspect_dict(dict_conn: &Connection) -> Result<(Vec<&'static str>, DictVersion)> {
+    // Slimmed ECDICT builds may lack optional columns: detect them once and select NULL instead
+    let present = {
+        let mut stmt = dict_conn.prepare("PRAGMA table_info(stardict)")?;
+        let names = stmt.query_map([], |row| row.get::<_, String>(1))?;
+        names.collect::<rusqlite::Result<HashSet<_>>>()?
+    };
+    let missing_dict_columns: Vec<&'static str> =
+        OPTIONAL_DICT_COLUMNS.into_iter().filter(|column| !present.contains(*column)).collect();
+    // An empty table_info means no stardict table at all (ecdict_word_count reports that).
+    // Warn once per run: a Database is opened on every screen change
+    static WARNED_MISSING_COLUMNS: std::sync::Once = std::sync::Once::new();
+    if !present.is_empty() && !missing_dict_columns.is_empty() {
+        WARNED_MISSING_COLUMNS.call_once(|| {
+            logger::warn(&format!(
+                "ECDICT has no {} column(s); treating them as empty",
+                missing_dict_columns.join(", ")
+            ))
+        });
+    }
+
+    // 版本不同的 ECDICT 列的含义（如 tag 代码）可能不同：有版本标记用标记，没有就按列推断
+    let dict_version = if present.is_empty() {
+        DictVersion::default()
+    } else {
+        DictVersion {
+            marker: read_dict_version_marker(dict_conn),
+            missing_columns: ECDICT_COLUMNS
+                .into_iter()
+                .filter(|column| !OPTIONAL_DICT_COLUMNS.contains(column) && !UNUSED_DICT_COLUMNS.contains(column))
+                .filter(|column| !present.contains(*column))
+                .map(String::from)
+                .collect(),
+            extra_columns: {
+                let mut extra: Vec<String> =
+                    present.iter().filter(|column| !ECDICT_COLUMNS.contains(&column.as_str())).cloned().collect();
+                extra.sort();
+                extra
+            },
+        }
+    };
+    static WARNED_DICT_VERSION: std::sync::Once = std::sync::Once::new();
+    if dict_version.age(ECDICT_VERSION).is_ne() {
+        WARNED_DICT_VERSION.call_once(|| {
+            logger::warn(&format!("ECDICT {}", dict_version.describe(ECDICT_VERSION)))
+        });
+    }
+
+    Ok((missing_dict_columns, dict_version))
 }
 
 impl Database {
     pub fn initialize() -> Result<Self> {
         // Open ECDICT dictionary database (read-only)
-        let dict_conn = Connection::open("ecdict-sqlite-28/stardict.db")?;
+        let dict_conn = Connection::open(ECDICT_DB)?;
         
         // Open learning progress database
-        let learn_conn = Connection::open("lexrain_progress.db")?;
+        let learn_conn = Connection::open(PROGRESS_DB)?;
+
+        Self::setup(dict_conn, learn_conn)
+    }
+
+    /// Create or migrate the progress schema and inspect ECDICT on open connections
+    fn setup(dict_conn: Connection, learn_conn: Connection) -> Result<Self> {
+        // WAL lets several connections (one per screen) read while a review writes
+        learn_conn.query_row("PRAGMA journal_mode=WAL", [], |row| row.get::<_, String>(0))?;
 
         // Create learning log table (word_id references ECDICT stardict.id)
         learn_conn.execute(
@@ -24,7 +385,11 @@ impl Database {
                 interval INTEGER NOT NULL,
                 e_factor REAL NOT NULL,
                 next_review TEXT NOT NULL,
-                status INTEGER NOT NULL
+                status INTEGER NOT NULL,
+                added_at TEXT,
+                mastered_at TEXT,
+                consecutive_good INTEGER NOT NULL DEFAULT 0,
+                lapses INTEGER NOT NULL DEFAULT 0
             )",
             [],
         )?;
@@ -37,11 +402,66 @@ impl Database {
                 quality INTEGER NOT NULL,
                 repetition INTEGER NOT NULL,
                 interval INTEGER NOT NULL,
-                e_factor REAL NOT NULL
+                e_factor REAL NOT NULL,
+                response_ms INTEGER,
+                direction TEXT DEFAULT 'en_to_zh',
+                mode TEXT,
+                first_try INTEGER NOT NULL DEFAULT 1
             )",
             [],
         )?;
 
+        // 旧数据库的 review_history 没有 response_ms 列（NULL = 未计时）
+        if learn_conn.prepare("SELECT response_ms FROM review_history LIMIT 0").is_err() {
+            learn_conn.execute("ALTER TABLE review_history ADD COLUMN response_ms INTEGER", [])?;
+        }
+
+        // 旧数据库没有复习方向和方式：以前只有英→中，旧记录按英→中算；方式不知道，留 NULL
+        if learn_conn.prepare("SELECT direction, mode FROM review_history LIMIT 0").is_err() {
+            learn_conn.execute("ALTER TABLE review_history ADD COLUMN direction TEXT DEFAULT 'en_to_zh'", [])?;
+            learn_conn.execute("ALTER TABLE review_history ADD COLUMN mode TEXT", [])?;
+        }
+
+        // 旧数据库没有 first_try 列：以前同一轮里不会再考同一个词，旧记录都是第一次作答
+        if learn_conn.prepare("SELECT first_try FROM review_history LIMIT 0").is_err() {
+            learn_conn.execute("ALTER TABLE review_history ADD COLUMN first_try INTEGER NOT NULL DEFAULT 1", [])?;
+        }
+
+        // 旧数据库的 learning_log 没有 added_at / mastered_at 列：
+        // 用复习历史补近似值（首次复习、间隔首次超过 21 天），已清理的历史补不回来，留 NULL
+        if learn_conn.prepare("SELECT added_at, mastered_at FROM learning_log LIMIT 0").is_err() {
+            learn_conn.execute("ALTER TABLE learning_log ADD COLUMN added_at TEXT", [])?;
+            learn_conn.execute("ALTER TABLE learning_log ADD COLUMN mastered_at TEXT", [])?;
+            learn_conn.execute(
+                "UPDATE learning_log SET
+                     added_at = (SELECT MIN(reviewed_at) FROM review_history h WHERE h.word_id = learning_log.word_id),
+                     mastered_at = CASE WHEN status = 2 THEN
+                         (SELECT MIN(reviewed_at) FROM review_history h
+                          WHERE h.word_id = learning_log.word_id AND h.interval > 21)
+                     END",
+                [],
+            )?;
+        }
+
+        // 旧数据库没有 consecutive_good 列：没有取消掌握过的单词两者一致，用 repetition 补
+        if learn_conn.prepare("SELECT consecutive_good FROM learning_log LIMIT 0").is_err() {
+            learn_conn.execute(
+                "ALTER TABLE learning_log ADD COLUMN consecutive_good INTEGER NOT NULL DEFAULT 0",
+                [],
+            )?;
+            learn_conn.execute("UPDATE learning_log SET consecutive_good = repetition", [])?;
+        }
+
+        // 旧数据库没有 lapses 列：按复习历史里评“忘记”的次数补（已清理的历史补不回来）
+        if learn_conn.prepare("SELECT lapses FROM learning_log LIMIT 0").is_err() {
+            learn_conn.execute("ALTER TABLE learning_log ADD COLUMN lapses INTEGER NOT NULL DEFAULT 0", [])?;
+            learn_conn.execute(
+                "UPDATE learning_log SET lapses =
+                     (SELECT COUNT(*) FROM review_history h WHERE h.word_id = learning_log.word_id AND h.quality = 1)",
+                [],
+            )?;
+        }
+
         // Create settings table
         learn_conn.execute(
             "CREATE TABLE IF NOT EXISTS settings (
@@ -71,6 +491,40 @@ impl Database {
             [],
         )?;
 
+        // Create suspended table (words kept out of every review queue)
+        learn_conn.execute(
+            "CREATE TABLE IF NOT EXISTS suspended (
+                word_id INTEGER PRIMARY KEY,
+                suspended_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // Pairs the two passes of a two-direction card (see CARD_ROWS)
+        learn_conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_history_word ON review_history(word_id, reviewed_at)",
+            [],
+        )?;
+
+        // Daily totals of pruned review_history rows (keeps long-range charts after a prune)
+        learn_conn.execute(
+            "CREATE TABLE IF NOT EXISTS history_daily_summary (
+                day TEXT PRIMARY KEY,
+                reviews INTEGER NOT NULL,
+                quality_sum INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        // Unlocked milestone badges (id = Achievement::id)
+        learn_conn.execute(
+            "CREATE TABLE IF NOT EXISTS achievements (
+                id TEXT PRIMARY KEY,
+                unlocked_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
         // Initialize default settings if not exists
         learn_conn.execute(
             "INSERT OR IGNORE INTO settings (key, value) VALUES ('daily_goal', '20')",
@@ -93,52 +547,168 @@ impl Database {
         // Note: Can't create index on translation (TEXT with Chinese) as it's too large
         // But word-based search will be much faster now
 
-        Ok(Self { dict_conn, learn_conn })
+        let (missing_dict_columns, dict_version) = inspect_dict(&dict_conn)?;
+
+        let mut db = Self { dict_conn: RefCell::new(dict_conn), learn_conn, missing_dict_columns, word_columns: String::new(), glossary: None, dict_version };
+        db.word_columns = db.dict_select(WORD_COLUMNS);
+        db.glossary = db.load_glossary();
+        Ok(db)
+    }
+
+    /// Read the glossary file named in Settings. A broken file must not block the app:
+    /// it is logged (once per run) and the plain ECDICT entries are used
+    fn load_glossary(&self) -> Option<GlossarySource> {
+        let path = self.get_glossary_path().ok()??;
+        match GlossarySource::load(Path::new(&path)) {
+            Ok(glossary) => Some(glossary),
+            Err(e) => {
+                static WARNED_GLOSSARY: std::sync::Once = std::sync::Once::new();
+                WARNED_GLOSSARY.call_once(|| logger::warn(&format!("glossary not loaded: {:#}", e)));
+                None
+            }
+        }
+    }
+
+    /// Merge the personal glossary entry for this spelling, if one is configured
+    fn apply_glossary(&self, word: &mut Word) {
+        if let Some(glossary) = &self.glossary {
+            glossary.apply(word);
+        }
+    }
+
+    /// Comma-separated `stardict` columns with any missing optional column written as
+    /// `NULL AS name`; SQLite resolves WHERE / ORDER BY references to that alias
+    fn dict_select(&self, columns: &str) -> String {
+        columns
+            .split(',')
+            .map(str::trim)
+            .map(|column| {
+                if self.missing_dict_columns.contains(&column) {
+                    format!("NULL AS {}", column)
+                } else {
+                    column.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Open both databases read-only without touching the schema (`lexrain doctor`):
+    /// no WAL switch, migrations or CREATE statements. Missing tables or columns
+    /// make the queries that need them fail; `missing_progress_schema` lists them
+    pub fn open_read_only() -> Result<Self> {
+        let flags = OpenFlags::SQLITE_OPEN_READ_ONLY;
+        let dict_conn = Connection::open_with_flags(ECDICT_DB, flags)?;
+        let learn_conn = Connection::open_with_flags(PROGRESS_DB, flags)?;
+        let (missing_dict_columns, dict_version) = inspect_dict(&dict_conn)?;
+        let mut db = Self { dict_conn: RefCell::new(dict_conn), learn_conn, missing_dict_columns, word_columns: String::new(), glossary: None, dict_version };
+        db.word_columns = db.dict_select(WORD_COLUMNS);
+        Ok(db)
+    }
+
+    /// Tables (as `table`) and columns (as `table.column`) of the progress database
+    /// that this version expects but the file does not have yet
+    pub fn missing_progress_schema(&self) -> Result<Vec<String>> {
+        let mut missing = Vec::new();
+        for (table, columns) in PROGRESS_SCHEMA {
+            let present = table_columns(&self.learn_conn, table)?;
+            if present.is_empty() {
+                missing.push(table.to_string());
+                continue;
+            }
+            missing.extend(
+                columns.iter().filter(|column| !present.contains(**column)).map(|column| format!("{}.{}", table, column)),
+            );
+        }
+        Ok(missing)
+    }
+
+    /// A second handle on the same files opened read-only, for background threads
+    /// (`--serve`); skips the schema setup `initialize` already did
+    pub fn read_only_clone(&self) -> Result<Self> {
+        let flags = OpenFlags::SQLITE_OPEN_READ_ONLY;
+        Ok(Self {
+            dict_conn: RefCell::new(Connection::open_with_flags(ECDICT_DB, flags)?),
+            learn_conn: Connection::open_with_flags(PROGRESS_DB, flags)?,
+            missing_dict_columns: self.missing_dict_columns.clone(),
+            word_columns: self.word_columns.clone(),
+            glossary: None,
+            dict_version: self.dict_version.clone(),
+        })
+    }
+
+    /// 将 WAL 文件合并回主数据库并截断，避免长时间使用后 -wal 文件过大
+    pub fn checkpoint(&self) -> Result<()> {
+        self.learn_conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+        Ok(())
+    }
+
+    /// 查词典；连接断了（文件所在的盘暂时断开）就只读重开一次 ECDICT 再试，
+    /// 还不行才报错并标记词典不可用
+    fn query_dict<T>(&self, query: impl Fn(&Connection) -> rusqlite::Result<T>) -> Result<T> {
+        let err = match query(&self.dict_conn.borrow()) {
+            Err(err) if is_connection_error(&err) => err,
+            result => {
+                if result.is_ok() && dict_unavailable() {
+                    DICT_UNAVAILABLE.store(false, Ordering::Relaxed);
+                }
+                return Ok(result?);
+            }
+        };
+        logger::warn(&format!("ECDICT query failed ({}); reopening {}", err, ECDICT_DB));
+        let result = Connection::open_with_flags(ECDICT_DB, OpenFlags::SQLITE_OPEN_READ_ONLY).and_then(|conn| {
+            let result = query(&conn);
+            *self.dict_conn.borrow_mut() = conn;
+            result
+        });
+        let unavailable = match &result {
+            Ok(_) | Err(rusqlite::Error::QueryReturnedNoRows) => false,
+            Err(err) => {
+                logger::warn(&format!("ECDICT still unavailable after reopening: {}", err));
+                true
+            }
+        };
+        DICT_UNAVAILABLE.store(unavailable, Ordering::Relaxed);
+        match result {
+            Err(err) if unavailable => Err(DictUnavailable(err).into()),
+            result => Ok(result?),
+        }
     }
 
     // Get word by ID from ECDICT
     fn get_word_by_id(&self, id: i64) -> Result<Word> {
         let is_favorited = self.is_favorited(id).unwrap_or(false);
-        
-        Ok(self.dict_conn.query_row(
-            "SELECT id, word, phonetic, definition, translation, pos, collins, oxford, tag, bnc, frq, exchange
-             FROM stardict WHERE id = ?1",
-            params![id],
-            |row| {
-                Ok(Word {
-                    id: Some(row.get(0)?),
-                    spelling: row.get(1)?,
-                    phonetic: row.get(2)?,
-                    definition: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
-                    translation: row.get(4)?,
-                    pos: row.get(5)?,
-                    collins: row.get::<_, Option<i32>>(6)?.unwrap_or(0),
-                    oxford: row.get::<_, Option<i32>>(7)?.unwrap_or(0) > 0,
-                    tag: row.get(8)?,
-                    bnc: row.get(9)?,
-                    frq: row.get(10)?,
-                    exchange: row.get(11)?,
-                    favorited: is_favorited,
-                })
-            },
-        )?)
+
+        let mut word = self.query_dict(|dict| {
+            dict.query_row(
+                &format!("SELECT {} FROM stardict WHERE id = ?1", self.word_columns),
+                params![id],
+                word_from_row,
+            )
+        })?;
+        word.favorited = is_favorited;
+        self.apply_glossary(&mut word);
+        Ok(word)
     }
 
     pub fn get_due_reviews(&self) -> Result<Vec<(Word, LearningLog)>> {
         let now = Utc::now();
-        let mut stmt = self.learn_conn.prepare(
-            "SELECT word_id, repetition, interval, e_factor, next_review, status
+        let mut stmt = self.learn_conn.prepare(&format!(
+            "SELECT word_id, repetition, interval, e_factor, next_review, status, consecutive_good
              FROM learning_log
-             WHERE next_review <= ?1
-             ORDER BY next_review ASC"
-        )?;
+             WHERE next_review <= ?1{}
+             ORDER BY next_review ASC",
+            self.review_filter()?
+        ))?;
 
         let rows = stmt.query_map(params![now.to_rfc3339()], |row| {
             let word_id: i64 = row.get(0)?;
             let next_review_str: String = row.get(4)?;
-            let next_review = DateTime::parse_from_rfc3339(&next_review_str)
-                .map(|dt| dt.with_timezone(&Utc))
-                .unwrap_or(Utc::now());
+            // Corrupt timestamps are not "due now": skip them and let
+            // find_corrupt_logs() surface them instead
+            let Some(next_review) = parse_next_review(word_id, &next_review_str) else {
+                return Ok(None);
+            };
 
             let log = LearningLog {
                 word_id,
@@ -147,20 +717,273 @@ impl Database {
                 e_factor: row.get(3)?,
                 next_review,
                 status: LearningStatus::from(row.get::<_, i32>(5)?),
+                consecutive_good: row.get(6)?,
             };
-            Ok((word_id, log))
+            Ok(Some((word_id, log)))
         })?;
 
         let mut results = Vec::new();
         for row in rows {
-            let (word_id, log) = row?;
-            if let Ok(word) = self.get_word_by_id(word_id) {
-                results.push((word, log));
+            if let Some((word_id, log)) = row? {
+                if let Ok(word) = self.get_word_by_id(word_id) {
+                    results.push((word, log));
+                }
             }
         }
         Ok(results)
     }
 
+    /// 手动覆盖下次复习时间（不运行 SM2）
+    pub fn set_next_review(&self, word_id: i64, next_review: DateTime<Utc>) -> Result<()> {
+        self.learn_conn.execute(
+            "UPDATE learning_log SET next_review = ?1 WHERE word_id = ?2",
+            params![next_review.to_rfc3339(), word_id],
+        )?;
+        Ok(())
+    }
+
+    /// Extra WHERE clause hiding mastered words when they are set to never come back
+    /// Extra WHERE conditions for due counts/queues: suspended words never come
+    /// up, mastered ones only when the mastered-review setting allows it
+    fn review_filter(&self) -> Result<&'static str> {
+        Ok(match self.get_mastered_review()? {
+            MasteredReview::Never => " AND status != 2 AND word_id NOT IN (SELECT word_id FROM suspended)",
+            _ => " AND word_id NOT IN (SELECT word_id FROM suspended)",
+        })
+    }
+
+    // Bulk management
+
+    fn suspended_ids(&self) -> Result<HashSet<i64>> {
+        let mut stmt = self.learn_conn.prepare("SELECT word_id FROM suspended")?;
+        let ids = stmt.query_map([], |row| row.get(0))?.collect::<rusqlite::Result<_>>()?;
+        Ok(ids)
+    }
+
+    /// 单词本中已进入 learning_log 的单词 id
+    fn learned_ids_with_tag(&self, tag: &str) -> Result<Vec<i64>> {
+        let dict = self.dict_conn.borrow();
+        let mut stmt = dict.prepare(&format!(
+            "SELECT {} FROM stardict WHERE tag IS NOT NULL AND tag != ''",
+            self.dict_select("id, tag")
+        ))?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?;
+        let mut tagged = HashSet::new();
+        for row in rows {
+            let (id, tag_string) = row?;
+            if split_tags(&tag_string).any(|t| t == tag) {
+                tagged.insert(id);
+            }
+        }
+
+        let mut stmt = self.learn_conn.prepare("SELECT word_id FROM learning_log")?;
+        let learned = stmt.query_map([], |row| row.get::<_, i64>(0))?;
+        let mut ids = Vec::new();
+        for word_id in learned {
+            let word_id = word_id?;
+            if tagged.contains(&word_id) {
+                ids.push(word_id);
+            }
+        }
+        Ok(ids)
+    }
+
+    /// Suspend every learned word of a wordbook in one transaction.
+    /// Returns the number of words newly suspended.
+    pub fn suspend_by_tag(&self, tag: &str) -> Result<usize> {
+        let ids = self.learned_ids_with_tag(tag)?;
+        let tx = self.learn_conn.unchecked_transaction()?;
+        let now = Utc::now().to_rfc3339();
+        let mut affected = 0;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT OR IGNORE INTO suspended (word_id, suspended_at) VALUES (?1, ?2)"
+            )?;
+            for word_id in ids {
+                affected += stmt.execute(params![word_id, now])?;
+            }
+        }
+        tx.commit()?;
+        Ok(affected)
+    }
+
+    /// Undo `suspend_by_tag`. Returns the number of words resumed.
+    pub fn unsuspend_by_tag(&self, tag: &str) -> Result<usize> {
+        let ids = self.learned_ids_with_tag(tag)?;
+        let tx = self.learn_conn.unchecked_transaction()?;
+        let mut affected = 0;
+        {
+            let mut stmt = tx.prepare("DELETE FROM suspended WHERE word_id = ?1")?;
+            for word_id in ids {
+                affected += stmt.execute(params![word_id])?;
+            }
+        }
+        tx.commit()?;
+        Ok(affected)
+    }
+
+    /// Reset every word with `status` back to a brand-new card (review history is kept).
+    /// Returns the number of words reset.
+    pub fn reset_by_status(&self, status: LearningStatus) -> Result<usize> {
+        let tx = self.learn_conn.unchecked_transaction()?;
+        let affected = tx.execute(
+            "UPDATE learning_log
+             SET repetition = 0, interval = 0, e_factor = 2.5, next_review = ?1, status = 0,
+                 mastered_at = NULL, consecutive_good = 0
+             WHERE status = ?2",
+            params![Utc::now().to_rfc3339(), i32::from(status)],
+        )?;
+        tx.commit()?;
+        Ok(affected)
+    }
+
+    /// 把当前所有到期单词按同一个评分复习一遍（离线学过或数据出问题后对齐进度）。
+    /// 每个词照常走 SM2 并记入复习历史，全部在一个事务里；返回更新的单词数
+    pub fn bulk_review(&self, quality: u8) -> Result<usize> {
+        let due = self.get_due_reviews()?;
+        let mastered_review = self.get_mastered_review()?;
+        let mastery = self.get_mastery_rule()?;
+        let count = due.len();
+        let tx = self.learn_conn.unchecked_transaction()?;
+        for (_, mut log) in due {
+            sm2::process_review(&mut log, quality, mastered_review, mastery);
+            self.update_log(&log)?;
+            self.add_review_history(log.word_id, quality, &log, None, None, "bulk")?;
+        }
+        tx.commit()?;
+        let _ = self.record_checkin_if_goal_met();
+        Ok(count)
+    }
+
+    /// Number of review_history rows `prune_history(keep_days)` would delete
+    pub fn count_history_older_than(&self, keep_days: i64) -> Result<i64> {
+        let study_day = self.day_modifiers()?;
+        Ok(self.learn_conn.query_row(
+            &format!("SELECT COUNT(*) FROM review_history
+             WHERE DATE(reviewed_at, {study_day}) < DATE('now', {study_day}, '-' || ?1 || ' days')"),
+            params![keep_days],
+            |r| r.get(0),
+        )?)
+    }
+
+    /// Delete review history older than `keep_days` (at least MIN_PRUNE_KEEP_DAYS).
+    /// Each pruned day is first rolled into history_daily_summary. Returns the rows removed.
+    pub fn prune_history(&self, keep_days: i64) -> Result<usize> {
+        if keep_days < MIN_PRUNE_KEEP_DAYS {
+            anyhow::bail!("must keep at least {} days of review history", MIN_PRUNE_KEEP_DAYS);
+        }
+        let study_day = self.day_modifiers()?;
+        let tx = self.learn_conn.unchecked_transaction()?;
+        tx.execute(
+            &format!("INSERT INTO history_daily_summary (day, reviews, quality_sum)
+             SELECT DATE(reviewed_at, {study_day}) AS day, COUNT(*), SUM(quality)
+             FROM review_history
+             WHERE day < DATE('now', {study_day}, '-' || ?1 || ' days') AND {CARD_ROWS}
+             GROUP BY day
+             ON CONFLICT(day) DO UPDATE SET
+                reviews = reviews + excluded.reviews,
+                quality_sum = quality_sum + excluded.quality_sum"),
+            params![keep_days],
+        )?;
+        let removed = tx.execute(
+            &format!("DELETE FROM review_history
+             WHERE DATE(reviewed_at, {study_day}) < DATE('now', {study_day}, '-' || ?1 || ' days')"),
+            params![keep_days],
+        )?;
+        tx.commit()?;
+        Ok(removed)
+    }
+
+    // Diagnostics (lexrain doctor)
+
+    /// ECDICT build detected when this Database was opened
+    pub fn dict_version(&self) -> &DictVersion {
+        &self.dict_version
+    }
+
+    /// ECDICT 词条数（stardict 表不存在时返回错误）
+    pub fn ecdict_word_count(&self) -> Result<i64> {
+        Ok(self.dict_conn.borrow().query_row("SELECT COUNT(*) FROM stardict", [], |r| r.get(0))?)
+    }
+
+    /// learning_log 按状态计数 (new, learning, mastered)
+    pub fn learning_status_counts(&self) -> Result<(i64, i64, i64)> {
+        Ok(self.learn_conn.query_row(
+            "SELECT COALESCE(SUM(status = 0), 0), COALESCE(SUM(status = 1), 0), COALESCE(SUM(status = 2), 0)
+             FROM learning_log",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+        )?)
+    }
+
+    pub fn review_history_count(&self) -> Result<i64> {
+        Ok(self.learn_conn.query_row("SELECT COUNT(*) FROM review_history", [], |r| r.get(0))?)
+    }
+
+    /// word_id 在 ECDICT 中不存在的学习记录
+    pub fn find_orphaned_logs(&self) -> Result<Vec<i64>> {
+        let mut stmt = self.learn_conn.prepare("SELECT word_id FROM learning_log ORDER BY word_id ASC")?;
+        let word_ids = stmt
+            .query_map([], |row| row.get::<_, i64>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let dict = self.dict_conn.borrow();
+        let mut lookup = dict.prepare("SELECT 1 FROM stardict WHERE id = ?1")?;
+        let mut orphaned = Vec::new();
+        for word_id in word_ids {
+            if !lookup.exists(params![word_id])? {
+                orphaned.push(word_id);
+            }
+        }
+        Ok(orphaned)
+    }
+
+    /// 进度数据库 -wal 文件大小（字节，不存在时为 0）
+    pub fn wal_size(&self) -> u64 {
+        std::fs::metadata(format!("{}-wal", PROGRESS_DB))
+            .map(|m| m.len())
+            .unwrap_or(0)
+    }
+
+    /// 只把记忆因子恢复为初始值 2.5，复习次数、间隔和下次复习时间不变
+    pub fn reset_ease(&self, word_id: i64) -> Result<()> {
+        self.learn_conn.execute(
+            "UPDATE learning_log SET e_factor = 2.5 WHERE word_id = ?1",
+            params![word_id],
+        )?;
+        Ok(())
+    }
+
+    /// 取消掌握：重新进入学习状态并立即到期；连续答对清零，要重新攒够才会再次掌握
+    pub fn unmaster_word(&self, word_id: i64) -> Result<()> {
+        self.learn_conn.execute(
+            "UPDATE learning_log SET status = 1, next_review = ?1, mastered_at = NULL, consecutive_good = 0
+             WHERE word_id = ?2 AND status = 2",
+            params![Utc::now().to_rfc3339(), word_id],
+        )?;
+        Ok(())
+    }
+
+    /// 列出 next_review 无法解析的学习记录（word_id, 原始值）
+    pub fn find_corrupt_logs(&self) -> Result<Vec<(i64, String)>> {
+        let mut stmt = self.learn_conn.prepare(
+            "SELECT word_id, next_review FROM learning_log ORDER BY word_id ASC"
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })?;
+
+        let mut corrupt = Vec::new();
+        for row in rows {
+            let (word_id, raw) = row?;
+            if DateTime::parse_from_rfc3339(&raw).is_err() {
+                corrupt.push((word_id, raw));
+            }
+        }
+        Ok(corrupt)
+    }
+
     // Initialize a learning log for a new word if it doesn't exist
     pub fn init_learning_log(&self, word_id: i64) -> Result<()> {
         let exists: Option<i64> = self.learn_conn.query_row(
@@ -171,18 +994,22 @@ impl Database {
 
         if exists.is_none() {
             self.learn_conn.execute(
-                "INSERT INTO learning_log (word_id, repetition, interval, e_factor, next_review, status)
-                 VALUES (?1, 0, 0, 2.5, ?2, 0)",
+                "INSERT INTO learning_log (word_id, repetition, interval, e_factor, next_review, status, added_at)
+                 VALUES (?1, 0, 0, 2.5, ?2, 0, ?2)",
                 params![word_id, Utc::now().to_rfc3339()],
             )?;
         }
         Ok(())
     }
 
+    /// Save the state `sm2::process_review` computed; entering Mastered stamps
+    /// mastered_at, leaving it clears the stamp
     pub fn update_log(&self, log: &LearningLog) -> Result<()> {
         self.learn_conn.execute(
             "UPDATE learning_log 
-             SET repetition = ?1, interval = ?2, e_factor = ?3, next_review = ?4, status = ?5
+             SET repetition = ?1, interval = ?2, e_factor = ?3, next_review = ?4, status = ?5,
+                 mastered_at = CASE WHEN ?5 != 2 THEN NULL WHEN status = 2 THEN mastered_at ELSE ?7 END,
+                 consecutive_good = ?8
              WHERE word_id = ?6",
             params![
                 log.repetition,
@@ -190,7 +1017,9 @@ impl Database {
                 log.e_factor,
                 log.next_review.to_rfc3339(),
                 i32::from(log.status),
-                log.word_id
+                log.word_id,
+                Utc::now().to_rfc3339(),
+                log.consecutive_good
             ],
         )?;
         Ok(())
@@ -203,25 +1032,67 @@ impl Database {
         let mastered: i64 = self.learn_conn.query_row("SELECT COUNT(*) FROM learning_log WHERE status = 2", [], |r| r.get(0))?;
         // Due today
         let now = Utc::now().to_rfc3339();
-        let due: i64 = self.learn_conn.query_row("SELECT COUNT(*) FROM learning_log WHERE next_review <= ?1", params![now], |r| r.get(0))?;
+        let due: i64 = self.learn_conn.query_row(
+            &format!("SELECT COUNT(*) FROM learning_log WHERE next_review <= ?1{}", self.review_filter()?),
+            params![now],
+            |r| r.get(0),
+        )?;
 
         Ok((total, mastered, due))
     }
 
-    // Get all words with their learning status (limit to words we're learning)
-    pub fn get_all_words(&self) -> Result<Vec<(Word, Option<LearningLog>)>> {
+    /// 学过的单词按成熟度分三档：新词（repetition 为 0）、短期、成熟（间隔超过掌握线或已掌握）
+    pub fn get_maturity_counts(&self) -> Result<MaturityCounts> {
         let mut stmt = self.learn_conn.prepare(
-            "SELECT word_id, repetition, interval, e_factor, next_review, status
+            "SELECT CASE WHEN status = 2 OR interval > ?1 THEN 2 WHEN repetition = 0 THEN 0 ELSE 1 END AS maturity,
+                    COUNT(*)
              FROM learning_log
-             ORDER BY word_id ASC"
+             GROUP BY maturity",
         )?;
+        let rows = stmt.query_map(params![sm2::MASTERY_INTERVAL], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)))?;
+        let mut counts = MaturityCounts::default();
+        for row in rows {
+            match row? {
+                (0, n) => counts.new = n,
+                (1, n) => counts.young = n,
+                (_, n) => counts.mature = n,
+            }
+        }
+        Ok(counts)
+    }
 
-        let rows = stmt.query_map([], |row| {
-            let word_id: i64 = row.get(0)?;
-            let next_review_str: String = row.get(4)?;
-            let next_review = DateTime::parse_from_rfc3339(&next_review_str)
-                .map(|dt| dt.with_timezone(&Utc))
-                .unwrap_or(Utc::now());
+    /// 最近的一次未来复习时间（没有待安排的单词时为 None）
+    pub fn get_next_review_time(&self) -> Result<Option<DateTime<Utc>>> {
+        let next = self.learn_conn.query_row(
+            &format!(
+                "SELECT word_id, next_review FROM learning_log WHERE next_review > ?1{} ORDER BY next_review LIMIT 1",
+                self.review_filter()?
+            ),
+            params![Utc::now().to_rfc3339()],
+            |r| Ok((r.get::<_, i64>(0)?, r.get::<_, String>(1)?)),
+        ).optional()?;
+        Ok(next.and_then(|(word_id, raw)| parse_next_review(word_id, &raw)))
+    }
+
+    // Get all words with their learning status (limit to words we're learning)
+    pub fn get_all_words(&self) -> Result<Vec<(Word, Option<LearningLog>)>> {
+        self.get_learned_words(false)
+    }
+
+    /// 词典默认列表；`hide_mastered` 时在查询里就排除已掌握的单词
+    pub fn get_learned_words(&self, hide_mastered: bool) -> Result<Vec<(Word, Option<LearningLog>)>> {
+        let mut stmt = self.learn_conn.prepare(&format!(
+            "SELECT word_id, repetition, interval, e_factor, next_review, status, consecutive_good
+             FROM learning_log{}
+             ORDER BY word_id ASC",
+            if hide_mastered { " WHERE status != 2" } else { "" }
+        ))?;
+
+        let rows = stmt.query_map([], |row| {
+            let word_id: i64 = row.get(0)?;
+            let next_review_str: String = row.get(4)?;
+            let next_review = parse_next_review(word_id, &next_review_str)
+                .unwrap_or_else(Utc::now);
 
             let log = LearningLog {
                 word_id,
@@ -230,6 +1101,7 @@ impl Database {
                 e_factor: row.get(3)?,
                 next_review,
                 status: LearningStatus::from(row.get::<_, i32>(5)?),
+                consecutive_good: row.get(6)?,
             };
             Ok((word_id, log))
         })?;
@@ -246,42 +1118,48 @@ impl Database {
 
     // Search words in ECDICT dictionary
     pub fn search_words(&self, query: &str) -> Result<Vec<(Word, Option<LearningLog>)>> {
+        self.search_stardict("word LIKE ?1 OR translation LIKE ?1", query)
+    }
+
+    /// `def:` search: English definition contains the query
+    pub fn search_by_definition(&self, query: &str) -> Result<Vec<(Word, Option<LearningLog>)>> {
+        self.search_stardict("definition LIKE ?1", query)
+    }
+
+    /// `zh:` search: Chinese translation contains the query
+    pub fn search_by_translation(&self, query: &str) -> Result<Vec<(Word, Option<LearningLog>)>> {
+        self.search_stardict("translation LIKE ?1", query)
+    }
+
+    /// `tag:` search: words carrying the exact tag (e.g. cet6)
+    pub fn search_by_tag(&self, tag: &str) -> Result<Vec<(Word, Option<LearningLog>)>> {
+        self.search_stardict(&tag_match_sql("?2"), tag)
+    }
+
+    /// Shared dictionary search: `condition` may use ?1 (`%query%`) and ?2 (the raw query)
+    fn search_stardict(&self, condition: &str, query: &str) -> Result<Vec<(Word, Option<LearningLog>)>> {
         let search_pattern = format!("%{}%", query);
-        let mut stmt = self.dict_conn.prepare(
-            "SELECT id, word, phonetic, definition, translation, pos, collins, oxford, tag, bnc, frq, exchange
-             FROM stardict
-             WHERE word LIKE ?1 OR translation LIKE ?1
-             ORDER BY 
-                CASE 
-                    WHEN word = ?2 THEN 1
-                    WHEN word LIKE ?2 || '%' THEN 2
-                    ELSE 3
-                END,
-                collins DESC, oxford DESC, bnc ASC
-             LIMIT 100"
-        )?;
-
-        let rows = stmt.query_map(params![search_pattern, query], |row| {
-            Ok(Word {
-                id: Some(row.get(0)?),
-                spelling: row.get(1)?,
-                phonetic: row.get(2)?,
-                definition: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
-                translation: row.get(4)?,
-                pos: row.get(5)?,
-                collins: row.get::<_, Option<i32>>(6)?.unwrap_or(0),
-                oxford: row.get::<_, Option<i32>>(7)?.unwrap_or(0) > 0,
-                tag: row.get(8)?,
-                bnc: row.get(9)?,
-                frq: row.get(10)?,
-                exchange: row.get(11)?,
-                favorited: false, // Will be checked later for each word
-            })
+        let words = self.query_dict(|dict| {
+            let mut stmt = dict.prepare(&format!(
+                "SELECT {} FROM stardict
+                 WHERE {}
+                 ORDER BY 
+                    CASE 
+                        WHEN word = ?2 THEN 1
+                        WHEN word LIKE ?2 || '%' THEN 2
+                        ELSE 3
+                    END,
+                    collins DESC, oxford DESC, bnc ASC
+                 LIMIT 100",
+                self.word_columns, condition
+            ))?;
+            let rows = stmt.query_map(params![search_pattern, query], word_from_row)?;
+            rows.collect::<rusqlite::Result<Vec<_>>>()
         })?;
 
         let mut results = Vec::new();
-        for row in rows {
-            let word = row?;
+        for mut word in words {
+            self.apply_glossary(&mut word);
             // Check if this word has a learning log
             let log = if let Some(word_id) = word.id {
                 self.get_learning_log(word_id)?
@@ -293,48 +1171,520 @@ impl Database {
         Ok(results)
     }
 
+    /// 按完整拼写找学习中的单词（`:` 跳转）；大小写不同的多个词条时优先大小写完全一致的
+    pub fn find_learned_by_spelling(&self, spelling: &str) -> Result<Option<Word>> {
+        let dict = self.dict_conn.borrow();
+        let mut stmt = dict.prepare(
+            "SELECT id FROM stardict WHERE word = ?1 ORDER BY (word COLLATE BINARY = ?1) DESC, id ASC"
+        )?;
+        let ids = stmt
+            .query_map(params![spelling], |row| row.get::<_, i64>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        for id in ids {
+            if self.get_learning_log(id)?.is_some() {
+                return Ok(Some(self.get_word_by_id(id)?));
+            }
+        }
+        Ok(None)
+    }
+
+    /// 按拼写找词条 id：大小写完全一致的优先，没有再取忽略大小写的（Polish 和 polish 不混）
+    fn find_dict_id_by_spelling(&self, spelling: &str) -> Result<Option<i64>> {
+        self.query_dict(|dict| {
+            dict.query_row(
+                "SELECT id FROM stardict WHERE word = ?1 ORDER BY (word COLLATE BINARY = ?1) DESC, id ASC LIMIT 1",
+                params![spelling],
+                |row| row.get(0),
+            )
+            .optional()
+        })
+    }
+
+    // Find an ECDICT entry by exact spelling
+    pub fn find_word_by_spelling(&self, spelling: &str) -> Result<Option<Word>> {
+        let word = self.query_dict(|dict| {
+            dict.query_row(
+                &format!("SELECT {} FROM stardict WHERE word = ?1 LIMIT 1", self.word_columns),
+                params![spelling],
+                word_from_row,
+            )
+            .optional()
+        })?;
+        Ok(word.map(|mut word| {
+            self.apply_glossary(&mut word);
+            word
+        }))
+    }
+
+    /// 查询同词族单词：原型、词形变化（含原型的其他变化）以及同词根前缀的派生词
+    pub fn get_word_family(&self, word: &Word) -> Result<Vec<(Word, Option<LearningLog>)>> {
+        // Key "1" holds the lemma's transform codes, not a spelling
+        let forms_of = |exchange: &str| -> Vec<String> {
+            parse_exchange(exchange)
+                .into_iter()
+                .filter(|(key, _)| *key != "1")
+                .map(|(_, value)| value)
+                .collect()
+        };
+
+        let mut base = word.spelling.clone();
+        let mut spellings = Vec::new();
+        if let Some(exchange) = &word.exchange {
+            if let Some(lemma) = parse_exchange(exchange).get("0") {
+                base = lemma.clone();
+            }
+            spellings.extend(forms_of(exchange));
+        }
+
+        // An inflected word: its siblings live in the base form's exchange
+        if base != word.spelling {
+            if let Some(base_word) = self.find_word_by_spelling(&base)? {
+                if let Some(exchange) = &base_word.exchange {
+                    spellings.extend(forms_of(exchange));
+                }
+            }
+            spellings.push(base.clone());
+        }
+        spellings.sort();
+        spellings.dedup();
+
+        let mut seen = HashSet::new();
+        seen.insert(word.spelling.clone());
+
+        let mut family = Vec::new();
+        for spelling in &spellings {
+            if let Some(member) = self.find_word_by_spelling(spelling)? {
+                if seen.insert(member.spelling.clone()) {
+                    family.push(member);
+                }
+            }
+        }
+
+        // Derived words sharing the stem ("govern" -> "government", "governor").
+        // A range on `word` instead of LIKE keeps the idx_word index usable.
+        if base.chars().count() >= 4 {
+            let upper_bound = format!("{}{}", base, char::MAX);
+            let dict = self.dict_conn.borrow();
+            let mut stmt = dict.prepare(&format!(
+                "SELECT {} FROM stardict
+                 WHERE word > ?1 AND word < ?2
+                 AND translation IS NOT NULL
+                 AND word NOT LIKE '% %'
+                 AND word NOT LIKE '%-%'
+                 ORDER BY
+                    collins DESC,
+                    oxford DESC,
+                    CASE WHEN bnc IS NOT NULL THEN bnc ELSE 999999 END ASC
+                 LIMIT ?3",
+                self.word_columns
+            ))?;
+            let rows = stmt.query_map(params![base, upper_bound, FAMILY_DERIVED_LIMIT], word_from_row)?;
+            for row in rows {
+                let member = row?;
+                if seen.insert(member.spelling.clone()) {
+                    family.push(member);
+                }
+            }
+        }
+
+        let mut results = Vec::new();
+        for mut member in family {
+            self.apply_glossary(&mut member);
+            let log = match member.id {
+                Some(word_id) => self.get_learning_log(word_id)?,
+                None => None,
+            };
+            results.push((member, log));
+        }
+        Ok(results)
+    }
+
+    /// 单词复习用：不存在学习记录时先创建
+    pub fn get_word_for_review(&self, word_id: i64) -> Result<Option<(Word, LearningLog)>> {
+        let word = self.get_word_by_id(word_id)?;
+        self.init_learning_log(word_id)?;
+        Ok(self.get_learning_log(word_id)?.map(|log| (word, log)))
+    }
+
     // Get learning log for a word
     fn get_learning_log(&self, word_id: i64) -> Result<Option<LearningLog>> {
         let log = self.learn_conn.query_row(
-            "SELECT word_id, repetition, interval, e_factor, next_review, status
+            "SELECT word_id, repetition, interval, e_factor, next_review, status, consecutive_good
              FROM learning_log WHERE word_id = ?1",
             params![word_id],
             |row| {
+                let word_id: i64 = row.get(0)?;
                 let next_review_str: String = row.get(4)?;
-                let next_review = DateTime::parse_from_rfc3339(&next_review_str)
-                    .map(|dt| dt.with_timezone(&Utc))
-                    .unwrap_or(Utc::now());
+                let next_review = parse_next_review(word_id, &next_review_str)
+                    .unwrap_or_else(Utc::now);
 
                 Ok(LearningLog {
-                    word_id: row.get(0)?,
+                    word_id,
                     repetition: row.get(1)?,
                     interval: row.get(2)?,
                     e_factor: row.get(3)?,
                     next_review,
                     status: LearningStatus::from(row.get::<_, i32>(5)?),
+                    consecutive_good: row.get(6)?,
                 })
             },
         ).optional()?;
         Ok(log)
     }
 
+    /// 单词最近 `limit` 次复习的评分，从旧到新（词典详情的评分轨迹）
+    pub fn get_word_history(&self, word_id: i64, limit: usize) -> Result<Vec<u8>> {
+        let mut stmt = self.learn_conn.prepare(
+            "SELECT quality FROM review_history WHERE word_id = ?1 ORDER BY reviewed_at DESC LIMIT ?2"
+        )?;
+        let mut qualities = stmt
+            .query_map(params![word_id, limit as i64], |row| row.get::<_, u8>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        qualities.reverse();
+        Ok(qualities)
+    }
+
+    /// 最近 RECENTLY_FORGOTTEN_DAYS 天评过“忘记”(1) 的单词，最近忘记的在前；
+    /// 每个词只取最近一次，已掌握和暂停的不算
+    pub fn get_recently_forgotten(&self, limit: usize) -> Result<Vec<(Word, LearningLog, DateTime<Utc>)>> {
+        let study_day = self.day_modifiers()?;
+        let mut stmt = self.learn_conn.prepare(
+            &format!("SELECT h.word_id, MAX(h.reviewed_at) FROM review_history h
+             JOIN learning_log l ON l.word_id = h.word_id
+             WHERE h.quality = 1
+               AND DATE(h.reviewed_at, {study_day}) >= DATE('now', {study_day}, '-' || ?1 || ' days')
+               AND l.status != 2
+               AND h.word_id NOT IN (SELECT word_id FROM suspended)
+             GROUP BY h.word_id
+             ORDER BY MAX(h.reviewed_at) DESC
+             LIMIT ?2")
+        )?;
+        let lapses = stmt
+            .query_map(params![RECENTLY_FORGOTTEN_DAYS, limit as i64], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut results = Vec::new();
+        for (word_id, reviewed_at) in lapses {
+            let Ok(forgot_at) = DateTime::parse_from_rfc3339(&reviewed_at) else { continue };
+            let Some(log) = self.get_learning_log(word_id)? else { continue };
+            let Ok(word) = self.get_word_by_id(word_id) else { continue };
+            results.push((word, log, forgot_at.with_timezone(&Utc)));
+        }
+        Ok(results)
+    }
+
+    /// 开始学习和掌握的时间（单词详情显示用）
+    pub fn get_word_dates(&self, word_id: i64) -> Result<WordDates> {
+        let dates: Option<(Option<String>, Option<String>)> = self.learn_conn.query_row(
+            "SELECT added_at, mastered_at FROM learning_log WHERE word_id = ?1",
+            params![word_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        ).optional()?;
+        let parse = |raw: Option<String>| {
+            raw.and_then(|raw| DateTime::parse_from_rfc3339(&raw).ok()).map(|dt| dt.with_timezone(&Utc))
+        };
+        Ok(dates
+            .map(|(added_at, mastered_at)| WordDates { added_at: parse(added_at), mastered_at: parse(mastered_at) })
+            .unwrap_or_default())
+    }
+
     // Add review to history
-    pub fn add_review_history(&self, word_id: i64, quality: u8, log: &LearningLog) -> Result<()> {
+    /// `response_ms`: time spent on the card with idle pauses excluded (None when not timed).
+    /// `direction`: how the card was asked (None when no card was shown, e.g. bulk review);
+    /// `mode`: where the rating came from — a `ReviewMode::history_name()`, "new" for new words
+    /// mixed into a due session, "self_test" (dictionary), "bulk" (Settings) or "v1"
+    pub fn add_review_history(
+        &self,
+        word_id: i64,
+        quality: u8,
+        log: &LearningLog,
+        response_ms: Option<i64>,
+        direction: Option<ReviewDirection>,
+        mode: &str,
+    ) -> Result<i64> {
+        let history_id =
+            self.insert_review_history(word_id, &Utc::now().to_rfc3339(), quality, log, response_ms, direction, mode)?;
+        // Every rating path ends here, so this is where a Forgot counts as a lapse
+        if quality == 1 {
+            self.add_lapse(word_id)?;
+        }
+        Ok(history_id)
+    }
+
+    /// 双向复习的一张卡：英→中、中→英各记一条（各自的评分和用时），
+    /// `log` 是按较低评分走过一次 SM-2 后的进度，两遍都忘记也只算一次忘记。
+    /// `passes` 是 [(英→中评分, 用时), (中→英评分, 用时)]，返回两条记录的 id
+    pub fn add_both_review_history(
+        &self,
+        word_id: i64,
+        passes: [(u8, i64); 2],
+        log: &LearningLog,
+        mode: &str,
+    ) -> Result<[i64; 2]> {
+        let [(recognition, recognition_ms), (production, production_ms)] = passes;
+        // 两条共用一个时间，按卡计数时靠它配对（CARD_ROWS）
+        let reviewed_at = Utc::now().to_rfc3339();
+        let ids = [
+            self.insert_review_history(word_id, &reviewed_at, recognition, log, Some(recognition_ms), Some(ReviewDirection::EnToZh), mode)?,
+            self.insert_review_history(word_id, &reviewed_at, production, log, Some(production_ms), Some(ReviewDirection::ZhToEn), mode)?,
+        ];
+        if recognition.min(production) == 1 {
+            self.add_lapse(word_id)?;
+        }
+        Ok(ids)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn insert_review_history(
+        &self,
+        word_id: i64,
+        reviewed_at: &str,
+        quality: u8,
+        log: &LearningLog,
+        response_ms: Option<i64>,
+        direction: Option<ReviewDirection>,
+        mode: &str,
+    ) -> Result<i64> {
         self.learn_conn.execute(
-            "INSERT INTO review_history (word_id, reviewed_at, quality, repetition, interval, e_factor)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            "INSERT INTO review_history (word_id, reviewed_at, quality, repetition, interval, e_factor, response_ms, direction, mode)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
             params![
                 word_id,
-                Utc::now().to_rfc3339(),
+                reviewed_at,
                 quality,
                 log.repetition,
                 log.interval,
-                log.e_factor
+                log.e_factor,
+                response_ms,
+                direction.map(ReviewDirection::to_setting),
+                mode
             ],
         )?;
+        Ok(self.learn_conn.last_insert_rowid())
+    }
+
+    fn add_lapse(&self, word_id: i64) -> Result<()> {
+        self.learn_conn.execute("UPDATE learning_log SET lapses = lapses + 1 WHERE word_id = ?1", params![word_id])?;
         Ok(())
     }
 
+    /// 同一轮复习里再次作答的记录不算“第一次就答对”（见 `get_first_try_retention`）
+    pub fn mark_retry(&self, history_id: i64) -> Result<()> {
+        self.learn_conn.execute("UPDATE review_history SET first_try = 0 WHERE id = ?1", params![history_id])?;
+        Ok(())
+    }
+
+    /// (复习次数, 评 3 分以上次数)，不含批量复习（那不是真的回忆）
+    fn retention_where(&self, condition: &str) -> Result<(i64, i64)> {
+        Ok(self.learn_conn.query_row(
+            &format!(
+                "SELECT COUNT(*), COALESCE(SUM(quality >= 3), 0) FROM review_history
+                 WHERE mode IS NOT 'bulk'{condition}"
+            ),
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?)
+    }
+
+    /// 全部复习记录的保持率
+    pub fn get_overall_retention(&self) -> Result<(i64, i64)> {
+        self.retention_where("")
+    }
+
+    /// 只看每轮复习里每个词的第一次作答：同一轮里忘了又重考答对的不算答对
+    pub fn get_first_try_retention(&self) -> Result<(i64, i64)> {
+        self.retention_where(" AND first_try = 1")
+    }
+
+    /// 评“忘记”的总次数（清理复习历史不影响）
+    pub fn get_lapses(&self, word_id: i64) -> Result<i64> {
+        Ok(self
+            .learn_conn
+            .query_row("SELECT lapses FROM learning_log WHERE word_id = ?1", params![word_id], |r| r.get(0))
+            .optional()?
+            .unwrap_or(0))
+    }
+
+    /// 各复习方向的 (复习次数, 评 3 分以上次数)，按 `ReviewDirection::ALL` 的顺序，没有记录的方向不列出
+    pub fn get_retention_by_direction(&self) -> Result<Vec<(ReviewDirection, i64, i64)>> {
+        let mut stmt = self.learn_conn.prepare(
+            "SELECT direction, COUNT(*), COALESCE(SUM(quality >= 3), 0) FROM review_history
+             WHERE direction IS NOT NULL
+             GROUP BY direction",
+        )?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?, row.get::<_, i64>(2)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(ReviewDirection::ALL
+            .into_iter()
+            .filter_map(|direction| {
+                let (_, reviews, recalled) = rows.iter().find(|(raw, _, _)| raw == direction.to_setting())?;
+                Some((direction, *reviews, *recalled))
+            })
+            .collect())
+    }
+
+    /// 合并另一台机器上的进度数据库：
+    /// 同一单词保留更靠后（next_review 更晚，其次 repetition 更高）的学习记录，
+    /// 复习历史按 (word_id, reviewed_at, quality) 去重后追加
+    pub fn merge_progress(&self, path: &Path) -> Result<MergeSummary> {
+        let other = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let mut summary = MergeSummary::default();
+
+        // An older file may lack later columns: fill them the way initialize's migrations would
+        let log_columns = table_columns(&other, "learning_log")?;
+        let log_column = |column: &str, fallback: &str| {
+            if log_columns.contains(column) { column.to_string() } else { fallback.to_string() }
+        };
+        let mut stmt = other.prepare(&format!(
+            "SELECT word_id, repetition, interval, e_factor, next_review, status, {}, {}, {}, {} FROM learning_log",
+            log_column("consecutive_good", "repetition"),
+            log_column(
+                "lapses",
+                "(SELECT COUNT(*) FROM review_history h WHERE h.word_id = learning_log.word_id AND h.quality = 1)",
+            ),
+            log_column("added_at", "NULL"),
+            log_column("mastered_at", "NULL"),
+        ))?;
+        let incoming = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, i32>(1)?,
+                    row.get::<_, i32>(2)?,
+                    row.get::<_, f64>(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get::<_, i32>(5)?,
+                    row.get::<_, i32>(6)?,
+                    row.get::<_, i64>(7)?,
+                    row.get::<_, Option<String>>(8)?,
+                    row.get::<_, Option<String>>(9)?,
+                ))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let tx = self.learn_conn.unchecked_transaction()?;
+
+        for (word_id, repetition, interval, e_factor, next_review_str, status, consecutive_good, lapses, added_at, mastered_at) in
+            incoming
+        {
+            let Some(next_review) = parse_next_review(word_id, &next_review_str) else {
+                summary.skipped += 1;
+                continue;
+            };
+
+            let local: Option<(i32, i32, f64, String, i32)> = tx.query_row(
+                "SELECT repetition, interval, e_factor, next_review, status
+                 FROM learning_log WHERE word_id = ?1",
+                params![word_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)),
+            ).optional()?;
+
+            let take_incoming = match &local {
+                None => true,
+                Some(local) if *local == (repetition, interval, e_factor, next_review_str.clone(), status) => false,
+                Some((local_repetition, _, _, local_next_str, _)) => {
+                    summary.conflicted += 1;
+                    match DateTime::parse_from_rfc3339(local_next_str) {
+                        Ok(local_next) => {
+                            let local_next = local_next.with_timezone(&Utc);
+                            next_review > local_next
+                                || (next_review == local_next && repetition > *local_repetition)
+                        }
+                        // A corrupt local timestamp loses to any valid one
+                        Err(_) => true,
+                    }
+                }
+            };
+
+            if take_incoming {
+                // The winning row brings its streak, lapses and dates along with the schedule,
+                // so mastery and auto-favorite see one consistent record. Dates an older file
+                // does not have keep the local value; mastered_at only while Mastered
+                tx.execute(
+                    "INSERT INTO learning_log
+                     (word_id, repetition, interval, e_factor, next_review, status,
+                      consecutive_good, lapses, added_at, mastered_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+                     ON CONFLICT(word_id) DO UPDATE SET
+                         repetition = excluded.repetition,
+                         interval = excluded.interval,
+                         e_factor = excluded.e_factor,
+                         next_review = excluded.next_review,
+                         status = excluded.status,
+                         consecutive_good = excluded.consecutive_good,
+                         lapses = excluded.lapses,
+                         added_at = COALESCE(excluded.added_at, added_at),
+                         mastered_at = CASE WHEN excluded.status = 2 THEN COALESCE(excluded.mastered_at, mastered_at) END",
+                    params![
+                        word_id,
+                        repetition,
+                        interval,
+                        e_factor,
+                        next_review.to_rfc3339(),
+                        status,
+                        consecutive_good,
+                        lapses,
+                        added_at,
+                        mastered_at
+                    ],
+                )?;
+                summary.merged += 1;
+            } else {
+                summary.skipped += 1;
+            }
+        }
+
+        // Same fallbacks as the review_history migrations: old rows are English → Chinese
+        // first tries with unknown timing and mode
+        let history_columns = table_columns(&other, "review_history")?;
+        let history_column = |column: &str, fallback: &str| {
+            if history_columns.contains(column) { column.to_string() } else { fallback.to_string() }
+        };
+        let mut stmt = other.prepare(&format!(
+            "SELECT word_id, reviewed_at, quality, repetition, interval, e_factor, {}, {}, {}, {} FROM review_history",
+            history_column("response_ms", "NULL"),
+            history_column("direction", "'en_to_zh'"),
+            history_column("mode", "NULL"),
+            history_column("first_try", "1"),
+        ))?;
+        let history = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i32>(2)?,
+                    row.get::<_, i32>(3)?,
+                    row.get::<_, i32>(4)?,
+                    row.get::<_, f64>(5)?,
+                    row.get::<_, Option<i64>>(6)?,
+                    row.get::<_, Option<String>>(7)?,
+                    row.get::<_, Option<String>>(8)?,
+                    row.get::<_, i64>(9)?,
+                ))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        for (word_id, reviewed_at, quality, repetition, interval, e_factor, response_ms, direction, mode, first_try) in history {
+            summary.history_added += tx.execute(
+                "INSERT INTO review_history
+                 (word_id, reviewed_at, quality, repetition, interval, e_factor, response_ms, direction, mode, first_try)
+                 SELECT ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10
+                 WHERE NOT EXISTS (
+                     SELECT 1 FROM review_history
+                     WHERE word_id = ?1 AND reviewed_at = ?2 AND quality = ?3
+                       AND direction IS COALESCE(?8, 'en_to_zh')
+                 )",
+                params![word_id, reviewed_at, quality, repetition, interval, e_factor, response_ms, direction, mode, first_try],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(summary)
+    }
+
     // Get recent review history
     pub fn get_recent_reviews(&self, limit: i64) -> Result<Vec<(Word, String, u8)>> {
         let mut stmt = self.learn_conn.prepare(
@@ -361,6 +1711,62 @@ impl Database {
         Ok(results)
     }
 
+    /// 全局搜索：只在学过的单词、复习历史和收藏里查（拼写不分大小写，或中文释义包含）
+    pub fn global_search(&self, query: &str) -> Result<GlobalSearchHits> {
+        let query = query.trim();
+        let mut hits = GlobalSearchHits::default();
+        if query.is_empty() {
+            return Ok(hits);
+        }
+        let needle = query.to_lowercase();
+        let matches = |word: &Word| {
+            word.spelling.to_lowercase().contains(&needle)
+                || word.translation.as_deref().is_some_and(|t| t.contains(query))
+        };
+
+        let mut learned_words: HashMap<i64, Word> = HashMap::new();
+        for (word, log) in self.get_all_words()? {
+            let Some(log) = log else { continue };
+            let word_id = log.word_id;
+            if matches(&word) && hits.learned.len() < GLOBAL_SEARCH_LIMIT {
+                hits.learned.push((word.clone(), log));
+            }
+            learned_words.insert(word_id, word);
+        }
+
+        // SQLite returns the quality of the MAX(reviewed_at) row for the bare column
+        let mut stmt = self.learn_conn.prepare(
+            "SELECT word_id, MAX(reviewed_at), quality FROM review_history
+             GROUP BY word_id ORDER BY MAX(reviewed_at) DESC"
+        )?;
+        let reviews = stmt
+            .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, u8>(2)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        for (word_id, reviewed_at, quality) in reviews {
+            if hits.history.len() >= GLOBAL_SEARCH_LIMIT {
+                break;
+            }
+            let word = match learned_words.get(&word_id) {
+                Some(word) => word.clone(),
+                None => match self.get_word_by_id(word_id) {
+                    Ok(word) => word,
+                    Err(_) => continue,
+                },
+            };
+            if matches(&word) {
+                hits.history.push((word, reviewed_at, quality));
+            }
+        }
+
+        hits.favorites = self
+            .get_favorites()?
+            .into_iter()
+            .filter(|word| matches(word))
+            .take(GLOBAL_SEARCH_LIMIT)
+            .collect();
+        Ok(hits)
+    }
+
     // Get review statistics for forgetting curve
     pub fn get_review_stats_by_interval(&self) -> Result<Vec<(i32, f64, i64)>> {
         let mut stmt = self.learn_conn.prepare(
@@ -387,12 +1793,14 @@ impl Database {
 
     // Get daily review count for the last N days
     pub fn get_daily_review_counts(&self, days: i64) -> Result<Vec<(String, i64)>> {
+        let study_day = self.day_modifiers()?;
         let mut stmt = self.learn_conn.prepare(
-            "SELECT DATE(reviewed_at, 'localtime') as review_date, COUNT(*) as count
+            &format!("SELECT DATE(reviewed_at, {study_day}) as review_date, COUNT(*) as count
              FROM review_history
-             WHERE DATE(reviewed_at, 'localtime') >= DATE('now', 'localtime', '-' || ?1 || ' days')
+             WHERE DATE(reviewed_at, {study_day}) >= DATE('now', {study_day}, '-' || ?1 || ' days')
+               AND {CARD_ROWS}
              GROUP BY review_date
-             ORDER BY review_date ASC"
+             ORDER BY review_date ASC")
         )?;
 
         let rows = stmt.query_map(params![days], |row| {
@@ -406,11 +1814,217 @@ impl Database {
         Ok(results)
     }
 
+    /// 已复习过的单词按 e_factor 分档计数（下标对应 EASE_BUCKET_BOUNDS，最后一档为 2.5 及以上）。
+    ///
+    /// 不含从没复习过的新词（status = 0，e_factor 还是初始的 2.5）；忘记后 repetition
+    /// 归零的单词仍计入，低 e_factor 的正是它们
+    pub fn get_ease_distribution(&self) -> Result<[u64; EASE_BUCKET_BOUNDS.len() + 1]> {
+        let mut stmt = self.learn_conn.prepare("SELECT e_factor FROM learning_log WHERE status != 0")?;
+        let mut buckets = [0; EASE_BUCKET_BOUNDS.len() + 1];
+        for e_factor in stmt.query_map([], |row| row.get::<_, f64>(0))? {
+            let e_factor = e_factor?;
+            buckets[EASE_BUCKET_BOUNDS.partition_point(|bound| *bound <= e_factor)] += 1;
+        }
+        Ok(buckets)
+    }
+
+    /// 总览页：状态、到期、间隔和记忆因子、打卡、近期保持率和各考试单词本的覆盖
+    pub fn get_deck_summary(&self) -> Result<DeckSummary> {
+        let (new, learning, mastered) = self.learning_status_counts()?;
+        let forecast = self.get_due_forecast(7)?;
+        let (avg_interval, avg_ease) = self.learn_conn.query_row(
+            "SELECT COALESCE(AVG(interval), 0), COALESCE(AVG(e_factor), 0) FROM learning_log WHERE status != 0",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?)),
+        )?;
+        let since = (Utc::now() - chrono::Duration::days(RETENTION_DAYS)).to_rfc3339();
+        let (recent_reviews, recent_recalled) = self.learn_conn.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(quality >= 3), 0) FROM review_history WHERE reviewed_at >= ?1",
+            params![since],
+            |r| Ok((r.get(0)?, r.get(1)?)),
+        )?;
+        Ok(DeckSummary {
+            new,
+            learning,
+            mastered,
+            suspended: self.learn_conn.query_row("SELECT COUNT(*) FROM suspended", [], |r| r.get(0))?,
+            favorites: self.get_favorites_count()?,
+            due_today: forecast.first().copied().unwrap_or(0),
+            due_week: forecast.iter().sum(),
+            avg_interval,
+            avg_ease,
+            ease_buckets: self.get_ease_distribution()?.to_vec(),
+            streak: self.get_checkin_streak()?,
+            recent_reviews,
+            recent_recalled,
+            tag_coverage: self.get_tag_coverage()?,
+        })
+    }
+
+    /// 各考试标签（EXAM_TAGS 顺序）学过的单词数（status > 0，只加入未学的不算）和单词本总词数；ECDICT 里没有的标签不列
+    fn get_tag_coverage(&self) -> Result<Vec<(String, usize, usize)>> {
+        let learned: HashSet<i64> = {
+            let mut stmt = self.learn_conn.prepare("SELECT word_id FROM learning_log WHERE status > 0")?;
+            let ids = stmt.query_map([], |row| row.get(0))?;
+            ids.collect::<rusqlite::Result<_>>()?
+        };
+        let mut counts: HashMap<&str, (usize, usize)> = HashMap::new();
+        let dict = self.dict_conn.borrow();
+        let mut stmt = dict.prepare(&format!(
+            "SELECT {} FROM stardict WHERE tag IS NOT NULL AND tag != '' AND translation IS NOT NULL",
+            self.dict_select("id, tag")
+        ))?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?;
+        for row in rows {
+            let (word_id, tags) = row?;
+            for tag in split_tags(&tags) {
+                let Some(tag) = EXAM_TAGS.into_iter().find(|exam| *exam == tag) else { continue };
+                let entry = counts.entry(tag).or_default();
+                entry.1 += 1;
+                if learned.contains(&word_id) {
+                    entry.0 += 1;
+                }
+            }
+        }
+        Ok(EXAM_TAGS
+            .into_iter()
+            .filter_map(|tag| counts.get(tag).map(|&(learned, total)| (tag.to_string(), learned, total)))
+            .collect())
+    }
+
+    /// 最近 N 周每周的复习次数，按周一日期分组（无复习的周不出现）
+    pub fn get_weekly_review_counts(&self, weeks: i64) -> Result<Vec<(String, i64)>> {
+        let study_day = self.day_modifiers()?;
+        let mut stmt = self.learn_conn.prepare(
+            &format!("SELECT DATE(day, 'weekday 0', '-6 days') as week_start, SUM(reviews) as count
+             FROM (
+                SELECT DATE(reviewed_at, {study_day}) AS day, 1 AS reviews FROM review_history WHERE {CARD_ROWS}
+                UNION ALL
+                SELECT day, reviews FROM history_daily_summary
+             )
+             WHERE week_start >= DATE('now', {study_day}, 'weekday 0', '-6 days', '-' || ((?1 - 1) * 7) || ' days')
+             GROUP BY week_start
+             ORDER BY week_start ASC")
+        )?;
+
+        let rows = stmt.query_map(params![weeks], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+        Ok(results)
+    }
+
+    /// 最近 N 天每天加入学习的单词数（按 added_at，只算复习过的），按日期从早到晚，无记录的天为 0。
+    /// 不看复习历史：清理历史后剩下的最早一条不是真正的第一次复习
+    pub fn get_words_added_by_day(&self, days: i64) -> Result<Vec<u64>> {
+        let study_day = self.day_modifiers()?;
+        self.daily_series(
+            &format!("SELECT DATE(added_at, {study_day}) AS day, COUNT(*)
+             FROM learning_log
+             WHERE status > 0 AND day >= DATE('now', {study_day}, '-' || (?1 - 1) || ' days')
+             GROUP BY day"),
+            days,
+        )
+    }
+
+    /// 最近 N 天每天新掌握的单词数（按 mastered_at，取消掌握的不算）
+    pub fn get_words_mastered_by_day(&self, days: i64) -> Result<Vec<u64>> {
+        let study_day = self.day_modifiers()?;
+        self.daily_series(
+            &format!("SELECT DATE(mastered_at, {study_day}) AS day, COUNT(*)
+             FROM learning_log
+             WHERE day >= DATE('now', {study_day}, '-' || (?1 - 1) || ' days')
+             GROUP BY day"),
+            days,
+        )
+    }
+
+    /// Run a `(day, count)` query and spread it over the last `days` days, oldest first
+    fn daily_series(&self, sql: &str, days: i64) -> Result<Vec<u64>> {
+        let mut stmt = self.learn_conn.prepare(sql)?;
+        let counts = stmt
+            .query_map(params![days], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))?
+            .collect::<rusqlite::Result<HashMap<_, _>>>()?;
+
+        let today = self.today()?;
+        Ok((0..days)
+            .rev()
+            .map(|offset| {
+                let day = (today - chrono::Duration::days(offset)).format("%Y-%m-%d").to_string();
+                counts.get(&day).copied().unwrap_or(0) as u64
+            })
+            .collect())
+    }
+
+    /// 未来 `days` 天每天到期的复习数（下标 0 为今天，含已逾期）
+    pub fn get_due_forecast(&self, days: i64) -> Result<Vec<u64>> {
+        let mut stmt = self.learn_conn.prepare(&format!(
+            "SELECT word_id, next_review FROM learning_log WHERE 1 = 1{}",
+            self.review_filter()?
+        ))?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?;
+
+        // Same study day boundary as the dashboard and the daily counts
+        let today = self.today()?;
+        let rollover_hour = self.get_day_rollover_hour()?;
+        let mut forecast = vec![0u64; days.max(0) as usize];
+        for row in rows {
+            let (word_id, next_review_str) = row?;
+            let Some(next_review) = parse_next_review(word_id, &next_review_str) else {
+                continue;
+            };
+            let due_day = effective_date(next_review.with_timezone(&chrono::Local), rollover_hour);
+            let offset = (due_day - today).num_days().max(0) as usize;
+            if let Some(count) = forecast.get_mut(offset) {
+                *count += 1;
+            }
+        }
+        Ok(forecast)
+    }
+
+    /// 根据未来一周的复习量和每日目标，建议本次引入多少新词
+    ///
+    /// Up to `goal * 7` upcoming reviews the full batch is allowed; beyond that
+    /// the budget shrinks linearly and reaches zero at `BACKLOG_CUTOFF_RATIO`
+    /// times the weekly capacity.
+    pub fn suggest_new_word_budget(&self) -> Result<NewWordBudget> {
+        let forecast_due: u64 = self.get_due_forecast(FORECAST_DAYS)?.iter().sum();
+        let capacity = (self.get_daily_goal()?.max(1) * FORECAST_DAYS) as f64;
+        let forecast_due = forecast_due as i64;
+
+        let excess = (forecast_due as f64 - capacity) / (capacity * (BACKLOG_CUTOFF_RATIO - 1.0));
+        let budget = (NEW_WORDS_BATCH as f64 * (1.0 - excess.clamp(0.0, 1.0))).round() as i64;
+        Ok(NewWordBudget {
+            budget,
+            forecast_due,
+            overdue: self.get_overdue_count()?,
+            backlogged: forecast_due as f64 > capacity,
+        })
+    }
+
+    /// 已逾期的复习数：过了 next_review 再加上宽限时间还没复习的单词
+    ///
+    /// 宽限期内的单词仍然到期（get_due_reviews 照常给出），只是还不算逾期、也不标成逾期样式
+    pub fn get_overdue_count(&self) -> Result<i64> {
+        let cutoff = Utc::now() - chrono::Duration::hours(self.get_overdue_grace_hours()?);
+        let count = self.learn_conn.query_row(
+            &format!("SELECT COUNT(*) FROM learning_log WHERE next_review <= ?1{}", self.review_filter()?),
+            params![cutoff.to_rfc3339()],
+            |r| r.get(0),
+        )?;
+        Ok(count)
+    }
+
     // Get today's completed review count
     pub fn get_today_completed_count(&self) -> Result<i64> {
+        let study_day = self.day_modifiers()?;
         let count: i64 = self.learn_conn.query_row(
-            "SELECT COUNT(*) FROM review_history
-             WHERE DATE(reviewed_at, 'localtime') = DATE('now', 'localtime')",
+            &format!("SELECT COUNT(*) FROM review_history
+             WHERE DATE(reviewed_at, {study_day}) = DATE('now', {study_day}) AND {CARD_ROWS}"),
             [],
             |r| r.get(0)
         )?;
@@ -420,48 +2034,20 @@ impl Database {
     // Get new words to learn from high-quality ECDICT entries
     // Prioritize: oxford 3000, high collins rating, common words by frequency
     pub fn get_new_words_to_learn(&self, limit: i64) -> Result<Vec<(Word, LearningLog)>> {
+        if self.get_skip_new_words_today()? {
+            return Ok(Vec::new());
+        }
         // First, check if we have enough words with status = 0
         let new_count: i64 = self.learn_conn.query_row(
-            "SELECT COUNT(*) FROM learning_log WHERE status = 0",
+            "SELECT COUNT(*) FROM learning_log
+             WHERE status = 0 AND word_id NOT IN (SELECT word_id FROM suspended)",
             [],
             |r| r.get(0)
         )?;
 
+        // Words queued by an earlier session come first
+        let mut results = self.queued_new_words(limit)?;
         if new_count >= limit {
-            // Return existing new words
-            let mut stmt = self.learn_conn.prepare(
-                "SELECT word_id, repetition, interval, e_factor, next_review, status
-                 FROM learning_log
-                 WHERE status = 0
-                 ORDER BY word_id ASC
-                 LIMIT ?1"
-            )?;
-
-            let rows = stmt.query_map(params![limit], |row| {
-                let word_id: i64 = row.get(0)?;
-                let next_review_str: String = row.get(4)?;
-                let next_review = DateTime::parse_from_rfc3339(&next_review_str)
-                    .map(|dt| dt.with_timezone(&Utc))
-                    .unwrap_or(Utc::now());
-
-                let log = LearningLog {
-                    word_id,
-                    repetition: row.get(1)?,
-                    interval: row.get(2)?,
-                    e_factor: row.get(3)?,
-                    next_review,
-                    status: LearningStatus::from(row.get::<_, i32>(5)?),
-                };
-                Ok((word_id, log))
-            })?;
-
-            let mut results = Vec::new();
-            for row in rows {
-                let (word_id, log) = row?;
-                if let Ok(word) = self.get_word_by_id(word_id) {
-                    results.push((word, log));
-                }
-            }
             return Ok(results);
         }
 
@@ -483,46 +2069,44 @@ impl Database {
             existing_ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(",")
         };
         
+        // 设置了重点单词本时先取该单词本的词，取完后自然回落到全库排序
+        let focus_tag = self.get_new_word_tag()?;
+        let focus_order = if focus_tag.is_some() {
+            format!("CASE WHEN {} THEN 0 ELSE 1 END,", tag_match_sql("?2"))
+        } else {
+            String::new()
+        };
+
+        let proper_noun_filter = if self.get_skip_proper_nouns()? { PROPER_NOUN_FILTER } else { "" };
+        let band_filter = freq_band_sql(self.get_freq_band()?);
+
         let query = format!(
-            "SELECT id, word, phonetic, definition, translation, pos, collins, oxford, tag, bnc, frq, exchange
-             FROM stardict
+            "SELECT {} FROM stardict
              WHERE id NOT IN ({})
              AND translation IS NOT NULL
              AND LENGTH(word) > 1
              AND word NOT LIKE '%-%'
-             AND word NOT LIKE '% %'
-             ORDER BY 
+             AND word NOT LIKE '% %'{}{}
+             ORDER BY {}
                 oxford DESC,
                 collins DESC,
                 CASE WHEN bnc IS NOT NULL THEN bnc ELSE 999999 END ASC,
                 CASE WHEN frq IS NOT NULL THEN frq ELSE 999999 END ASC
              LIMIT ?1",
-            placeholders
+            self.word_columns, placeholders, proper_noun_filter, band_filter, focus_order
         );
 
-        let mut stmt = self.dict_conn.prepare(&query)?;
-        let rows = stmt.query_map(params![needed], |row| {
-            Ok(Word {
-                id: Some(row.get(0)?),
-                spelling: row.get(1)?,
-                phonetic: row.get(2)?,
-                definition: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
-                translation: row.get(4)?,
-                pos: row.get(5)?,
-                collins: row.get::<_, Option<i32>>(6)?.unwrap_or(0),
-                oxford: row.get::<_, Option<i32>>(7)?.unwrap_or(0) > 0,
-                tag: row.get(8)?,
-                bnc: row.get(9)?,
-                frq: row.get(10)?,
-                exchange: row.get(11)?,
-                favorited: false,
-            })
-        })?;
+        let dict = self.dict_conn.borrow();
+        let mut stmt = dict.prepare(&query)?;
+        let rows = match &focus_tag {
+            Some(tag) => stmt.query_map(params![needed, tag], word_from_row)?,
+            None => stmt.query_map(params![needed], word_from_row)?,
+        };
 
         // Add these words to learning_log and return them
-        let mut results = Vec::new();
         for row in rows {
-            let word = row?;
+            let mut word = row?;
+            self.apply_glossary(&mut word);
             if let Some(word_id) = word.id {
                 self.init_learning_log(word_id)?;
                 if let Ok(Some(log)) = self.get_learning_log(word_id) {
@@ -531,20 +2115,24 @@ impl Database {
             }
         }
 
-        // Also get existing new words
+        Ok(results)
+    }
+
+    /// Words queued by an earlier call but not reviewed yet, in the order they were picked
+    fn queued_new_words(&self, limit: i64) -> Result<Vec<(Word, LearningLog)>> {
         let mut stmt = self.learn_conn.prepare(
-            "SELECT word_id, repetition, interval, e_factor, next_review, status
+            "SELECT word_id, repetition, interval, e_factor, next_review, status, consecutive_good
              FROM learning_log
-             WHERE status = 0
-             ORDER BY word_id ASC"
+             WHERE status = 0 AND word_id NOT IN (SELECT word_id FROM suspended)
+             ORDER BY added_at ASC, word_id ASC
+             LIMIT ?1"
         )?;
 
-        let rows = stmt.query_map([], |row| {
+        let rows = stmt.query_map(params![limit], |row| {
             let word_id: i64 = row.get(0)?;
             let next_review_str: String = row.get(4)?;
-            let next_review = DateTime::parse_from_rfc3339(&next_review_str)
-                .map(|dt| dt.with_timezone(&Utc))
-                .unwrap_or(Utc::now());
+            let next_review = parse_next_review(word_id, &next_review_str)
+                .unwrap_or_else(Utc::now);
 
             let log = LearningLog {
                 word_id,
@@ -553,48 +2141,41 @@ impl Database {
                 e_factor: row.get(3)?,
                 next_review,
                 status: LearningStatus::from(row.get::<_, i32>(5)?),
+                consecutive_good: row.get(6)?,
             };
             Ok((word_id, log))
         })?;
 
+        let mut results = Vec::new();
         for row in rows {
             let (word_id, log) = row?;
             if let Ok(word) = self.get_word_by_id(word_id) {
                 results.push((word, log));
-                if results.len() >= limit as usize {
-                    break;
-                }
             }
         }
-
         Ok(results)
     }
 
     /// 获取所有可用的单词本（按 tag 分组，返回 tag 和单词数量）
     pub fn get_wordbooks(&self) -> Result<Vec<(String, usize)>> {
         // 定义主要考试标签的优先级顺序
-        let priority_tags = vec!["GRE", "TOEFL", "IELTS", "考研", "CET-6", "CET-4", "高考", "中考"];
+        let priority_tags = ["GRE", "TOEFL", "IELTS", "考研", "CET-6", "CET-4", "高考", "中考"];
         
         let mut wordbook_map: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
         
         // 查询所有单词的标签
-        let mut stmt = self.dict_conn.prepare(
-            "SELECT tag FROM stardict WHERE tag IS NOT NULL AND tag != '' AND translation IS NOT NULL"
-        )?;
+        let dict = self.dict_conn.borrow();
+        let mut stmt = dict.prepare(&format!(
+            "SELECT {} FROM stardict WHERE tag IS NOT NULL AND tag != '' AND translation IS NOT NULL",
+            self.dict_select("tag")
+        ))?;
         
         let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
         
         for row in rows {
             let tag_string = row?;
-            // 拆分复合标签 (分隔符: 空格, 逗号, 顿号, 中点)
-            let tags: Vec<&str> = tag_string
-                .split(|c| c == ' ' || c == ',' || c == '、' || c == '·')
-                .map(|s| s.trim())
-                .filter(|s| !s.is_empty())
-                .collect();
-            
             // 为每个标签计数
-            for tag in tags {
+            for tag in split_tags(&tag_string) {
                 *wordbook_map.entry(tag.to_string()).or_insert(0) += 1;
             }
         }
@@ -619,65 +2200,129 @@ impl Database {
         Ok(wordbooks)
     }
 
-    /// 根据 tag 获取单词列表（支持乱序）
-    pub fn get_words_by_tag(&self, tag: &str, limit: usize, shuffle: bool) -> Result<Vec<(Word, LearningLog)>> {
-        let order_clause = if shuffle {
-            "ORDER BY RANDOM()"
+    /// 比较两个单词本的词汇重合度（多标签单词会同时计入两边）
+    pub fn wordbook_overlap(&self, tag_a: &str, tag_b: &str) -> Result<WordbookOverlap> {
+        let dict = self.dict_conn.borrow();
+        let mut stmt = dict.prepare(&format!(
+            "SELECT {} FROM stardict
+             WHERE tag IS NOT NULL AND tag != '' AND translation IS NOT NULL",
+            self.dict_select("word, tag, oxford, collins")
+        ))?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<i32>>(2)?.unwrap_or(0),
+                row.get::<_, Option<i32>>(3)?.unwrap_or(0),
+            ))
+        })?;
+
+        let mut overlap = WordbookOverlap::default();
+        let mut shared_words = Vec::new();
+        for row in rows {
+            let (spelling, tag_string, oxford, collins) = row?;
+            let in_a = split_tags(&tag_string).any(|t| t == tag_a);
+            let in_b = split_tags(&tag_string).any(|t| t == tag_b);
+            match (in_a, in_b) {
+                (true, true) => {
+                    overlap.shared += 1;
+                    shared_words.push((oxford, collins, spelling));
+                }
+                (true, false) => overlap.only_a += 1,
+                (false, true) => overlap.only_b += 1,
+                (false, false) => {}
+            }
+        }
+
+        // Most important shared words first
+        shared_words.sort_by(|a, b| b.0.cmp(&a.0).then(b.1.cmp(&a.1)).then(a.2.cmp(&b.2)));
+        overlap.shared_sample = shared_words
+            .into_iter()
+            .take(OVERLAP_SAMPLE_SIZE)
+            .map(|(_, _, spelling)| spelling)
+            .collect();
+
+        Ok(overlap)
+    }
+
+    /// 根据 tag 获取单词列表（支持乱序；due_only 时只取已到期的单词），还没学的词加入学习
+    pub fn get_words_by_tag(&self, tag: &str, limit: usize, order: WordOrder, due_only: bool) -> Result<Vec<(Word, LearningLog)>> {
+        self.words_by_tag(tag, limit, order, due_only, true)
+    }
+
+    /// 同 `get_words_by_tag`，但不写数据库（浏览模式）：还没学的词配一张没有进度的新卡
+    pub fn browse_words_by_tag(&self, tag: &str, limit: usize, order: WordOrder, due_only: bool) -> Result<Vec<(Word, LearningLog)>> {
+        self.words_by_tag(tag, limit, order, due_only, false)
+    }
+
+    fn words_by_tag(&self, tag: &str, limit: usize, order: WordOrder, due_only: bool, enroll: bool) -> Result<Vec<(Word, LearningLog)>> {
+        let order_clause = match order {
+            WordOrder::Frequency => "ORDER BY oxford DESC, collins DESC, bnc ASC, frq ASC",
+            WordOrder::Shuffle => "ORDER BY RANDOM()",
+            WordOrder::Dictionary => "ORDER BY id ASC",
+        };
+
+        // learning_log 在另一个数据库，先取出到期的 id 再限定 stardict 查询
+        let due_clause = if due_only {
+            let mut stmt = self.learn_conn.prepare(&format!(
+                "SELECT word_id FROM learning_log WHERE next_review <= ?1{}",
+                self.review_filter()?
+            ))?;
+            let due_ids = stmt
+                .query_map(params![Utc::now().to_rfc3339()], |row| row.get::<_, i64>(0))?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            if due_ids.is_empty() {
+                return Ok(Vec::new());
+            }
+            format!(
+                "AND id IN ({})",
+                due_ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(",")
+            )
         } else {
-            "ORDER BY oxford DESC, collins DESC, bnc ASC, frq ASC"
+            String::new()
         };
 
-        // 使用精确标签匹配：标签必须是独立的词（被分隔符包围或在开头/结尾）
-        // SQLite 正则表达式：(^|[· ,、])tag($|[· ,、])
         let query = format!(
-            "SELECT id, word, phonetic, definition, translation, pos, collins, oxford, tag, bnc, frq, exchange
-             FROM stardict
-             WHERE (tag = ?1 
-                    OR tag LIKE ?1 || ' %' 
-                    OR tag LIKE '% ' || ?1 
-                    OR tag LIKE '% ' || ?1 || ' %'
-                    OR tag LIKE ?1 || '·%'
-                    OR tag LIKE '%·' || ?1
-                    OR tag LIKE '%·' || ?1 || '·%'
-                    OR tag LIKE ?1 || ',%'
-                    OR tag LIKE '%,' || ?1
-                    OR tag LIKE '%,' || ?1 || ',%'
-                    OR tag LIKE ?1 || '、%'
-                    OR tag LIKE '%、' || ?1
-                    OR tag LIKE '%、' || ?1 || '、%')
+            "SELECT {} FROM stardict
+             WHERE {}
              AND translation IS NOT NULL
              {}
+             {}
              LIMIT ?2",
-            order_clause
+            self.word_columns, tag_match_sql("?1"), due_clause, order_clause
         );
 
-        let mut stmt = self.dict_conn.prepare(&query)?;
-        let rows = stmt.query_map(params![tag, limit], |row| {
-            Ok(Word {
-                id: Some(row.get(0)?),
-                spelling: row.get(1)?,
-                phonetic: row.get(2)?,
-                definition: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
-                translation: row.get(4)?,
-                pos: row.get(5)?,
-                collins: row.get::<_, Option<i32>>(6)?.unwrap_or(0),
-                oxford: row.get::<_, Option<i32>>(7)?.unwrap_or(0) > 0,
-                tag: row.get(8)?,
-                bnc: row.get(9)?,
-                frq: row.get(10)?,
-                exchange: row.get(11)?,
-                favorited: false,
-            })
-        })?;
+        let dict = self.dict_conn.borrow();
+        let mut stmt = dict.prepare(&query)?;
+        let rows = stmt.query_map(params![tag, limit], word_from_row)?;
+        let suspended = self.suspended_ids()?;
 
         let mut results = Vec::new();
         for row in rows {
-            let word = row?;
+            let mut word = row?;
+            self.apply_glossary(&mut word);
             if let Some(word_id) = word.id {
-                self.init_learning_log(word_id)?;
-                if let Ok(Some(log)) = self.get_learning_log(word_id) {
-                    results.push((word, log));
+                if suspended.contains(&word_id) {
+                    continue;
+                }
+                if enroll {
+                    self.init_learning_log(word_id)?;
                 }
+                let log = match self.get_learning_log(word_id) {
+                    Ok(Some(log)) => log,
+                    Ok(None) if !enroll => LearningLog {
+                        word_id,
+                        repetition: 0,
+                        interval: 0,
+                        e_factor: 2.5,
+                        next_review: Utc::now(),
+                        status: LearningStatus::New,
+                        consecutive_good: 0,
+                    },
+                    _ => continue,
+                };
+                results.push((word, log));
             }
         }
 
@@ -715,19 +2360,468 @@ impl Database {
         self.set_setting("daily_goal", &goal.to_string())
     }
 
+    /// 积压时是否自动减少新词（默认关闭，只在主页提示）
+    pub fn get_auto_limit_new_words(&self) -> Result<bool> {
+        Ok(self.get_setting("auto_limit_new_words")?.as_deref() == Some("on"))
+    }
+
+    pub fn set_auto_limit_new_words(&self, enabled: bool) -> Result<()> {
+        self.set_setting("auto_limit_new_words", if enabled { "on" } else { "off" })
+    }
+
+    /// 到期复习按单词本（主标签）分组连续出现
+    pub fn get_group_by_wordbook(&self) -> Result<bool> {
+        Ok(self.get_setting("group_by_wordbook")?.as_deref() == Some("on"))
+    }
+
+    pub fn set_group_by_wordbook(&self, enabled: bool) -> Result<()> {
+        self.set_setting("group_by_wordbook", if enabled { "on" } else { "off" })
+    }
+
+    pub fn get_freq_band(&self) -> Result<FreqBand> {
+        Ok(self.get_setting("freq_band")?
+            .map(|s| FreqBand::from_setting(&s))
+            .unwrap_or(FreqBand::All))
+    }
+
+    pub fn set_freq_band(&self, band: FreqBand) -> Result<()> {
+        self.set_setting("freq_band", &band.to_setting())
+    }
+
+    /// 默认开启：只有显式设为 "off" 才会选到专有名词和缩写
+    pub fn get_skip_proper_nouns(&self) -> Result<bool> {
+        Ok(self.get_setting("skip_proper_nouns")?.as_deref() != Some("off"))
+    }
+
+    pub fn set_skip_proper_nouns(&self, enabled: bool) -> Result<()> {
+        self.set_setting("skip_proper_nouns", if enabled { "on" } else { "off" })
+    }
+
+    /// 退出时在终端打印今日小结（默认开启）
+    pub fn get_exit_summary(&self) -> Result<bool> {
+        Ok(self.get_setting("exit_summary")?.as_deref() != Some("off"))
+    }
+
+    pub fn set_exit_summary(&self, enabled: bool) -> Result<()> {
+        self.set_setting("exit_summary", if enabled { "on" } else { "off" })
+    }
+
+    /// 复习中还有卡片时按 q/Esc 先确认（默认开启）
+    pub fn get_confirm_review_quit(&self) -> Result<bool> {
+        Ok(self.get_setting("confirm_review_quit")?.as_deref() != Some("off"))
+    }
+
+    pub fn set_confirm_review_quit(&self, enabled: bool) -> Result<()> {
+        self.set_setting("confirm_review_quit", if enabled { "on" } else { "off" })
+    }
+
+    /// 一轮复习做完后去哪里（默认回主页）
+    pub fn get_on_session_complete(&self) -> Result<SessionComplete> {
+        Ok(self.get_setting("on_session_complete")?
+            .map(|s| SessionComplete::from_setting(&s))
+            .unwrap_or(SessionComplete::Dashboard))
+    }
+
+    pub fn set_on_session_complete(&self, action: SessionComplete) -> Result<()> {
+        self.set_setting("on_session_complete", action.to_setting())
+    }
+
+    pub fn get_start_in_review(&self) -> Result<bool> {
+        Ok(self.get_setting("start_in_review")?.as_deref() == Some("on"))
+    }
+
+    pub fn set_start_in_review(&self, enabled: bool) -> Result<()> {
+        self.set_setting("start_in_review", if enabled { "on" } else { "off" })
+    }
+
+    pub fn get_animations(&self) -> Result<bool> {
+        Ok(self.get_setting("animations")?.as_deref() == Some("on"))
+    }
+
+    pub fn set_animations(&self, enabled: bool) -> Result<()> {
+        self.set_setting("animations", if enabled { "on" } else { "off" })
+    }
+
+    pub fn get_stats_granularity(&self) -> Result<StatsGranularity> {
+        Ok(self.get_setting("stats_granularity")?
+            .map(|s| StatsGranularity::from_setting(&s))
+            .unwrap_or(StatsGranularity::Daily))
+    }
+
+    pub fn set_stats_granularity(&self, granularity: StatsGranularity) -> Result<()> {
+        self.set_setting("stats_granularity", granularity.to_setting())
+    }
+
+    pub fn get_calendar_style(&self) -> Result<CalendarStyle> {
+        Ok(self.get_setting("calendar_style")?
+            .map(|s| CalendarStyle::from_setting(&s))
+            .unwrap_or(CalendarStyle::Checkin))
+    }
+
+    pub fn set_calendar_style(&self, style: CalendarStyle) -> Result<()> {
+        self.set_setting("calendar_style", style.to_setting())
+    }
+
+    /// 重点单词本：学新词时优先从该 tag 取词（空字符串表示未设置）
+    pub fn get_focus_tag(&self) -> Result<Option<String>> {
+        Ok(self.get_setting("focus_tag")?.filter(|tag| !tag.is_empty()))
+    }
+
+    pub fn set_focus_tag(&self, tag: Option<&str>) -> Result<()> {
+        self.set_setting("focus_tag", tag.unwrap_or(""))
+    }
+
+    /// 限时优先单词本；过了截止日期视为未设置（设置项本身留到下次设置时覆盖）
+    pub fn get_priority_wordbook(&self) -> Result<Option<PriorityWordbook>> {
+        let Some(tag) = self.get_setting("priority_wordbook")?.filter(|tag| !tag.is_empty()) else {
+            return Ok(None);
+        };
+        let until = self
+            .get_setting("priority_until")?
+            .and_then(|date| NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok());
+        let today = self.today()?;
+        Ok(until.filter(|until| *until >= today).map(|until| PriorityWordbook { tag, until }))
+    }
+
+    pub fn set_priority_wordbook(&self, priority: Option<&PriorityWordbook>) -> Result<()> {
+        match priority {
+            Some(priority) => {
+                self.set_setting("priority_wordbook", &priority.tag)?;
+                self.set_setting("priority_until", &priority.until.format("%Y-%m-%d").to_string())
+            }
+            None => self.set_setting("priority_wordbook", ""),
+        }
+    }
+
+    /// 学新词先取的单词本：生效中的限时优先单词本盖过重点单词本
+    pub fn get_new_word_tag(&self) -> Result<Option<String>> {
+        match self.get_priority_wordbook()? {
+            Some(priority) => Ok(Some(priority.tag)),
+            None => self.get_focus_tag(),
+        }
+    }
+
+    pub fn get_language(&self) -> Result<Lang> {
+        Ok(self.get_setting("language")?
+            .map(|s| Lang::from_setting(&s))
+            .unwrap_or(Lang::Zh))
+    }
+
+    pub fn set_language(&self, lang: Lang) -> Result<()> {
+        self.set_setting("language", lang.to_setting())
+    }
+
+    pub fn get_review_direction(&self) -> Result<ReviewDirection> {
+        Ok(self.get_setting("review_direction")?
+            .map(|s| ReviewDirection::from_setting(&s))
+            .unwrap_or(ReviewDirection::EnToZh))
+    }
+
+    pub fn set_review_direction(&self, direction: ReviewDirection) -> Result<()> {
+        self.set_setting("review_direction", direction.to_setting())
+    }
+
+    /// 词典表格显示的列（默认全部）
+    pub fn get_dict_columns(&self) -> Result<Vec<DictColumn>> {
+        Ok(self.get_setting("dict_columns")?
+            .map(|s| DictColumn::from_setting(&s))
+            .unwrap_or_else(|| DictColumn::ALL.to_vec()))
+    }
+
+    pub fn set_dict_columns(&self, columns: &[DictColumn]) -> Result<()> {
+        self.set_setting("dict_columns", &DictColumn::to_setting(columns))
+    }
+
+    /// 复习卡片正面（问题面）显示哪些提示，默认全部显示
+    pub fn get_card_front(&self) -> Result<Vec<CardFrontField>> {
+        Ok(self.get_setting("card_front")?
+            .map(|s| CardFrontField::from_setting(&s))
+            .unwrap_or_else(|| CardFrontField::ALL.to_vec()))
+    }
+
+    pub fn set_card_front(&self, fields: &[CardFrontField]) -> Result<()> {
+        self.set_setting("card_front", &CardFrontField::to_setting(fields))
+    }
+
+    /// 词典默认列表隐藏已掌握的单词（默认关闭，词典里按 m 临时切换）
+    pub fn get_hide_mastered_in_dict(&self) -> Result<bool> {
+        Ok(self.get_setting("hide_mastered_in_dict")?.as_deref() == Some("on"))
+    }
+
+    pub fn set_hide_mastered_in_dict(&self, enabled: bool) -> Result<()> {
+        self.set_setting("hide_mastered_in_dict", if enabled { "on" } else { "off" })
+    }
+
+    /// 离开词典时记下搜索和选中的单词，回来时恢复（默认开启）
+    pub fn get_remember_dict_position(&self) -> Result<bool> {
+        Ok(self.get_setting("remember_dict_position")?.as_deref() != Some("off"))
+    }
+
+    pub fn set_remember_dict_position(&self, enabled: bool) -> Result<()> {
+        self.set_setting("remember_dict_position", if enabled { "on" } else { "off" })
+    }
+
+    pub fn get_dict_position(&self) -> Result<Option<DictPosition>> {
+        Ok(self.get_setting("dict_position")?.and_then(|s| DictPosition::from_setting(&s)))
+    }
+
+    pub fn set_dict_position(&self, position: &DictPosition) -> Result<()> {
+        self.set_setting("dict_position", &position.to_setting())
+    }
+
+    /// 英文释义整理 ECDICT 排版残留后再显示（默认开启，只影响显示）
+    pub fn get_clean_definitions(&self) -> Result<bool> {
+        Ok(self.get_setting("clean_definitions")?.as_deref() != Some("off"))
+    }
+
+    pub fn set_clean_definitions(&self, enabled: bool) -> Result<()> {
+        self.set_setting("clean_definitions", if enabled { "on" } else { "off" })
+    }
+
+    /// 今日暂停学新词：存的是开启那天的日期，第二天自动失效
+    pub fn get_skip_new_words_today(&self) -> Result<bool> {
+        let today = self.today()?.format("%Y-%m-%d").to_string();
+        Ok(self.get_setting("skip_new_words_on")?.as_deref() == Some(today.as_str()))
+    }
+
+    pub fn set_skip_new_words_today(&self, enabled: bool) -> Result<()> {
+        let value = if enabled { self.today()?.format("%Y-%m-%d").to_string() } else { String::new() };
+        self.set_setting("skip_new_words_on", &value)
+    }
+
+    pub fn get_border_density(&self) -> Result<BorderDensity> {
+        Ok(self.get_setting("border_density")?
+            .map(|s| BorderDensity::from_setting(&s))
+            .unwrap_or(BorderDensity::Thick))
+    }
+
+    pub fn set_border_density(&self, density: BorderDensity) -> Result<()> {
+        self.set_setting("border_density", density.to_setting())
+    }
+
+    pub fn get_display_emphasis(&self) -> Result<bool> {
+        Ok(self.get_setting("display_emphasis")?.as_deref() == Some("on"))
+    }
+
+    pub fn set_display_emphasis(&self, enabled: bool) -> Result<()> {
+        self.set_setting("display_emphasis", if enabled { "on" } else { "off" })
+    }
+
+    pub fn get_dict_hl_keys(&self) -> Result<DictHlKeys> {
+        Ok(self.get_setting("dict_hl_keys")?
+            .map(|s| DictHlKeys::from_setting(&s))
+            .unwrap_or(DictHlKeys::Unbound))
+    }
+
+    pub fn set_dict_hl_keys(&self, keys: DictHlKeys) -> Result<()> {
+        self.set_setting("dict_hl_keys", keys.to_setting())
+    }
+
+    /// 设置页的高级操作（批量复习到期单词）默认隐藏在这个开关后面
+    pub fn get_advanced_actions(&self) -> Result<bool> {
+        Ok(self.get_setting("advanced_actions")?.as_deref() == Some("on"))
+    }
+
+    pub fn set_advanced_actions(&self, enabled: bool) -> Result<()> {
+        self.set_setting("advanced_actions", if enabled { "on" } else { "off" })
+    }
+
+    pub fn get_dict_enter_action(&self) -> Result<DictEnterAction> {
+        Ok(self.get_setting("dict_enter_action")?
+            .map(|s| DictEnterAction::from_setting(&s))
+            .unwrap_or(DictEnterAction::OpenDetail))
+    }
+
+    pub fn set_dict_enter_action(&self, action: DictEnterAction) -> Result<()> {
+        self.set_setting("dict_enter_action", action.to_setting())
+    }
+
+    pub fn get_search_focus_key(&self) -> Result<SearchFocusKey> {
+        Ok(self.get_setting("search_focus_key")?
+            .map(|s| SearchFocusKey::from_setting(&s))
+            .unwrap_or(SearchFocusKey::Tab))
+    }
+
+    pub fn set_search_focus_key(&self, key: SearchFocusKey) -> Result<()> {
+        self.set_setting("search_focus_key", key.to_setting())
+    }
+
+    pub fn get_mastered_review(&self) -> Result<MasteredReview> {
+        Ok(self.get_setting("mastered_review")?
+            .map(|s| MasteredReview::from_setting(&s))
+            .unwrap_or(MasteredReview::Normal))
+    }
+
+    pub fn set_mastered_review(&self, mode: MasteredReview) -> Result<()> {
+        self.set_setting("mastered_review", &mode.to_setting())
+    }
+
+    pub fn get_learning_mix(&self) -> Result<LearningMix> {
+        Ok(self.get_setting("learning_mix")?
+            .map(|s| LearningMix::from_setting(&s))
+            .unwrap_or(LearningMix::Off))
+    }
+
+    pub fn set_learning_mix(&self, mix: LearningMix) -> Result<()> {
+        self.set_setting("learning_mix", &mix.to_setting())
+    }
+
+    /// 复习开头先放几个熟词热身（0 = 关闭）
+    pub fn get_warmup_count(&self) -> Result<i64> {
+        Ok(self.get_setting("warmup_count")?
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0))
+    }
+
+    pub fn set_warmup_count(&self, count: i64) -> Result<()> {
+        self.set_setting("warmup_count", &count.to_string())
+    }
+
+    /// 忘记超过这么多次的单词自动收藏（0 = 关闭）
+    pub fn get_auto_favorite_lapses(&self) -> Result<i64> {
+        Ok(self.get_setting("auto_favorite_lapses")?
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0))
+    }
+
+    pub fn set_auto_favorite_lapses(&self, lapses: i64) -> Result<()> {
+        self.set_setting("auto_favorite_lapses", &lapses.to_string())
+    }
+
+    /// 几点换成新的一天（0 = 午夜）；今日复习数、打卡、连续天数和日历都按这个学习日算
+    pub fn get_day_rollover_hour(&self) -> Result<u32> {
+        Ok(self.get_setting("day_rollover_hour")?
+            .and_then(|s| s.parse().ok())
+            .filter(|h| *h < 24)
+            .unwrap_or(0))
+    }
+
+    pub fn set_day_rollover_hour(&self, hour: u32) -> Result<()> {
+        self.set_setting("day_rollover_hour", &hour.to_string())
+    }
+
+    /// 当前学习日（见 `effective_date`）
+    pub fn today(&self) -> Result<NaiveDate> {
+        Ok(effective_date(chrono::Local::now(), self.get_day_rollover_hour()?))
+    }
+
+    /// SQLite date modifiers turning a UTC timestamp into its study day, for
+    /// `DATE(column, ...)` and `DATE('now', ...)`; same boundary as `today`
+    fn day_modifiers(&self) -> Result<String> {
+        Ok(format!("'localtime', '-{} hours'", self.get_day_rollover_hour()?))
+    }
+
+    /// 到期后多少小时才算逾期（0 = 一到期就算）
+    pub fn get_overdue_grace_hours(&self) -> Result<i64> {
+        Ok(self.get_setting("overdue_grace_hours")?
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0))
+    }
+
+    pub fn set_overdue_grace_hours(&self, hours: i64) -> Result<()> {
+        self.set_setting("overdue_grace_hours", &hours.to_string())
+    }
+
+    /// 每次到期复习最多几个（0 = 不限）；剩下的仍然到期，下次按 r 接着复习
+    pub fn get_session_cap(&self) -> Result<i64> {
+        Ok(self.get_setting("session_cap")?
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0))
+    }
+
+    pub fn set_session_cap(&self, cap: i64) -> Result<()> {
+        self.set_setting("session_cap", &cap.to_string())
+    }
+
+    /// 学习提醒时间（整点，0-23）；到点后今天还没复习，主页提示一句
+    pub fn get_reminder_hour(&self) -> Result<Option<u32>> {
+        Ok(self.get_setting("reminder_hour")?
+            .and_then(|s| s.parse().ok())
+            .filter(|h| *h < 24))
+    }
+
+    pub fn set_reminder_hour(&self, hour: Option<u32>) -> Result<()> {
+        match hour {
+            Some(h) => self.set_setting("reminder_hour", &h.to_string()),
+            None => self.set_setting("reminder_hour", "off"),
+        }
+    }
+
+    /// 自动掌握的附加条件（除间隔超过 21 天外）
+    pub fn get_mastery_rule(&self) -> Result<MasteryRule> {
+        let get = |key| -> Result<i32> {
+            Ok(self.get_setting(key)?.and_then(|s| s.parse().ok()).unwrap_or(0))
+        };
+        Ok(MasteryRule {
+            min_repetition: get("mastery_min_repetition")?,
+            consecutive_good: get("mastery_consecutive_good")?,
+        })
+    }
+
+    pub fn set_mastery_rule(&self, rule: MasteryRule) -> Result<()> {
+        self.set_setting("mastery_min_repetition", &rule.min_repetition.to_string())?;
+        self.set_setting("mastery_consecutive_good", &rule.consecutive_good.to_string())
+    }
+
+    /// 个人词汇表 JSON 文件路径（未设置为 None）
+    pub fn get_glossary_path(&self) -> Result<Option<String>> {
+        Ok(self.get_setting("glossary_path")?.filter(|path| !path.trim().is_empty()))
+    }
+
+    /// Empty `path` turns the glossary off
+    pub fn set_glossary_path(&self, path: &str) -> Result<()> {
+        self.set_setting("glossary_path", path.trim())
+    }
+
+    pub fn get_mix_order(&self) -> Result<MixOrder> {
+        Ok(self.get_setting("mix_order")?
+            .map(|s| MixOrder::from_setting(&s))
+            .unwrap_or(MixOrder::ReviewsFirst))
+    }
+
+    pub fn set_mix_order(&self, order: MixOrder) -> Result<()> {
+        self.set_setting("mix_order", order.to_setting())
+    }
+
     // Daily checkin methods
-    pub fn update_daily_checkin(&self) -> Result<()> {
-        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+
+    /// 今日复习数达到每日目标时写入打卡记录，返回今天是否已打卡
+    ///
+    /// Days with reviews below the goal get no row; the calendar shows them
+    /// separately via `get_active_dates`.
+    pub fn record_checkin_if_goal_met(&self) -> Result<bool> {
+        let today = self.today()?.format("%Y-%m-%d").to_string();
         let completed = self.get_today_completed_count()?;
         let goal = self.get_daily_goal()?;
-        let achieved = if completed >= goal { 1 } else { 0 };
+        if completed < goal {
+            return Ok(false);
+        }
 
         self.learn_conn.execute(
             "INSERT OR REPLACE INTO daily_checkin (date, completed_count, goal, achieved) 
-             VALUES (?1, ?2, ?3, ?4)",
-            params![today, completed, goal, achieved],
+             VALUES (?1, ?2, ?3, 1)",
+            params![today, completed, goal],
         )?;
-        Ok(())
+        Ok(true)
+    }
+
+    /// 连续达成每日目标的天数；今天尚未达成时从昨天开始算，不打断连续记录
+    pub fn get_checkin_streak(&self) -> Result<i64> {
+        let mut stmt = self.learn_conn.prepare("SELECT date FROM daily_checkin WHERE achieved = 1")?;
+        let achieved: HashSet<String> = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+
+        let mut day = self.today()?;
+        if !achieved.contains(&day.format("%Y-%m-%d").to_string()) {
+            day -= chrono::Duration::days(1);
+        }
+        let mut streak = 0;
+        while achieved.contains(&day.format("%Y-%m-%d").to_string()) {
+            streak += 1;
+            day -= chrono::Duration::days(1);
+        }
+        Ok(streak)
     }
 
     pub fn get_checkin_dates(&self, year: i32, month: u32) -> Result<Vec<String>> {
@@ -751,6 +2845,32 @@ impl Database {
         Ok(dates)
     }
 
+    /// Days of the month with at least one review, whether or not the goal was met
+    pub fn get_active_dates(&self, year: i32, month: u32) -> Result<Vec<String>> {
+        let study_day = self.day_modifiers()?;
+        let start_date = format!("{:04}-{:02}-01", year, month);
+        let end_date = if month == 12 {
+            format!("{:04}-01-01", year + 1)
+        } else {
+            format!("{:04}-{:02}-01", year, month + 1)
+        };
+
+        let mut stmt = self.learn_conn.prepare(
+            &format!("SELECT DATE(reviewed_at, {study_day}) AS day FROM review_history
+             WHERE day >= ?1 AND day < ?2
+             UNION
+             SELECT day FROM history_daily_summary
+             WHERE day >= ?1 AND day < ?2")
+        )?;
+
+        let dates = stmt.query_map(params![start_date, end_date], |row| {
+            row.get::<_, String>(0)
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(dates)
+    }
+
     // Favorites methods
     pub fn toggle_favorite(&self, word_id: i64) -> Result<bool> {
         let is_fav = self.is_favorited(word_id)?;
@@ -799,6 +2919,156 @@ impl Database {
         Ok(words)
     }
 
+    /// 导出收藏为纯文本：每行一个单词，最早收藏的在前（新收藏追加在末尾，方便 git diff）
+    pub fn export_favorites_txt(&self, path: &Path) -> Result<usize> {
+        let mut words = self.get_favorites()?;
+        words.reverse();
+        let mut text = String::new();
+        for word in &words {
+            text.push_str(&word.spelling);
+            text.push('\n');
+        }
+        std::fs::write(path, text)?;
+        Ok(words.len())
+    }
+
+    /// 导出统计页图表背后的数字（`export --stats-csv`）：`path` 写遗忘曲线（按间隔），
+    /// 同目录的 `<名字>_daily.csv` 写最近 `days` 天每天的复习数；返回 (两个文件的行数, 每日文件路径)
+    pub fn export_stats_csv(&self, path: &Path, days: i64) -> Result<(usize, usize, PathBuf)> {
+        let by_interval = self.get_review_stats_by_interval()?;
+        let mut text = String::from("interval_days_after_review,avg_quality_1_forgot_to_4_easy,review_count\n");
+        for (interval, avg_quality, count) in &by_interval {
+            text.push_str(&format!("{},{:.3},{}\n", interval, avg_quality, count));
+        }
+        std::fs::write(path, text).with_context(|| format!("cannot write {}", path.display()))?;
+
+        let daily = self.get_daily_review_counts(days)?;
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("stats");
+        let daily_path = path.with_file_name(format!("{}_daily.csv", stem));
+        let mut text = String::from("date_local,review_count\n");
+        for (date, count) in &daily {
+            text.push_str(&format!("{},{}\n", date, count));
+        }
+        std::fs::write(&daily_path, text).with_context(|| format!("cannot write {}", daily_path.display()))?;
+        Ok((by_interval.len(), daily.len(), daily_path))
+    }
+
+    /// 从纯文本导入收藏（每行一个单词，空行和 # 注释行跳过）；已收藏的保持不变
+    pub fn import_favorites_txt(&self, path: &Path) -> Result<FavoritesImport> {
+        let text = std::fs::read_to_string(path)?;
+        let mut summary = FavoritesImport::default();
+        for line in text.lines() {
+            let spelling = line.trim();
+            if spelling.is_empty() || spelling.starts_with('#') {
+                continue;
+            }
+            let Some(word_id) = self.find_word_by_spelling(spelling)?.and_then(|w| w.id) else {
+                summary.not_found.push(spelling.to_string());
+                continue;
+            };
+            if self.is_favorited(word_id)? {
+                summary.already += 1;
+            } else {
+                self.toggle_favorite(word_id)?;
+                summary.added += 1;
+            }
+        }
+        Ok(summary)
+    }
+
+    /// (spelling, timestamp) of every row in `favorites` / `suspended`, oldest first
+    fn user_data_words(&self, table: &str, time_column: &str) -> Result<Vec<UserDataWord>> {
+        let mut stmt = self.learn_conn.prepare(&format!(
+            "SELECT word_id, {time_column} FROM {table} ORDER BY {time_column}, word_id"
+        ))?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|(word_id, at)| {
+                let word = self.get_word_by_id(word_id).ok()?.spelling;
+                Some(UserDataWord { word, at })
+            })
+            .collect())
+    }
+
+    /// 导出用户数据备份（收藏、暂停和设置）为 JSON；学习进度不在里面，完整备份请复制数据库。
+    /// 设置只导出 BACKUP_SETTINGS 里的偏好
+    pub fn export_user_data_json(&self, path: &Path) -> Result<UserDataBackup> {
+        let mut stmt = self.learn_conn.prepare("SELECT key, value FROM settings")?;
+        let settings = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?
+            .into_iter()
+            .filter(|(key, _)| BACKUP_SETTINGS.contains(&key.as_str()))
+            .collect();
+        let backup = UserDataBackup {
+            version: USER_DATA_VERSION,
+            favorites: self.user_data_words("favorites", "added_at")?,
+            suspended: self.user_data_words("suspended", "suspended_at")?,
+            settings,
+        };
+        std::fs::write(path, serde_json::to_string_pretty(&backup)? + "\n")?;
+        Ok(backup)
+    }
+
+    /// 合并导入用户数据备份：只添加本机没有的收藏和暂停（已有的保留原时间），
+    /// 设置以备份里的值为准（旧备份里 BACKUP_SETTINGS 以外的键跳过）；整个导入在一个事务里
+    pub fn import_user_data_json(&self, path: &Path) -> Result<UserDataImport> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("cannot read user data backup {}", path.display()))?;
+        let backup: UserDataBackup = serde_json::from_str(&text)
+            .with_context(|| format!("invalid user data JSON in {}", path.display()))?;
+        if backup.version > USER_DATA_VERSION {
+            anyhow::bail!(
+                "{} was written by a newer LexRain (format version {}, this build reads up to {})",
+                path.display(),
+                backup.version,
+                USER_DATA_VERSION
+            );
+        }
+
+        let mut summary = UserDataImport::default();
+        let mut resolve = |spelling: &str| -> Result<Option<i64>> {
+            let word_id = self.find_dict_id_by_spelling(spelling)?;
+            if word_id.is_none() && !summary.not_found.iter().any(|s| s == spelling) {
+                summary.not_found.push(spelling.to_string());
+            }
+            Ok(word_id)
+        };
+        let mut favorites = Vec::new();
+        for entry in &backup.favorites {
+            if let Some(word_id) = resolve(&entry.word)? {
+                favorites.push((word_id, &entry.at));
+            }
+        }
+        let mut suspended = Vec::new();
+        for entry in &backup.suspended {
+            if let Some(word_id) = resolve(&entry.word)? {
+                suspended.push((word_id, &entry.at));
+            }
+        }
+
+        let tx = self.learn_conn.unchecked_transaction()?;
+        {
+            let mut stmt = tx.prepare("INSERT OR IGNORE INTO favorites (word_id, added_at) VALUES (?1, ?2)")?;
+            for (word_id, at) in favorites {
+                summary.favorites_added += stmt.execute(params![word_id, at])?;
+            }
+            let mut stmt = tx.prepare("INSERT OR IGNORE INTO suspended (word_id, suspended_at) VALUES (?1, ?2)")?;
+            for (word_id, at) in suspended {
+                summary.suspended_added += stmt.execute(params![word_id, at])?;
+            }
+            let mut stmt = tx.prepare("INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)")?;
+            for (key, value) in backup.settings.iter().filter(|(key, _)| BACKUP_SETTINGS.contains(&key.as_str())) {
+                summary.settings_applied += stmt.execute(params![key, value])?;
+            }
+        }
+        tx.commit()?;
+        Ok(summary)
+    }
+
     pub fn get_favorites_count(&self) -> Result<i64> {
         let count: i64 = self.learn_conn.query_row(
             "SELECT COUNT(*) FROM favorites",
@@ -807,4 +3077,588 @@ impl Database {
         )?;
         Ok(count)
     }
+
+    // Achievements methods
+
+    /// 已解锁的成就，按解锁时间排序（未知 id 忽略）
+    pub fn get_achievements(&self) -> Result<Vec<Achievement>> {
+        let mut stmt = self.learn_conn.prepare("SELECT id FROM achievements ORDER BY unlocked_at")?;
+        let ids = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(ids.iter().filter_map(|id| Achievement::from_id(id)).collect())
+    }
+
+    /// 对比当前数据和成就门槛，写入新达成的成就并返回它们
+    pub fn check_achievements(&self) -> Result<Vec<Achievement>> {
+        let unlocked = self.get_achievements()?;
+        let pending: Vec<Achievement> =
+            Achievement::ALL.into_iter().filter(|a| !unlocked.contains(a)).collect();
+        if pending.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let progress = AchievementProgress {
+            words: self.learn_conn.query_row("SELECT COUNT(*) FROM learning_log WHERE status != 0", [], |r| r.get(0))?,
+            streak: self.get_checkin_streak()?,
+            reviews: self.learn_conn.query_row(
+                "SELECT (SELECT COUNT(*) FROM review_history)
+                      + (SELECT COALESCE(SUM(reviews), 0) FROM history_daily_summary)",
+                [],
+                |r| r.get(0),
+            )?,
+            // 要扫一遍 ECDICT，已解锁后就不再算
+            wordbook_mastered: pending.contains(&Achievement::WordbookMastered)
+                && self.any_wordbook_mastered()?,
+        };
+
+        let now = Utc::now().to_rfc3339();
+        let mut newly = Vec::new();
+        for achievement in pending {
+            if achievement.reached(&progress) {
+                self.learn_conn.execute(
+                    "INSERT OR IGNORE INTO achievements (id, unlocked_at) VALUES (?1, ?2)",
+                    params![achievement.id(), now],
+                )?;
+                newly.push(achievement);
+            }
+        }
+        Ok(newly)
+    }
+
+    /// 是否有某个单词本（与 get_wordbooks 同样的计数口径）已全部掌握
+    fn any_wordbook_mastered(&self) -> Result<bool> {
+        let mut stmt = self.learn_conn.prepare("SELECT word_id FROM learning_log WHERE status = ?1")?;
+        let mastered: HashSet<i64> = stmt
+            .query_map(params![i32::from(LearningStatus::Mastered)], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        if mastered.is_empty() {
+            return Ok(false);
+        }
+
+        // tag -> (total, mastered)
+        let mut counts: HashMap<String, (usize, usize)> = HashMap::new();
+        let dict = self.dict_conn.borrow();
+        let mut stmt = dict.prepare(&format!(
+            "SELECT {} FROM stardict WHERE tag IS NOT NULL AND tag != '' AND translation IS NOT NULL",
+            self.dict_select("id, tag")
+        ))?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?;
+        for row in rows {
+            let (id, tag_string) = row?;
+            let is_mastered = mastered.contains(&id);
+            for tag in split_tags(&tag_string) {
+                let entry = counts.entry(tag.to_string()).or_insert((0, 0));
+                entry.0 += 1;
+                if is_mastered {
+                    entry.1 += 1;
+                }
+            }
+        }
+        Ok(counts.values().any(|&(total, done)| total > 0 && done == total))
+    }
 }
+
+#[cfg(test)]
+impl Database {
+    /// Both databases in memory, `stardict` created with `create_stardict`
+    pub fn in_memory_with_dict(create_stardict: &str) -> Result<Self> {
+        let dict_conn = Connection::open_in_memory()?;
+        dict_conn.execute(create_stardict, [])?;
+        Self::setup(dict_conn, Connection::open_in_memory()?)
+    }
+
+    /// Both databases in memory with the full ECDICT `stardict` schema
+    pub fn in_memory() -> Result<Self> {
+        Self::in_memory_with_dict(
+            "CREATE TABLE stardict (
+                id INTEGER PRIMARY KEY, word TEXT COLLATE NOCASE, sw TEXT, phonetic TEXT,
+                definition TEXT, translation TEXT, pos TEXT, collins INTEGER, oxford INTEGER,
+                tag TEXT, bnc INTEGER, frq INTEGER, exchange TEXT, detail TEXT, audio TEXT
+            )",
+        )
+    }
+
+    /// Add a dictionary word with a translation, returning its id
+    pub fn add_test_word(&self, word: &str, tag: &str, oxford: i32, collins: i32) -> Result<i64> {
+        let dict = self.dict_conn.borrow();
+        dict.execute(
+            "INSERT INTO stardict (word, translation, tag, oxford, collins) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![word, format!("n. {}", word), tag, oxford, collins],
+        )?;
+        Ok(dict.last_insert_rowid())
+    }
+
+    /// Learning words (tag "cet4") that fell due the given number of hours ago
+    pub fn with_due_words(words: &[(&str, i64)]) -> Result<Self> {
+        let db = Self::in_memory()?;
+        for (word, hours) in words {
+            let id = db.add_test_word(word, "cet4", 0, 0)?;
+            db.init_learning_log(id)?;
+            let next_review = Utc::now() - chrono::Duration::hours(*hours);
+            db.learn_conn.execute(
+                "UPDATE learning_log SET next_review = ?1, repetition = 1, interval = 1, status = 1 WHERE word_id = ?2",
+                params![next_review.to_rfc3339(), id],
+            )?;
+        }
+        Ok(db)
+    }
+
+    /// Raw access to the progress database, for setting up fixtures
+    pub fn learn_conn(&self) -> &Connection {
+        &self.learn_conn
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, FixedOffset, NaiveDateTime, TimeZone};
+
+    fn local(date: &str, time: &str) -> DateTime<FixedOffset> {
+        let naive = NaiveDateTime::parse_from_str(&format!("{date} {time}"), "%Y-%m-%d %H:%M").unwrap();
+        FixedOffset::east_opt(8 * 3600).unwrap().from_local_datetime(&naive).unwrap()
+    }
+
+    fn day(date: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn effective_date_rolls_over_at_the_hour() {
+        assert_eq!(effective_date(local("2026-10-15", "03:59"), 4), day("2026-10-14"));
+        assert_eq!(effective_date(local("2026-10-15", "04:00"), 4), day("2026-10-15"));
+    }
+
+    #[test]
+    fn effective_date_without_rollover_is_the_calendar_date() {
+        assert_eq!(effective_date(local("2026-10-15", "00:30"), 0), day("2026-10-15"));
+        assert_eq!(effective_date(local("2026-10-14", "23:59"), 0), day("2026-10-14"));
+    }
+
+    #[test]
+    fn effective_date_uses_the_wall_clock_across_dst() {
+        // US Eastern on 2026-03-08: 02:00 EST (UTC-5) jumps to 03:00 EDT (UTC-4)
+        let at = |time: &str, utc_hours: i32| {
+            let naive = NaiveDateTime::parse_from_str(&format!("2026-03-08 {time}"), "%Y-%m-%d %H:%M").unwrap();
+            FixedOffset::east_opt(utc_hours * 3600).unwrap().from_local_datetime(&naive).unwrap()
+        };
+        // Only three real hours after midnight, but the clock says 04:30: a new study day
+        assert_eq!(effective_date(at("04:30", -4), 4), day("2026-03-08"));
+        assert_eq!(effective_date(at("03:30", -4), 4), day("2026-03-07"));
+        assert_eq!(effective_date(at("01:30", -5), 4), day("2026-03-07"));
+        assert_eq!((at("04:30", -4) - at("00:30", -5)).num_hours(), 3);
+    }
+
+    /// A progress file on disk with the current schema, for merge_progress
+    fn progress_file(name: &str) -> (std::path::PathBuf, Database) {
+        let path = std::env::temp_dir().join(format!("lexrain-test-{}-{}.db", std::process::id(), name));
+        let _ = std::fs::remove_file(&path);
+        let db = Database::setup(Connection::open_in_memory().unwrap(), Connection::open(&path).unwrap()).unwrap();
+        (path, db)
+    }
+
+    #[test]
+    fn merge_takes_streak_lapses_and_dates_with_the_winning_schedule() {
+        let local = Database::in_memory().unwrap();
+        local
+            .learn_conn()
+            .execute(
+                "INSERT INTO learning_log VALUES (1, 1, 1, 2.5, '2026-01-02T00:00:00+00:00', 1,
+                     '2026-01-01T00:00:00+00:00', NULL, 1, 0)",
+                [],
+            )
+            .unwrap();
+
+        let (path, other) = progress_file("merge");
+        other
+            .learn_conn()
+            .execute(
+                "INSERT INTO learning_log VALUES (1, 6, 40, 2.6, '2026-03-01T00:00:00+00:00', 2,
+                     '2025-12-01T00:00:00+00:00', '2026-01-20T00:00:00+00:00', 6, 3)",
+                [],
+            )
+            .unwrap();
+        other
+            .learn_conn()
+            .execute(
+                "INSERT INTO review_history
+                 (word_id, reviewed_at, quality, repetition, interval, e_factor, response_ms, direction, mode, first_try)
+                 VALUES (1, '2026-01-20T00:00:00+00:00', 4, 6, 40, 2.6, 1500, 'zh_to_en', 'due', 0)",
+                [],
+            )
+            .unwrap();
+        drop(other);
+
+        let summary = local.merge_progress(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!((summary.merged, summary.history_added), (1, 1));
+
+        let merged: (i32, i32, i64, Option<String>, Option<String>) = local
+            .learn_conn()
+            .query_row(
+                "SELECT status, consecutive_good, lapses, added_at, mastered_at FROM learning_log WHERE word_id = 1",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)),
+            )
+            .unwrap();
+        assert_eq!(
+            merged,
+            (
+                2,
+                6,
+                3,
+                Some("2025-12-01T00:00:00+00:00".to_string()),
+                Some("2026-01-20T00:00:00+00:00".to_string())
+            )
+        );
+
+        let history: (Option<i64>, String, String, i64) = local
+            .learn_conn()
+            .query_row("SELECT response_ms, direction, mode, first_try FROM review_history", [], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+            })
+            .unwrap();
+        assert_eq!(history, (Some(1500), "zh_to_en".to_string(), "due".to_string(), 0));
+    }
+
+    #[test]
+    fn merge_fills_columns_an_older_file_lacks() {
+        let path = std::env::temp_dir().join(format!("lexrain-test-{}-old.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let old = Connection::open(&path).unwrap();
+        old.execute_batch(
+            "CREATE TABLE learning_log (word_id INTEGER PRIMARY KEY, repetition INTEGER, interval INTEGER,
+                 e_factor REAL, next_review TEXT, status INTEGER);
+             CREATE TABLE review_history (id INTEGER PRIMARY KEY, word_id INTEGER, reviewed_at TEXT,
+                 quality INTEGER, repetition INTEGER, interval INTEGER, e_factor REAL);
+             INSERT INTO learning_log VALUES (1, 3, 6, 2.5, '2026-03-01T00:00:00+00:00', 1);
+             INSERT INTO review_history VALUES (1, 1, '2026-01-01T00:00:00+00:00', 1, 0, 1, 2.3);",
+        )
+        .unwrap();
+        drop(old);
+
+        let local = Database::in_memory().unwrap();
+        local.merge_progress(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        let merged: (i32, i64) = local
+            .learn_conn()
+            .query_row("SELECT consecutive_good, lapses FROM learning_log WHERE word_id = 1", [], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .unwrap();
+        assert_eq!(merged, (3, 1));
+        let history: (String, i64) = local
+            .learn_conn()
+            .query_row("SELECT direction, first_try FROM review_history", [], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap();
+        assert_eq!(history, ("en_to_zh".to_string(), 1));
+    }
+
+    fn new_word_spellings(db: &Database) -> Vec<String> {
+        db.get_new_words_to_learn(100).unwrap().into_iter().map(|(w, _)| w.spelling).collect()
+    }
+
+    /// Dictionary words in priority order (descending Collins stars)
+    fn add_ranked_words(db: &Database, words: &[&str]) {
+        for (i, word) in words.iter().enumerate() {
+            db.add_test_word(word, "", 0, (words.len() - i) as i32).unwrap();
+        }
+    }
+
+    #[test]
+    fn new_words_skip_proper_nouns_and_abbreviations() {
+        let db = Database::in_memory().unwrap();
+        add_ranked_words(&db, &["London", "Polish", "NASA", "e.g.", "apple", "polish"]);
+        // Polish is kept because the dictionary also has the lowercase word
+        assert_eq!(new_word_spellings(&db), ["Polish", "apple", "polish"]);
+        // The next call returns the same queued words once each, in the same order
+        assert_eq!(new_word_spellings(&db), ["Polish", "apple", "polish"]);
+    }
+
+    #[test]
+    fn new_words_keep_proper_nouns_when_the_filter_is_off() {
+        let db = Database::in_memory().unwrap();
+        db.set_skip_proper_nouns(false).unwrap();
+        add_ranked_words(&db, &["London", "NASA", "e.g.", "apple"]);
+        assert_eq!(new_word_spellings(&db), ["London", "NASA", "e.g.", "apple"]);
+    }
+
+    #[test]
+    fn slimmed_dictionary_selects_missing_columns_as_null() {
+        let db = Database::in_memory_with_dict(
+            "CREATE TABLE stardict (
+                id INTEGER PRIMARY KEY, word TEXT COLLATE NOCASE, phonetic TEXT, definition TEXT,
+                translation TEXT, pos TEXT, collins INTEGER, oxford INTEGER, tag TEXT, bnc INTEGER
+            )",
+        )
+        .unwrap();
+        assert_eq!(db.missing_dict_columns, ["frq", "exchange"]);
+        assert!(db.word_columns.contains("NULL AS frq"));
+        assert!(db.word_columns.contains("NULL AS exchange"));
+        assert!(db.word_columns.contains("bnc"));
+        // Dropping optional or unused columns does not make the build look older
+        assert_eq!(db.dict_version().age(ECDICT_VERSION), std::cmp::Ordering::Equal);
+
+        let id = db.add_test_word("apple", "cet4", 1, 3).unwrap();
+        let word = db.find_word_by_spelling("apple").unwrap().unwrap();
+        assert_eq!((word.id, word.frq, word.exchange), (Some(id), None, None));
+
+        // The aliases also work in WHERE / ORDER BY (frequency band, new-word order)
+        db.set_freq_band(FreqBand::Band(5000, None)).unwrap();
+        let new_words = db.get_new_words_to_learn(10).unwrap();
+        assert_eq!(new_words[0].0.spelling, "apple");
+    }
+
+    #[test]
+    fn stats_csv_rows_match_the_stats_queries() {
+        let db = Database::in_memory().unwrap();
+        let now = Utc::now();
+        for (days_ago, quality, interval) in [(0, 4, 1), (0, 1, 6), (2, 3, 6), (2, 4, 15), (40, 2, 1)] {
+            db.learn_conn
+                .execute(
+                    "INSERT INTO review_history (word_id, reviewed_at, quality, repetition, interval, e_factor)
+                     VALUES (1, ?1, ?2, 1, ?3, 2.5)",
+                    params![(now - Duration::days(days_ago)).to_rfc3339(), quality, interval],
+                )
+                .unwrap();
+        }
+
+        let path = std::env::temp_dir().join(format!("lexrain-test-{}-stats.csv", std::process::id()));
+        let (interval_rows, daily_rows, daily_path) = db.export_stats_csv(&path, 7).unwrap();
+        let interval_csv = std::fs::read_to_string(&path).unwrap();
+        let daily_csv = std::fs::read_to_string(&daily_path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&daily_path);
+
+        let by_interval = db.get_review_stats_by_interval().unwrap();
+        assert_eq!(interval_rows, by_interval.len());
+        let mut lines = interval_csv.lines();
+        assert_eq!(lines.next(), Some("interval_days_after_review,avg_quality_1_forgot_to_4_easy,review_count"));
+        let rows: Vec<_> = lines.collect();
+        assert_eq!(rows, ["1,3.000,2", "6,2.000,2", "15,4.000,1"]);
+        for (row, (interval, avg_quality, count)) in rows.iter().zip(&by_interval) {
+            assert_eq!(*row, format!("{},{:.3},{}", interval, avg_quality, count));
+        }
+
+        // The review 40 days ago is outside the 7-day window
+        let daily = db.get_daily_review_counts(7).unwrap();
+        assert_eq!(daily_path.file_name().unwrap(), format!("lexrain-test-{}-stats_daily.csv", std::process::id()).as_str());
+        assert_eq!(daily_rows, daily.len());
+        assert_eq!(daily.iter().map(|(_, count)| count).sum::<i64>(), 4);
+        let mut lines = daily_csv.lines();
+        assert_eq!(lines.next(), Some("date_local,review_count"));
+        let rows: Vec<_> = lines.collect();
+        assert_eq!(rows, daily.iter().map(|(date, count)| format!("{},{}", date, count)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn pruning_history_leaves_added_and_mastered_counts_alone() {
+        let db = Database::in_memory().unwrap();
+        let days_ago = |days: i64| (Utc::now() - Duration::days(days)).to_rfc3339();
+        // (added, mastered, status, history as (days ago, interval))
+        let words = [
+            // Learned and mastered long ago; only its latest review survives the prune
+            (200, Some(120), 2, vec![(200, 1), (120, 25), (10, 40)]),
+            (5, None, 1, vec![(5, 1)]),
+        ];
+        for (i, (added, mastered, status, history)) in words.into_iter().enumerate() {
+            let word_id = db.add_test_word(&format!("word{i}"), "", 0, 0).unwrap();
+            db.learn_conn
+                .execute(
+                    "INSERT INTO learning_log (word_id, repetition, interval, e_factor, next_review, status, added_at, mastered_at)
+                     VALUES (?1, 1, 1, 2.5, ?2, ?3, ?4, ?5)",
+                    params![word_id, days_ago(-1), status, days_ago(added), mastered.map(days_ago)],
+                )
+                .unwrap();
+            for (reviewed, interval) in history {
+                db.learn_conn
+                    .execute(
+                        "INSERT INTO review_history (word_id, reviewed_at, quality, repetition, interval, e_factor)
+                         VALUES (?1, ?2, 4, 1, ?3, 2.5)",
+                        params![word_id, days_ago(reviewed), interval],
+                    )
+                    .unwrap();
+            }
+        }
+        // A word only queued for learning is not counted as added
+        let queued = db.add_test_word("queued", "", 0, 0).unwrap();
+        db.init_learning_log(queued).unwrap();
+
+        let counts = |db: &Database| {
+            let added = db.get_words_added_by_day(30).unwrap();
+            let mastered = db.get_words_mastered_by_day(30).unwrap();
+            (added.iter().sum::<u64>(), mastered.iter().sum::<u64>(), added, mastered)
+        };
+        let before = counts(&db);
+        assert_eq!((before.0, before.1), (1, 0));
+        assert_eq!(db.prune_history(MIN_PRUNE_KEEP_DAYS).unwrap(), 2);
+        assert_eq!(counts(&db), before);
+    }
+
+    #[test]
+    fn two_direction_card_counts_once_towards_today() {
+        let (path, db) = progress_file("both-count");
+        let (both, single) = (1, 2);
+        for word_id in [both, single] {
+            db.init_learning_log(word_id).unwrap();
+        }
+        let log = db.get_learning_log(both).unwrap().unwrap();
+        db.add_both_review_history(both, [(4, 1200), (4, 2500)], &log, "due").unwrap();
+        assert_eq!(db.get_today_completed_count().unwrap(), 1);
+        let log = db.get_learning_log(single).unwrap().unwrap();
+        db.add_review_history(single, 3, &log, None, Some(ReviewDirection::ZhToEn), "due").unwrap();
+        assert_eq!(db.get_today_completed_count().unwrap(), 2);
+        let daily = db.get_daily_review_counts(1).unwrap();
+        assert_eq!(daily.iter().map(|(_, count)| count).sum::<i64>(), 2);
+
+        // Both passes survive a sync even with the same rating
+        let other = Database::in_memory().unwrap();
+        let summary = other.merge_progress(&path).unwrap();
+        drop(db);
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(summary.history_added, 3);
+        assert_eq!(other.get_today_completed_count().unwrap(), 2);
+    }
+
+    #[test]
+    fn user_data_round_trip_keeps_case_and_only_preferences() {
+        let source = Database::in_memory().unwrap();
+        source.add_test_word("polish", "", 0, 0).unwrap();
+        let polish = source.add_test_word("Polish", "", 0, 0).unwrap();
+        let apple = source.add_test_word("apple", "", 0, 0).unwrap();
+        source.toggle_favorite(polish).unwrap();
+        source
+            .learn_conn
+            .execute("INSERT INTO suspended (word_id, suspended_at) VALUES (?1, '2026-01-01T00:00:00+00:00')", params![apple])
+            .unwrap();
+        source.set_daily_goal(35).unwrap();
+        source.set_glossary_path("/home/someone/glossary.csv").unwrap();
+        source.set_skip_new_words_today(true).unwrap();
+
+        let path = std::env::temp_dir().join(format!("lexrain-test-{}-user-data.json", std::process::id()));
+        let mut backup = source.export_user_data_json(&path).unwrap();
+        assert_eq!(backup.settings.get("daily_goal").map(String::as_str), Some("35"));
+        assert!(!backup.settings.contains_key("glossary_path"));
+        assert!(!backup.settings.contains_key("skip_new_words_on"));
+
+        // A backup from before the allowlist carries every key: those are skipped on import
+        backup.settings.insert("glossary_path".to_string(), "/elsewhere.csv".to_string());
+        std::fs::write(&path, serde_json::to_string(&backup).unwrap()).unwrap();
+
+        // Lowercase entry first, so a case-insensitive lookup would pick it
+        let target = Database::in_memory().unwrap();
+        let target_lower = target.add_test_word("polish", "", 0, 0).unwrap();
+        let target_polish = target.add_test_word("Polish", "", 0, 0).unwrap();
+        let target_apple = target.add_test_word("apple", "", 0, 0).unwrap();
+        let summary = target.import_user_data_json(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!((summary.favorites_added, summary.suspended_added), (1, 1));
+        assert!(summary.not_found.is_empty());
+        assert!(target.is_favorited(target_polish).unwrap());
+        assert!(!target.is_favorited(target_lower).unwrap());
+        let suspended: i64 = target
+            .learn_conn
+            .query_row("SELECT word_id FROM suspended", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(suspended, target_apple);
+        assert_eq!(target.get_daily_goal().unwrap(), 35);
+        assert_eq!(target.get_glossary_path().unwrap(), None);
+        assert!(!target.get_skip_new_words_today().unwrap());
+    }
+
+    #[test]
+    fn dict_version_marker_reads_the_last_number() {
+        let conn = Connection::open_in_memory().unwrap();
+        assert_eq!(read_dict_version_marker(&conn), None);
+        conn.execute("CREATE TABLE metadata (key TEXT PRIMARY KEY, value TEXT)", []).unwrap();
+        assert_eq!(read_dict_version_marker(&conn), None);
+        for (value, expected) in [("ecdict-sqlite-28", Some(28)), ("27", Some(27)), ("v1.0.29", Some(29)), ("unknown", None)] {
+            conn.execute("INSERT OR REPLACE INTO metadata (key, value) VALUES ('version', ?1)", params![value]).unwrap();
+            assert_eq!(read_dict_version_marker(&conn), expected, "{value}");
+        }
+    }
+
+    fn suspended_set(db: &Database) -> HashSet<i64> {
+        db.suspended_ids().unwrap()
+    }
+
+    #[test]
+    fn suspend_by_tag_touches_only_learned_words_of_that_tag() {
+        let db = Database::in_memory().unwrap();
+        let cet4 = db.add_test_word("apple", "cet4", 0, 0).unwrap();
+        let shared = db.add_test_word("banana", "cet4 gre", 0, 0).unwrap();
+        let gre = db.add_test_word("cherry", "gre", 0, 0).unwrap();
+        let cet6 = db.add_test_word("damson", "cet6", 0, 0).unwrap();
+        let unlearned = db.add_test_word("elder", "cet4", 0, 0).unwrap();
+        for id in [cet4, shared, gre, cet6] {
+            db.init_learning_log(id).unwrap();
+        }
+        // Suspended on its own before the bulk actions
+        db.learn_conn
+            .execute("INSERT INTO suspended (word_id, suspended_at) VALUES (?1, '2026-01-01T00:00:00+00:00')", params![gre])
+            .unwrap();
+
+        assert_eq!(db.suspend_by_tag("cet4").unwrap(), 2);
+        assert_eq!(suspended_set(&db), HashSet::from([cet4, shared, gre]));
+        assert!(!suspended_set(&db).contains(&unlearned));
+        // Already suspended words are not counted again
+        assert_eq!(db.suspend_by_tag("cet4").unwrap(), 0);
+
+        assert_eq!(db.unsuspend_by_tag("cet4").unwrap(), 2);
+        assert_eq!(suspended_set(&db), HashSet::from([gre]));
+    }
+
+    #[test]
+    fn reset_by_status_touches_only_that_status() {
+        let db = Database::with_due_words(&[("apple", 1), ("banana", 1)]).unwrap();
+        let (learning, mastered) = (1, 2);
+        db.learn_conn
+            .execute(
+                "UPDATE learning_log SET status = 2, interval = 40, mastered_at = '2026-01-01T00:00:00+00:00'
+                 WHERE word_id = ?1",
+                params![mastered],
+            )
+            .unwrap();
+
+        assert_eq!(db.reset_by_status(LearningStatus::Mastered).unwrap(), 1);
+
+        let row = |id: i64| -> (i32, i32, i32, Option<String>) {
+            db.learn_conn
+                .query_row(
+                    "SELECT status, repetition, interval, mastered_at FROM learning_log WHERE word_id = ?1",
+                    params![id],
+                    |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+                )
+                .unwrap()
+        };
+        assert_eq!(row(mastered), (0, 0, 0, None));
+        assert_eq!(row(learning), (1, 1, 1, None));
+    }
+
+    #[test]
+    fn tag_coverage_skips_words_not_yet_studied() {
+        let db = Database::with_due_words(&[("apple", 1)]).unwrap();
+        let queued = db.add_test_word("banana", "cet4", 0, 0).unwrap();
+        db.init_learning_log(queued).unwrap();
+        db.add_test_word("cherry", "cet4", 0, 0).unwrap();
+
+        assert_eq!(db.get_tag_coverage().unwrap(), vec![("cet4".to_string(), 1, 3)]);
+    }
+
+    #[test]
+    fn words_achievement_counts_only_studied_words() {
+        let db = Database::in_memory().unwrap();
+        for i in 0..100 {
+            let id = db.add_test_word(&format!("word{i}"), "cet4", 0, 0).unwrap();
+            db.init_learning_log(id).unwrap();
+        }
+        assert!(!db.check_achievements().unwrap().contains(&Achievement::Words100));
+
+        db.learn_conn.execute("UPDATE learning_log SET status = 1", []).unwrap();
+        assert!(db.check_achievements().unwrap().contains(&Achievement::Words100));
+    }
+}
+
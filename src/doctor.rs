@@ -0,0 +1,138 @@
+use crate::db::{Database, ECDICT_DB, ECDICT_VERSION, PROGRESS_DB};
+use std::path::Path;
+
+/// WAL files above this size suggest checkpoints are not running
+const WAL_WARN_BYTES: u64 = 32 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Level {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl Level {
+    fn tag(self) -> &'static str {
+        match self {
+            Level::Pass => "[PASS]",
+            Level::Warn => "[WARN]",
+            Level::Fail => "[FAIL]",
+        }
+    }
+}
+
+/// Print a read-only health report of both databases (`lexrain doctor`)
+pub fn run() {
+    let mut findings: Vec<(Level, String)> = Vec::new();
+
+    // Read-only: doctor must not migrate or create anything
+    match Database::open_read_only() {
+        Ok(db) => check(&db, &mut findings),
+        Err(e) => {
+            let hint = if Path::new(ECDICT_DB).exists() {
+                ""
+            } else {
+                " ECDICT is missing: download it and unpack to ecdict-sqlite-28/stardict.db."
+            };
+            findings.push((Level::Fail, format!("cannot open databases: {}.{}", e, hint)));
+        }
+    }
+
+    println!("LexRain doctor");
+    for (level, message) in &findings {
+        println!("  {} {}", level.tag(), message);
+    }
+    let count = |level| findings.iter().filter(|(l, _)| *l == level).count();
+    println!(
+        "{} passed, {} warnings, {} failed",
+        count(Level::Pass),
+        count(Level::Warn),
+        count(Level::Fail)
+    );
+}
+
+fn check(db: &Database, findings: &mut Vec<(Level, String)>) {
+    match db.ecdict_word_count() {
+        Ok(0) => findings.push((Level::Fail, format!("ECDICT: {} has no words", ECDICT_DB))),
+        Ok(count) => findings.push((Level::Pass, format!("ECDICT: {} words ({})", count, ECDICT_DB))),
+        Err(e) => findings.push((
+            Level::Fail,
+            format!("ECDICT: cannot read {} ({}). Download ECDICT and unpack it there.", ECDICT_DB, e),
+        )),
+    }
+
+    match db.missing_progress_schema() {
+        Ok(missing) if missing.is_empty() => {
+            findings.push((Level::Pass, format!("{}: schema up to date", PROGRESS_DB)))
+        }
+        Ok(missing) => findings.push((
+            Level::Warn,
+            format!(
+                "{}: missing {} (added automatically the next time lexrain starts)",
+                PROGRESS_DB,
+                missing.join(", ")
+            ),
+        )),
+        Err(e) => findings.push((Level::Fail, format!("{}: cannot read schema ({})", PROGRESS_DB, e))),
+    }
+
+    let version = db.dict_version();
+    let version_level = if version.age(ECDICT_VERSION).is_eq() { Level::Pass } else { Level::Warn };
+    findings.push((version_level, format!("ECDICT schema: {}", version.describe(ECDICT_VERSION))));
+
+    match db.learning_status_counts() {
+        Ok((new, learning, mastered)) => findings.push((
+            Level::Pass,
+            format!(
+                "learning_log: {} words (new {}, learning {}, mastered {})",
+                new + learning + mastered,
+                new,
+                learning,
+                mastered
+            ),
+        )),
+        Err(e) => findings.push((Level::Fail, format!("learning_log: {}", e))),
+    }
+
+    match db.review_history_count() {
+        Ok(count) => findings.push((Level::Pass, format!("review_history: {} reviews", count))),
+        Err(e) => findings.push((Level::Fail, format!("review_history: {}", e))),
+    }
+
+    match db.find_corrupt_logs() {
+        Ok(corrupt) if corrupt.is_empty() => {
+            findings.push((Level::Pass, "next_review timestamps: all parseable".to_string()))
+        }
+        Ok(corrupt) => findings.push((
+            Level::Warn,
+            format!(
+                "next_review timestamps: {} unparseable, skipped in review (word_ids: {})",
+                corrupt.len(),
+                corrupt.iter().map(|(id, _)| id.to_string()).collect::<Vec<_>>().join(", ")
+            ),
+        )),
+        Err(e) => findings.push((Level::Fail, format!("next_review timestamps: {}", e))),
+    }
+
+    match db.find_orphaned_logs() {
+        Ok(orphaned) if orphaned.is_empty() => {
+            findings.push((Level::Pass, "orphaned logs: none".to_string()))
+        }
+        Ok(orphaned) => findings.push((
+            Level::Warn,
+            format!(
+                "orphaned logs: {} word_ids not found in ECDICT (e.g. {})",
+                orphaned.len(),
+                orphaned.iter().take(10).map(|id| id.to_string()).collect::<Vec<_>>().join(", ")
+            ),
+        )),
+        Err(e) => findings.push((Level::Fail, format!("orphaned logs: {}", e))),
+    }
+
+    let wal_size = db.wal_size();
+    let wal_level = if wal_size > WAL_WARN_BYTES { Level::Warn } else { Level::Pass };
+    findings.push((
+        wal_level,
+        format!("WAL: {}-wal is {:.1} MiB", PROGRESS_DB, wal_size as f64 / 1024.0 / 1024.0),
+    ));
+}
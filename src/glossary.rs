@@ -0,0 +1,58 @@
+use crate::models::{GlossaryMatch, Word};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// One glossary entry; both fields are optional
+#[derive(Debug, Clone, Deserialize)]
+struct GlossaryEntry {
+    translation: Option<String>, // Replaces the ECDICT Chinese translation
+    note: Option<String>,        // Shown next to the ECDICT entry
+}
+
+/// 个人词汇表：用户自己的 JSON 文件，按拼写给 ECDICT 词条补充笔记或覆盖中文释义
+///
+/// ```json
+/// {
+///   "stat": { "translation": "adv. 立即（医嘱）", "note": "拉丁语 statim" },
+///   "tort": { "note": "民事侵权" }
+/// }
+/// ```
+pub struct GlossarySource {
+    entries: HashMap<String, GlossaryEntry>, // Keyed by lowercase spelling (ECDICT matches case-insensitively too)
+}
+
+impl GlossarySource {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("cannot read glossary {}", path.display()))?;
+        let raw: HashMap<String, GlossaryEntry> = serde_json::from_str(&text)
+            .with_context(|| format!("invalid glossary JSON in {}", path.display()))?;
+        let entries = raw
+            .into_iter()
+            .filter(|(_, entry)| entry.translation.is_some() || entry.note.is_some())
+            .map(|(spelling, entry)| (spelling.trim().to_lowercase(), entry))
+            .collect();
+        Ok(Self { entries })
+    }
+
+    pub fn entry_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Merge the entry for `word`'s spelling, if any, and record it on `word.glossary`
+    pub fn apply(&self, word: &mut Word) {
+        let Some(entry) = self.entries.get(&word.spelling.to_lowercase()) else {
+            return;
+        };
+        let replaced_translation = match &entry.translation {
+            Some(translation) => word.translation.replace(translation.clone()),
+            None => None,
+        };
+        word.glossary = Some(GlossaryMatch {
+            note: entry.note.clone(),
+            replaced_translation,
+        });
+    }
+}
@@ -0,0 +1,231 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// UI language
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Lang {
+    En,
+    Zh,
+}
+
+impl Lang {
+    pub const ALL: [Lang; 2] = [Lang::Zh, Lang::En];
+
+    /// Parse the stored setting value ("en" / "zh"), defaulting to Chinese
+    pub fn from_setting(value: &str) -> Self {
+        match value {
+            "en" => Lang::En,
+            _ => Lang::Zh,
+        }
+    }
+
+    pub fn to_setting(self) -> &'static str {
+        match self {
+            Lang::En => "en",
+            Lang::Zh => "zh",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Lang::En => "English",
+            Lang::Zh => "中文",
+        }
+    }
+}
+
+/// 当前界面语言（全局，与 Theme 一样在渲染时直接读取）
+static CURRENT: AtomicU8 = AtomicU8::new(Lang::Zh as u8);
+
+pub fn set_lang(lang: Lang) {
+    CURRENT.store(lang as u8, Ordering::Relaxed);
+}
+
+pub fn lang() -> Lang {
+    if CURRENT.load(Ordering::Relaxed) == Lang::En as u8 {
+        Lang::En
+    } else {
+        Lang::Zh
+    }
+}
+
+/// Look up a user-facing string in the current language.
+///
+/// Unknown keys are returned unchanged so a missing entry shows up on screen
+/// instead of panicking.
+pub fn t(key: &'static str) -> &'static str {
+    let Some((en, zh)) = entry(key) else {
+        debug_assert!(false, "missing i18n key: {}", key);
+        return key;
+    };
+    match lang() {
+        Lang::En => en,
+        Lang::Zh => zh,
+    }
+}
+
+/// (English, 中文)
+fn entry(key: &str) -> Option<(&'static str, &'static str)> {
+    Some(match key {
+        // Tab bar
+        "tab.dashboard" => ("Dashboard", "主页"),
+        "tab.review" => ("Review", "复习"),
+        "tab.dictionary" => ("Dictionary", "词典"),
+        "tab.history" => ("History", "历史"),
+        "tab.statistics" => ("Statistics", "统计"),
+        "tab.wordbook" => ("Wordbook", "单词本"),
+        "tab.favorites" => ("Favorites", "收藏"),
+        "tab.settings" => ("Settings", "设置"),
+        "tab.quit" => ("Quit", "退出"),
+
+        // Footer key hints
+        "key.review_forgotten" => ("Forgotten", "复习最近忘记"),
+        "key.oldest_due" => ("Oldest Due First", "最久逾期优先"),
+        "key.granularity" => ("Day/Week", "按天/按周"),
+        "key.calendar_style" => ("Heatmap", "热力图"),
+        "key.learn_new" => ("Learn New", "学新词"),
+        "key.back" => ("Back", "返回"),
+        "key.quick_due" => ("Review Due", "复习到期"),
+        "key.show_answer" => ("Show Answer", "显示答案"),
+        "key.next_card" => ("Next Card", "下一张"),
+        "key.browse" => ("Browse (no rating)", "浏览（不计分）"),
+        "key.hint" => ("Letter Hint", "字母提示"),
+        "key.hard" => ("Hard", "忘记"),
+        "key.difficult" => ("Difficult", "模糊"),
+        "key.good" => ("Good", "清楚"),
+        "key.easy" => ("Easy", "简单"),
+        "key.favorite" => ("Favorite", "收藏"),
+        "key.lookup" => ("Look Up", "查词"),
+        "key.search" => ("Search", "搜索"),
+        "key.goto" => ("Go To", "跳转"),
+        "key.global_search" => ("Search All", "全局搜索"),
+        "key.confirm" => ("Confirm", "确认"),
+        "key.switch_panel" => ("Switch Panel", "切换面板"),
+        "key.results" => ("Results", "结果"),
+        "key.open" => ("Open", "打开"),
+        "key.navigate" => ("Navigate", "移动"),
+        "key.self_test" => ("Self-test", "自测"),
+        "key.review_word" => ("Review Word", "复习此词"),
+        "key.add_to_learning" => ("Add to Learning", "加入学习"),
+        "key.tag_filter" => ("Tags", "标签筛选"),
+        "key.freq_band" => ("Freq Band", "词频筛选"),
+        "key.variants" => ("Variants", "变体"),
+        "key.show_mastered" => ("Mastered", "已掌握"),
+        "key.reschedule" => ("Reschedule", "调整复习"),
+        "key.unmaster" => ("Unmaster", "取消掌握"),
+        "key.reset_ease" => ("Reset Ease", "重置难度"),
+        "key.first_last" => ("First/Last", "首/尾"),
+        "key.page" => ("Page", "翻页"),
+        "key.start_review" => ("Start Review", "开始复习"),
+        "key.word_order" => ("Order", "顺序/乱序/词典序"),
+        "key.toggle_due_only" => ("Due Only", "只复习到期"),
+        "key.focus" => ("Focus", "重点"),
+        "key.priority" => ("Priority", "限时优先"),
+        "key.compare" => ("Compare", "对比"),
+        "key.suspend_resume" => ("Suspend/Resume", "暂停/恢复"),
+        "key.select" => ("Select", "选择"),
+        "key.unfavorite" => ("Unfavorite", "取消收藏"),
+        "key.edit" => ("Edit", "编辑"),
+        "key.change" => ("Change", "切换"),
+        "key.scroll_detail" => ("Scroll Detail", "滚动详情"),
+        "key.scroll_guide" => ("Scroll Guide", "滚动指南"),
+        "key.scroll" => ("Scroll", "滚动"),
+        "key.scroll_both" => ("Scroll Both", "两栏同时滚动"),
+        "key.top" => ("Top", "回到顶部"),
+        "key.deck_summary" => ("Overview", "总览"),
+        "key.skip_new" => ("Skip New Today", "今日暂停新词"),
+        "key.cancel_back" => ("Cancel/Back", "取消/返回"),
+
+        // Dashboard
+        "dash.stats_title" => (" 📊 Statistics ", " 📊 学习统计 "),
+        "dash.vocabulary" => ("Vocabulary: ", "词汇库: "),
+        "dash.words_unit" => (" words", " 个单词"),
+        "dash.mastered" => ("Mastered: ", "已掌握: "),
+        "dash.due" => ("Due: ", "待复习: "),
+        "dash.maturity_new" => (" New ", " 新 "),
+        "dash.maturity_young" => (" · Young ", " · 短期 "),
+        "dash.maturity_mature" => (" · Mature ", " · 成熟 "),
+        "dash.unit" => ("", " 个"),
+        "dash.new_word_source" => ("New words from: ", "新词来源: "),
+        "dash.priority_until" => (" (priority until {n}, reviews first too)", "（优先至 {n}，复习也先排）"),
+        "dash.freq_band" => (" · frequency ", " · 词频 "),
+        "dash.new_word_global" => ("all words by frequency", "全库（按词频）"),
+        "dash.skip_new" => ("Skip new words today: ", "今日暂停学新词: "),
+        "dash.skip_new_hint" => ("  (p to toggle)", "  (p 切换)"),
+        "dash.on" => ("on", "开"),
+        "dash.off" => ("off", "关"),
+        "dash.today_title" => (" 📅 Today's Progress ", " 📅 今日进度 "),
+        "dash.today_reviewed" => ("Reviewed today: ", "今日已复习: "),
+        "dash.actions_title" => (" ⌨️  Quick Actions ", " ⌨️  快捷操作 "),
+        "dash.actions_done_title" => (" 🎉 Quick Actions ", " 🎉 快捷操作 "),
+        "dash.corrupt_logs" => (
+            "⚠ {n} learning records have an unreadable review time and were skipped (see lexrain.log)",
+            "⚠ {n} 条学习记录的复习时间无法解析，已跳过（详见 lexrain.log）",
+        ),
+        "dash.dict_older" => (
+            "⚠ ECDICT older than ecdict-sqlite-{n}: tags may display wrong (lexrain doctor)",
+            "⚠ 词典比 ecdict-sqlite-{n} 旧，标签等可能显示不对（见 lexrain doctor）",
+        ),
+        "dash.dict_newer" => (
+            "⚠ ECDICT newer than ecdict-sqlite-{n}: tags may display wrong (lexrain doctor)",
+            "⚠ 词典比 ecdict-sqlite-{n} 新，标签等可能显示不对（见 lexrain doctor）",
+        ),
+        "dash.backlog_hint" => (
+            "Backlog building up ({n} reviews due in 7 days): consider pausing new words",
+            "积压较多（未来 7 天 {n} 个待复习），建议暂缓学新词",
+        ),
+        "dash.backlog_overdue" => ("{n} of them already overdue", "其中 {n} 个已逾期"),
+        "dash.reminder" => ("⏰ No reviews yet today", "⏰ 今天还没有复习哦"),
+        "dash.reminder_dismiss" => ("  (Esc to hide)", "  (Esc 关闭)"),
+        "dash.velocity_title" => (" 📈 Velocity (14 days) ", " 📈 学习速度 (14天) "),
+        "dash.added" => ("Added", "新增"),
+        "dash.mastered_short" => ("Mastered", "掌握"),
+        "dash.calendar_title" => (" 📅 Calendar ", " 📅 日历 "),
+        "dash.goal_days" => ("goal met {n}d ", "达标 {n} 天 "),
+        "dash.active_days" => ("studied {n}d ", "学习 {n} 天 "),
+        "dash.heatmap_less" => ("less", "少"),
+        "dash.heatmap_more" => ("more", "多"),
+        "dash.today" => (" Today ", " 今天 "),
+        "dash.gauge_title" => (" ✓ Mastered: {n} ", " ✓ 已掌握: {n} "),
+        "dash.gauge_label" => ("{n} mastered", "已掌握 {n}"),
+
+        "dash.forgotten_title" => (" 🔁 Recently Forgotten ({n}) ", " 🔁 最近忘记 ({n}) "),
+        "dash.forgotten_review" => (" F: review these ", " F 复习这些词 "),
+        "dash.achievements" => ("Badges: ", "成就: "),
+
+        // Wordbook
+        "wordbook.no_due" => ("No due words in this wordbook", "这个单词本没有到期的单词"),
+
+        // Achievements
+        "ach.unlocked" => ("🏆 Achievement unlocked: ", "🏆 解锁成就: "),
+        "ach.words_100" => ("📚 100 Words", "📚 百词起步"),
+        "ach.streak_7" => ("🔥 7-Day Streak", "🔥 连续 7 天"),
+        "ach.streak_30" => ("🔥 30-Day Streak", "🔥 连续 30 天"),
+        "ach.streak_100" => ("💯 100-Day Streak", "💯 连续 100 天"),
+        "ach.reviews_1000" => ("🔁 1000 Reviews", "🔁 复习 1000 次"),
+        "ach.wordbook_mastered" => ("🎓 Wordbook Mastered", "🎓 单词本全掌握"),
+
+        // Glance line (below the tabs)
+        "glance.due" => ("Due ", "待复习 "),
+        "glance.today" => ("Today ", "今日 "),
+        "glance.streak" => ("Streak: {n} days", "连续打卡 {n} 天"),
+        "glance.dict_unavailable" => (
+            "⚠ Dictionary unavailable: cannot read the ECDICT file (drive disconnected?); it reconnects on its own",
+            "⚠ 词典暂时无法读取（ECDICT 所在的盘断开了？），恢复后会自动重连",
+        ),
+
+        // Exit summary (printed after the terminal is restored)
+        "exit.next_review" => ("next review ", "下次复习 "),
+        "exit.now" => ("now", "现在"),
+
+        // Settings
+        "settings.language" => ("Language", "界面语言"),
+        "settings.language_desc" => ("Tabs, footer and dashboard text", "标签栏、底部提示和主页的文字"),
+
+        _ => return None,
+    })
+}
+
+/// `t` with a `{n}` placeholder filled in
+pub fn t_n(key: &'static str, n: impl std::fmt::Display) -> String {
+    t(key).replace("{n}", &n.to_string())
+}
@@ -0,0 +1,32 @@
+use chrono::Local;
+use std::fs::OpenOptions;
+use std::io::Write;
+
+/// 日志文件路径（与进度数据库放在同一目录）
+const LOG_FILE: &str = "lexrain.log";
+
+/// Append a warning line to `lexrain.log`.
+///
+/// The TUI owns stdout/stderr while running, so diagnostics go to a file
+/// instead. Failures to write the log are ignored on purpose: logging must
+/// never take the app down.
+pub fn warn(message: &str) {
+    write_line("WARN", message);
+}
+
+/// Append an error line to `lexrain.log` (used for panics, which also go to stderr)
+pub fn error(message: &str) {
+    write_line("ERROR", message);
+}
+
+fn write_line(level: &str, message: &str) {
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(LOG_FILE) {
+        let _ = writeln!(
+            file,
+            "{} [{}] {}",
+            Local::now().format("%Y-%m-%d %H:%M:%S"),
+            level,
+            message
+        );
+    }
+}
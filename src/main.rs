@@ -1,42 +1,232 @@
+#[cfg(feature = "legacy-v1")]
 mod app;
 mod app_v2;
 mod components;
 mod db;
+mod doctor;
 mod event;
+mod glossary;
+mod i18n;
+mod logger;
 mod models;
+mod serve;
 mod sm2;
 mod theme;
 mod tui;
+#[cfg(feature = "legacy-v1")]
 mod ui;
 
 use anyhow::Result;
+#[cfg(feature = "legacy-v1")]
 use app::{App, CurrentScreen, ReviewState};
-use clap::Parser;
-use crossterm::event::{KeyCode, KeyEventKind};
+use clap::{Parser, Subcommand};
+#[cfg(feature = "legacy-v1")]
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEventKind;
 use db::Database;
+use std::path::PathBuf;
 use std::time::Duration;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    /// Use the new component-based architecture (default)
-    #[arg(long, default_value_t = true)]
+    /// Use the new component-based architecture (default; kept for old scripts)
+    #[arg(long, default_value_t = true, hide = true)]
     v2: bool,
+
+    /// Open a due review session right away (if anything is due)
+    #[arg(long)]
+    review: bool,
+
+    /// Run the deprecated v1 interface (needs a build with `--features legacy-v1`)
+    #[arg(long)]
+    legacy: bool,
+
+    /// While the TUI runs, answer `GET /stats` with JSON (due, today, streak) on
+    /// 127.0.0.1, for status-bar widgets
+    #[arg(long)]
+    serve: bool,
+
+    /// Port for --serve
+    #[arg(long, default_value_t = serve::DEFAULT_SERVE_PORT)]
+    serve_port: u16,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Merge data from another LexRain install into this one
+    Import {
+        /// Progress database of the other install (lexrain_progress.db)
+        #[arg(long)]
+        progress: PathBuf,
+    },
+    /// Check database health (read-only)
+    Doctor,
+    /// Delete old review history, keeping daily totals for the charts
+    Prune {
+        /// Keep reviews from the last N days
+        #[arg(long, default_value_t = db::DEFAULT_PRUNE_KEEP_DAYS)]
+        days: i64,
+    },
+    /// Export or import favorites as a plain-text list (one word per line)
+    Favorites {
+        #[command(subcommand)]
+        action: FavoritesCommand,
+    },
+    /// Back up only what you chose yourself (favorites, suspended words, settings) as JSON
+    UserData {
+        #[command(subcommand)]
+        action: UserDataCommand,
+    },
+    /// Write the numbers behind the statistics charts as CSV, for plotting elsewhere
+    Export {
+        /// Forgetting-curve rows go here; daily counts go to <name>_daily.csv next to it
+        #[arg(long)]
+        stats_csv: PathBuf,
+        /// Days of daily counts to include
+        #[arg(long, default_value_t = db::DEFAULT_STATS_CSV_DAYS)]
+        days: i64,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum FavoritesCommand {
+    /// Write all favorites to a text file, oldest first
+    Export { path: PathBuf },
+    /// Favorite every word listed in a text file (blank lines and # comments are skipped)
+    Import { path: PathBuf },
+}
+
+#[derive(Subcommand, Debug)]
+enum UserDataCommand {
+    /// Write favorites, suspended words and settings to a JSON file
+    Export { path: PathBuf },
+    /// Merge a JSON backup: adds missing favorites/suspensions, backup settings win
+    Import { path: PathBuf },
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
+
+    // Doctor must also work when the databases cannot be opened at all
+    if let Some(Command::Doctor) = args.command {
+        doctor::run();
+        return Ok(());
+    }
+
+    // Decide before touching the terminal so the warning/error stays readable
+    let use_v2 = args.v2 && !args.legacy;
+    if !use_v2 {
+        if cfg!(feature = "legacy-v1") {
+            eprintln!("warning: the v1 interface is deprecated and will be removed; run without --legacy to use v2");
+        } else {
+            anyhow::bail!("the v1 interface is not included in this build; rebuild with `--features legacy-v1`");
+        }
+    }
+
     let db = Database::initialize()?;
 
-    // Initialize TUI
-    let mut terminal = tui::init()?;
+    match args.command {
+        Some(Command::Import { progress }) => {
+            let summary = db.merge_progress(&progress)?;
+            println!("Merged progress from {}", progress.display());
+            println!(
+                "  learning_log: {} merged, {} skipped, {} conflicted (kept the more advanced log)",
+                summary.merged, summary.skipped, summary.conflicted
+            );
+            println!("  review_history: {} rows added", summary.history_added);
+            return Ok(());
+        }
+        Some(Command::Prune { days }) => {
+            let removed = db.prune_history(days)?;
+            println!(
+                "Pruned {} review_history rows older than {} days (daily totals kept in history_daily_summary)",
+                removed, days
+            );
+            return Ok(());
+        }
+        Some(Command::Favorites { action: FavoritesCommand::Export { path } }) => {
+            let count = db.export_favorites_txt(&path)?;
+            println!("Exported {} favorites to {}", count, path.display());
+            return Ok(());
+        }
+        Some(Command::Favorites { action: FavoritesCommand::Import { path } }) => {
+            let summary = db.import_favorites_txt(&path)?;
+            println!("Imported favorites from {}", path.display());
+            println!("  {} added, {} already favorited", summary.added, summary.already);
+            if !summary.not_found.is_empty() {
+                println!(
+                    "  {} not found in ECDICT: {}",
+                    summary.not_found.len(),
+                    summary.not_found.join(", ")
+                );
+            }
+            return Ok(());
+        }
+        Some(Command::UserData { action: UserDataCommand::Export { path } }) => {
+            let backup = db.export_user_data_json(&path)?;
+            println!(
+                "Exported {} favorites, {} suspended words and {} settings to {}",
+                backup.favorites.len(),
+                backup.suspended.len(),
+                backup.settings.len(),
+                path.display()
+            );
+            return Ok(());
+        }
+        Some(Command::UserData { action: UserDataCommand::Import { path } }) => {
+            let summary = db.import_user_data_json(&path)?;
+            println!("Imported user data from {}", path.display());
+            println!(
+                "  {} favorites added, {} words suspended, {} settings applied",
+                summary.favorites_added, summary.suspended_added, summary.settings_applied
+            );
+            if !summary.not_found.is_empty() {
+                println!(
+                    "  {} not found in ECDICT: {}",
+                    summary.not_found.len(),
+                    summary.not_found.join(", ")
+                );
+            }
+            return Ok(());
+        }
+        Some(Command::Export { stats_csv, days }) => {
+            let (interval_rows, daily_rows, daily_path) = db.export_stats_csv(&stats_csv, days)?;
+            println!("Exported {} interval rows to {}", interval_rows, stats_csv.display());
+            println!("Exported {} daily rows (last {} days) to {}", daily_rows, days, daily_path.display());
+            return Ok(());
+        }
+        Some(Command::Doctor) | None => {}
+    }
+
+    if args.serve {
+        serve::spawn(&db, args.serve_port)?;
+    }
+
+    // Initialize TUI (panic hook first so a crash never leaves the shell in raw mode)
+    tui::install_panic_hook();
+    let terminal = tui::init()?;
     let event_handler = event::EventHandler::new(Duration::from_millis(10));
 
-    // Choose architecture version
-    if args.v2 {
-        return run_v2(terminal, db, event_handler);
+    // Choose architecture version (--legacy was rejected above unless v1 is compiled in)
+    #[cfg(feature = "legacy-v1")]
+    if !use_v2 {
+        return run_v1(terminal, db, event_handler);
     }
 
+    run_v2(terminal, db, event_handler, args.review)
+}
+
+/// 旧版 v1 界面（已弃用，仅在 legacy-v1 feature 下编译）
+#[cfg(feature = "legacy-v1")]
+fn run_v1(
+    mut terminal: tui::Tui,
+    db: Database,
+    event_handler: event::EventHandler,
+) -> Result<()> {
     let mut app = App::new(db);
 
     // Main Loop
@@ -138,29 +328,48 @@ fn run_v2(
     mut terminal: ratatui::Terminal<ratatui::backend::CrosstermBackend<std::io::Stdout>>,
     db: Database,
     event_handler: event::EventHandler,
+    start_in_review: bool,
 ) -> Result<()> {
-    let mut app = app_v2::AppV2::new(db)?;
+    // Restores the terminal on every exit path, not only the normal one
+    let mut guard = tui::TerminalGuard::default();
+    let mut app = app_v2::AppV2::new(db, start_in_review)?;
+
+    let result = run_v2_loop(&mut terminal, &mut app, &event_handler);
+
+    // 出错时也先保存状态，再恢复终端并返回错误
+    app.shutdown();
+    guard.restore()?;
+    result?;
+
+    // Recap on the normal screen once the TUI is gone
+    if let Some(summary) = app.exit_summary()? {
+        println!("{}", summary);
+    }
+    Ok(())
+}
 
+fn run_v2_loop(
+    terminal: &mut tui::Tui,
+    app: &mut app_v2::AppV2,
+    event_handler: &event::EventHandler,
+) -> Result<()> {
     loop {
         terminal.draw(|frame| app.render(frame))?;
 
         if let Some(event) = event_handler.next()? {
             match event {
-                event::AppEvent::Key(key) => {
-                    if key.kind == KeyEventKind::Press {
-                        if app.handle_key(key)? {
-                            break;
-                        }
-                    }
-                }
-                event::AppEvent::Tick => {
-                    // Handle periodic updates if needed
-                }
+                event::AppEvent::Key(key) if key.kind == KeyEventKind::Press => match app.handle_key(key) {
+                    Ok(true) => return Ok(()),
+                    Ok(false) => {}
+                    // ECDICT went away mid-session: the glance line shows a banner, keep running
+                    // so it can come back instead of quitting
+                    Err(err) if err.downcast_ref::<db::DictUnavailable>().is_some() => {}
+                    Err(err) => return Err(err),
+                },
+                event::AppEvent::Key(_) => {}
+                event::AppEvent::Tick => app.on_tick(),
             }
         }
     }
-
-    tui::restore()?;
-    Ok(())
 }
 
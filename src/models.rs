@@ -1,5 +1,18 @@
 use serde::{Deserialize, Serialize};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::HashMap;
+use std::cmp::Ordering;
+
+/// Parse ECDICT exchange field ("p:went/d:gone/0:go") into a readable map
+pub fn parse_exchange(exchange: &str) -> HashMap<&str, String> {
+    let mut result = HashMap::new();
+    for part in exchange.split('/') {
+        if let Some((key, value)) = part.split_once(':') {
+            result.insert(key, value.to_string());
+        }
+    }
+    result
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Word {
@@ -16,6 +29,32 @@ pub struct Word {
     pub frq: Option<i32>,           // Contemporary corpus frequency rank
     pub exchange: Option<String>,    // Word forms (tenses, plural, etc.)
     pub favorited: bool,             // Favorited flag
+    pub glossary: Option<GlossaryMatch>, // Personal glossary entry merged in (see glossary.rs)
+}
+
+/// What the personal glossary changed on a `Word`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GlossaryMatch {
+    pub note: Option<String>,
+    pub replaced_translation: Option<String>, // ECDICT translation the glossary overrode
+}
+
+/// ECDICT exam tag codes, easiest first (the dictionary's tag legend uses this order)
+pub const EXAM_TAGS: [&str; 8] = ["zk", "gk", "cet4", "cet6", "ky", "toefl", "ielts", "gre"];
+
+/// Map an ECDICT tag code to its display name (zk -> 中考)
+pub fn tag_display_name(tag: &str) -> &str {
+    match tag {
+        "zk" => "中考",
+        "gk" => "高考",
+        "cet4" => "CET-4",
+        "cet6" => "CET-6",
+        "ky" => "考研",
+        "toefl" => "TOEFL",
+        "ielts" => "IELTS",
+        "gre" => "GRE",
+        _ => tag,
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,6 +65,7 @@ pub struct LearningLog {
     pub e_factor: f64,       // EF
     pub next_review: DateTime<Utc>,
     pub status: LearningStatus,
+    pub consecutive_good: i32, // Reviews rated 3+ in a row; cleared by a lapse or un-mastering
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -50,3 +90,914 @@ impl From<LearningStatus> for i32 {
         status as i32
     }
 }
+
+/// Extra conditions for auto-mastery on top of the 21-day interval (0 = not required)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct MasteryRule {
+    pub min_repetition: i32,
+    pub consecutive_good: i32,
+}
+
+impl MasteryRule {
+    pub fn is_met(&self, log: &LearningLog) -> bool {
+        log.repetition >= self.min_repetition && log.consecutive_good >= self.consecutive_good
+    }
+}
+
+/// How mastered words (interval > 21 days) come back into review
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MasteredReview {
+    Normal,
+    Reduced(f64), // Scheduled interval is multiplied by this factor
+    Never,        // Excluded from due reviews until un-mastered
+}
+
+impl MasteredReview {
+    /// Choices offered in the Settings screen
+    pub const PRESETS: [MasteredReview; 4] = [
+        MasteredReview::Normal,
+        MasteredReview::Reduced(2.0),
+        MasteredReview::Reduced(4.0),
+        MasteredReview::Never,
+    ];
+
+    /// Parse the stored setting value ("normal", "reduced:2", "never")
+    pub fn from_setting(value: &str) -> Self {
+        match value.split_once(':') {
+            Some(("reduced", factor)) => factor
+                .parse::<f64>()
+                .ok()
+                .filter(|f| *f >= 1.0)
+                .map(MasteredReview::Reduced)
+                .unwrap_or(MasteredReview::Normal),
+            _ if value == "never" => MasteredReview::Never,
+            _ => MasteredReview::Normal,
+        }
+    }
+
+    pub fn to_setting(self) -> String {
+        match self {
+            MasteredReview::Normal => "normal".to_string(),
+            MasteredReview::Reduced(factor) => format!("reduced:{}", factor),
+            MasteredReview::Never => "never".to_string(),
+        }
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            MasteredReview::Normal => "正常复习".to_string(),
+            MasteredReview::Reduced(factor) => format!("降低频率 (间隔 ×{})", factor),
+            MasteredReview::Never => "不再复习".to_string(),
+        }
+    }
+}
+
+/// Word-frequency band used when picking new words (and, on demand, when browsing).
+/// Rank is ECDICT's contemporary frequency (`frq`), falling back to BNC; 0/NULL means unranked.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FreqBand {
+    All,
+    Band(i64, Option<i64>), // (from, to) rank, inclusive; to = None: from onwards plus unranked words
+}
+
+impl FreqBand {
+    /// Choices offered in the Settings screen
+    pub const PRESETS: [FreqBand; 5] = [
+        FreqBand::All,
+        FreqBand::Band(1, Some(3000)),
+        FreqBand::Band(3001, Some(6000)),
+        FreqBand::Band(6001, Some(10000)),
+        FreqBand::Band(10001, None),
+    ];
+
+    /// Parse the stored setting value ("all", "band:1-3000", "band:10001-")
+    pub fn from_setting(value: &str) -> Self {
+        let Some(("band", range)) = value.split_once(':') else {
+            return FreqBand::All;
+        };
+        let Some((from, to)) = range.split_once('-') else {
+            return FreqBand::All;
+        };
+        match (from.parse::<i64>(), to) {
+            (Ok(from), "") => FreqBand::Band(from, None),
+            (Ok(from), to) => to.parse::<i64>().map(|to| FreqBand::Band(from, Some(to))).unwrap_or(FreqBand::All),
+            _ => FreqBand::All,
+        }
+    }
+
+    pub fn to_setting(self) -> String {
+        match self {
+            FreqBand::All => "all".to_string(),
+            FreqBand::Band(from, Some(to)) => format!("band:{}-{}", from, to),
+            FreqBand::Band(from, None) => format!("band:{}-", from),
+        }
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            FreqBand::All => "不限".to_string(),
+            FreqBand::Band(1, Some(to)) => format!("最常用 1–{}", to),
+            FreqBand::Band(from, Some(to)) => format!("{}–{}", from - 1, to),
+            FreqBand::Band(from, None) => format!("{} 以后（长尾）", from - 1),
+        }
+    }
+
+    /// Whether a word with these ECDICT ranks falls in the band (same rule as the SQL filter)
+    pub fn contains(self, frq: Option<i32>, bnc: Option<i32>) -> bool {
+        let rank = frq.filter(|r| *r > 0).or(bnc.filter(|r| *r > 0)).map(i64::from);
+        match (self, rank) {
+            (FreqBand::All, _) => true,
+            (FreqBand::Band(from, Some(to)), Some(rank)) => (from..=to).contains(&rank),
+            (FreqBand::Band(_, Some(_)), None) => false,
+            (FreqBand::Band(from, None), rank) => rank.is_none_or(|rank| rank >= from),
+        }
+    }
+}
+
+/// Blend new words into the due review session (`r`) instead of a separate flow
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LearningMix {
+    Off,
+    Every(u8), // One new word per this many reviews
+}
+
+impl LearningMix {
+    /// Choices offered in the Settings screen
+    pub const PRESETS: [LearningMix; 4] = [
+        LearningMix::Off,
+        LearningMix::Every(2),
+        LearningMix::Every(4),
+        LearningMix::Every(8),
+    ];
+
+    /// Parse the stored setting value ("off", "every:4")
+    pub fn from_setting(value: &str) -> Self {
+        match value.split_once(':') {
+            Some(("every", n)) => n
+                .parse::<u8>()
+                .ok()
+                .filter(|n| *n > 0)
+                .map(LearningMix::Every)
+                .unwrap_or(LearningMix::Off),
+            _ => LearningMix::Off,
+        }
+    }
+
+    pub fn to_setting(self) -> String {
+        match self {
+            LearningMix::Off => "off".to_string(),
+            LearningMix::Every(n) => format!("every:{}", n),
+        }
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            LearningMix::Off => "关闭（复习和学新词分开）".to_string(),
+            LearningMix::Every(n) => format!("每 {} 个复习插入 1 个新词", n),
+        }
+    }
+}
+
+/// Where the first new word goes in a mixed session
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MixOrder {
+    ReviewsFirst, // Start with due reviews
+    NewFirst,     // Start with a new word
+}
+
+impl MixOrder {
+    pub const ALL: [MixOrder; 2] = [MixOrder::ReviewsFirst, MixOrder::NewFirst];
+
+    pub fn from_setting(value: &str) -> Self {
+        match value {
+            "new_first" => MixOrder::NewFirst,
+            _ => MixOrder::ReviewsFirst,
+        }
+    }
+
+    pub fn to_setting(self) -> &'static str {
+        match self {
+            MixOrder::ReviewsFirst => "reviews_first",
+            MixOrder::NewFirst => "new_first",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MixOrder::ReviewsFirst => "先复习，再穿插新词",
+            MixOrder::NewFirst => "先学一个新词，再穿插复习",
+        }
+    }
+}
+
+/// Order of the words in a wordbook session (`s` on the wordbook page cycles)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WordOrder {
+    Frequency, // Most common first (Oxford/Collins, then BNC/frequency rank)
+    Shuffle,
+    Dictionary, // ECDICT row order (alphabetical); ECDICT has no textbook/list order
+}
+
+impl WordOrder {
+    pub const ALL: [WordOrder; 3] = [WordOrder::Frequency, WordOrder::Shuffle, WordOrder::Dictionary];
+
+    pub fn icon(self) -> &'static str {
+        match self {
+            WordOrder::Frequency => "📚",
+            WordOrder::Shuffle => "🔀",
+            WordOrder::Dictionary => "📜",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            WordOrder::Frequency => "顺序",
+            WordOrder::Shuffle => "乱序",
+            WordOrder::Dictionary => "词典序",
+        }
+    }
+}
+
+/// Which side of the card is the prompt
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReviewDirection {
+    EnToZh, // See the word, recall its meaning
+    ZhToEn, // See the meaning, recall the spelling
+    Both,   // Each card twice in a row: EnToZh, then ZhToEn; one shared schedule
+    PhoneticToWord, // See the IPA, recall spelling and meaning; words without a phonetic are skipped
+}
+
+impl ReviewDirection {
+    pub const ALL: [ReviewDirection; 4] = [
+        ReviewDirection::EnToZh,
+        ReviewDirection::ZhToEn,
+        ReviewDirection::Both,
+        ReviewDirection::PhoneticToWord,
+    ];
+
+    pub fn from_setting(value: &str) -> Self {
+        match value {
+            "zh_to_en" => ReviewDirection::ZhToEn,
+            "both" => ReviewDirection::Both,
+            "phonetic_to_word" => ReviewDirection::PhoneticToWord,
+            _ => ReviewDirection::EnToZh,
+        }
+    }
+
+    pub fn to_setting(self) -> &'static str {
+        match self {
+            ReviewDirection::EnToZh => "en_to_zh",
+            ReviewDirection::ZhToEn => "zh_to_en",
+            ReviewDirection::Both => "both",
+            ReviewDirection::PhoneticToWord => "phonetic_to_word",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ReviewDirection::EnToZh => "英 → 中（看单词回忆释义）",
+            ReviewDirection::ZhToEn => "中 → 英（看释义回忆拼写）",
+            ReviewDirection::Both => "双向（先英 → 中，再中 → 英）",
+            ReviewDirection::PhoneticToWord => "音标 → 词（看音标回忆拼写和释义）",
+        }
+    }
+
+    /// Compact name for tables and charts
+    pub fn short_label(self) -> &'static str {
+        match self {
+            ReviewDirection::EnToZh => "英→中",
+            ReviewDirection::ZhToEn => "中→英",
+            ReviewDirection::Both => "双向",
+            ReviewDirection::PhoneticToWord => "音标→词",
+        }
+    }
+}
+
+/// What h/l do in the dictionary's normal mode
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DictHlKeys {
+    Unbound,      // h/l free; detail scrolls with Ctrl-d/Ctrl-u and </>
+    ScrollDetail, // Legacy: h/l also scroll the detail panel
+}
+
+impl DictHlKeys {
+    pub const ALL: [DictHlKeys; 2] = [DictHlKeys::Unbound, DictHlKeys::ScrollDetail];
+
+    pub fn from_setting(value: &str) -> Self {
+        match value {
+            "scroll_detail" => DictHlKeys::ScrollDetail,
+            _ => DictHlKeys::Unbound,
+        }
+    }
+
+    pub fn to_setting(self) -> &'static str {
+        match self {
+            DictHlKeys::Unbound => "unbound",
+            DictHlKeys::ScrollDetail => "scroll_detail",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DictHlKeys::Unbound => "不绑定",
+            DictHlKeys::ScrollDetail => "滚动详情",
+        }
+    }
+}
+
+/// What Enter does on the selected word in the dictionary's normal mode
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DictEnterAction {
+    OpenDetail,    // Detail popup (default)
+    StartReview,   // Same as R: one-card review of the word
+    AddToLearning, // Put the word into the learning log as New
+}
+
+impl DictEnterAction {
+    pub const ALL: [DictEnterAction; 3] =
+        [DictEnterAction::OpenDetail, DictEnterAction::StartReview, DictEnterAction::AddToLearning];
+
+    pub fn from_setting(value: &str) -> Self {
+        match value {
+            "start_review" => DictEnterAction::StartReview,
+            "add_to_learning" => DictEnterAction::AddToLearning,
+            _ => DictEnterAction::OpenDetail,
+        }
+    }
+
+    pub fn to_setting(self) -> &'static str {
+        match self {
+            DictEnterAction::OpenDetail => "open_detail",
+            DictEnterAction::StartReview => "start_review",
+            DictEnterAction::AddToLearning => "add_to_learning",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DictEnterAction::OpenDetail => "打开详情（默认）",
+            DictEnterAction::StartReview => "复习此词",
+            DictEnterAction::AddToLearning => "加入学习",
+        }
+    }
+
+    /// Short English name for the dictionary's table hint line
+    pub fn hint(self) -> &'static str {
+        match self {
+            DictEnterAction::OpenDetail => "Detail",
+            DictEnterAction::StartReview => "Review",
+            DictEnterAction::AddToLearning => "Learn",
+        }
+    }
+}
+
+/// Dictionary cursor saved when leaving the dictionary (setting "记住词典位置")
+#[derive(Debug, Clone, PartialEq)]
+pub struct DictPosition {
+    pub query: String, // Search box text the position belongs to (empty = default list)
+    pub word_id: i64,
+    pub spelling: String, // Needed to expand a merged variant group
+    pub detail_scroll: u16,
+}
+
+impl DictPosition {
+    /// Tab-separated; Tab cannot be typed into the search box, so the query is safe as the last field
+    pub fn to_setting(&self) -> String {
+        format!("{}\t{}\t{}\t{}", self.word_id, self.detail_scroll, self.spelling, self.query)
+    }
+
+    pub fn from_setting(value: &str) -> Option<Self> {
+        let mut parts = value.splitn(4, '\t');
+        Some(DictPosition {
+            word_id: parts.next()?.parse().ok()?,
+            detail_scroll: parts.next()?.parse().ok()?,
+            spelling: parts.next()?.to_string(),
+            query: parts.next()?.to_string(),
+        })
+    }
+}
+
+/// Where the app goes after the last card of a review session
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SessionComplete {
+    Dashboard, // Dashboard with the "all done" message (default)
+    Summary,   // Deck overview (dashboard `o`)
+    LoopNew,   // Straight into a new-words session
+}
+
+impl SessionComplete {
+    pub const ALL: [SessionComplete; 3] =
+        [SessionComplete::Dashboard, SessionComplete::Summary, SessionComplete::LoopNew];
+
+    pub fn from_setting(value: &str) -> Self {
+        match value {
+            "summary" => SessionComplete::Summary,
+            "loop_new" => SessionComplete::LoopNew,
+            _ => SessionComplete::Dashboard,
+        }
+    }
+
+    pub fn to_setting(self) -> &'static str {
+        match self {
+            SessionComplete::Dashboard => "dashboard",
+            SessionComplete::Summary => "summary",
+            SessionComplete::LoopNew => "loop_new",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SessionComplete::Dashboard => "回到主页（默认）",
+            SessionComplete::Summary => "打开总览",
+            SessionComplete::LoopNew => "接着学新词",
+        }
+    }
+}
+
+/// Key that moves focus into and out of a search box (dictionary and global search).
+/// `i` always opens the box and Esc always leaves it; this is the extra toggle key
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SearchFocusKey {
+    Tab,
+    F2,
+    Unbound, // Tab is left for panel/section switching only
+}
+
+impl SearchFocusKey {
+    pub const ALL: [SearchFocusKey; 3] = [SearchFocusKey::Tab, SearchFocusKey::F2, SearchFocusKey::Unbound];
+
+    pub fn from_setting(value: &str) -> Self {
+        match value {
+            "f2" => SearchFocusKey::F2,
+            "unbound" => SearchFocusKey::Unbound,
+            _ => SearchFocusKey::Tab,
+        }
+    }
+
+    pub fn to_setting(self) -> &'static str {
+        match self {
+            SearchFocusKey::Tab => "tab",
+            SearchFocusKey::F2 => "f2",
+            SearchFocusKey::Unbound => "unbound",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SearchFocusKey::Tab => "Tab（默认）",
+            SearchFocusKey::F2 => "F2",
+            SearchFocusKey::Unbound => "不绑定",
+        }
+    }
+
+    /// Key shown in hints for opening the search box
+    pub fn open_hint(self) -> &'static str {
+        match self {
+            SearchFocusKey::Tab => "Tab",
+            SearchFocusKey::F2 => "F2",
+            SearchFocusKey::Unbound => "i",
+        }
+    }
+
+    /// Key shown in hints for leaving the search box
+    pub fn close_hint(self) -> &'static str {
+        match self {
+            SearchFocusKey::Tab => "Tab",
+            SearchFocusKey::F2 => "F2",
+            SearchFocusKey::Unbound => "Esc",
+        }
+    }
+}
+
+/// A column of the dictionary word table (visibility set in Settings)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DictColumn {
+    Status,
+    Word,
+    Phonetic,
+    Ease,
+    Due,
+    Interval,
+}
+
+impl DictColumn {
+    /// Display order; also the default (every column shown)
+    pub const ALL: [DictColumn; 6] = [
+        DictColumn::Status,
+        DictColumn::Word,
+        DictColumn::Phonetic,
+        DictColumn::Ease,
+        DictColumn::Due,
+        DictColumn::Interval,
+    ];
+
+    fn key(self) -> &'static str {
+        match self {
+            DictColumn::Status => "status",
+            DictColumn::Word => "word",
+            DictColumn::Phonetic => "phonetic",
+            DictColumn::Ease => "ease",
+            DictColumn::Due => "due",
+            DictColumn::Interval => "interval",
+        }
+    }
+
+    /// Parse the stored comma list; the word column is always kept
+    pub fn from_setting(value: &str) -> Vec<DictColumn> {
+        let keys: Vec<&str> = value.split(',').map(str::trim).collect();
+        DictColumn::ALL
+            .into_iter()
+            .filter(|c| *c == DictColumn::Word || keys.contains(&c.key()))
+            .collect()
+    }
+
+    pub fn to_setting(columns: &[DictColumn]) -> String {
+        DictColumn::ALL
+            .into_iter()
+            .filter(|c| columns.contains(c))
+            .map(DictColumn::key)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// Table header
+    pub fn header(self) -> &'static str {
+        match self {
+            DictColumn::Status => "",
+            DictColumn::Word => "Word",
+            DictColumn::Phonetic => "Phonetic",
+            DictColumn::Ease => "Ease",
+            DictColumn::Due => "Due",
+            DictColumn::Interval => "Interval",
+        }
+    }
+
+    /// Settings label
+    pub fn label(self) -> &'static str {
+        match self {
+            DictColumn::Status => "状态",
+            DictColumn::Word => "单词",
+            DictColumn::Phonetic => "音标",
+            DictColumn::Ease => "难度",
+            DictColumn::Due => "到期",
+            DictColumn::Interval => "间隔",
+        }
+    }
+}
+
+/// Optional cue on the question side of a review card (the answer side shows all of them)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CardFrontField {
+    Phonetic, // Only when the spelling is shown (英→中)
+    Pos,      // Part of speech
+    Ratings,  // Collins stars / Oxford 3000
+    Tags,     // Exam tags
+}
+
+impl CardFrontField {
+    /// Display order; also the default (every cue shown)
+    pub const ALL: [CardFrontField; 4] =
+        [CardFrontField::Phonetic, CardFrontField::Pos, CardFrontField::Ratings, CardFrontField::Tags];
+
+    fn key(self) -> &'static str {
+        match self {
+            CardFrontField::Phonetic => "phonetic",
+            CardFrontField::Pos => "pos",
+            CardFrontField::Ratings => "ratings",
+            CardFrontField::Tags => "tags",
+        }
+    }
+
+    /// Parse the stored comma list (empty = spelling only)
+    pub fn from_setting(value: &str) -> Vec<CardFrontField> {
+        let keys: Vec<&str> = value.split(',').map(str::trim).collect();
+        CardFrontField::ALL.into_iter().filter(|f| keys.contains(&f.key())).collect()
+    }
+
+    pub fn to_setting(fields: &[CardFrontField]) -> String {
+        CardFrontField::ALL
+            .into_iter()
+            .filter(|f| fields.contains(f))
+            .map(CardFrontField::key)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// Settings label
+    pub fn label(self) -> &'static str {
+        match self {
+            CardFrontField::Phonetic => "音标",
+            CardFrontField::Pos => "词性",
+            CardFrontField::Ratings => "柯林斯/牛津",
+            CardFrontField::Tags => "考试标签",
+        }
+    }
+}
+
+/// How the dashboard calendar marks past days
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CalendarStyle {
+    Checkin, // Goal met / studied marks
+    Heatmap, // Shaded by number of reviews
+}
+
+impl CalendarStyle {
+    pub fn from_setting(value: &str) -> Self {
+        match value {
+            "heatmap" => CalendarStyle::Heatmap,
+            _ => CalendarStyle::Checkin,
+        }
+    }
+
+    pub fn to_setting(self) -> &'static str {
+        match self {
+            CalendarStyle::Checkin => "checkin",
+            CalendarStyle::Heatmap => "heatmap",
+        }
+    }
+}
+
+/// Time bucket of the statistics activity chart (remembered across sessions)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StatsGranularity {
+    Daily,  // Last 7 days
+    Weekly, // Last 8 weeks
+}
+
+impl StatsGranularity {
+    pub fn from_setting(value: &str) -> Self {
+        match value {
+            "weekly" => StatsGranularity::Weekly,
+            _ => StatsGranularity::Daily,
+        }
+    }
+
+    pub fn to_setting(self) -> &'static str {
+        match self {
+            StatsGranularity::Daily => "daily",
+            StatsGranularity::Weekly => "weekly",
+        }
+    }
+
+    pub fn toggle(self) -> Self {
+        match self {
+            StatsGranularity::Daily => StatsGranularity::Weekly,
+            StatsGranularity::Weekly => StatsGranularity::Daily,
+        }
+    }
+}
+
+/// Milestone badges, stored by `id()` in the achievements table
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Achievement {
+    Words100,         // 100 words started
+    Streak7,          // Daily goal met 7 days in a row
+    Streak30,
+    Streak100,
+    Reviews1000,      // 1000 reviews in total
+    WordbookMastered, // Every word of some wordbook mastered
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 6] = [
+        Achievement::Words100,
+        Achievement::Streak7,
+        Achievement::Streak30,
+        Achievement::Streak100,
+        Achievement::Reviews1000,
+        Achievement::WordbookMastered,
+    ];
+
+    pub fn id(self) -> &'static str {
+        match self {
+            Achievement::Words100 => "words_100",
+            Achievement::Streak7 => "streak_7",
+            Achievement::Streak30 => "streak_30",
+            Achievement::Streak100 => "streak_100",
+            Achievement::Reviews1000 => "reviews_1000",
+            Achievement::WordbookMastered => "wordbook_mastered",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.id() == id)
+    }
+
+    /// i18n key of the badge name
+    pub fn label_key(self) -> &'static str {
+        match self {
+            Achievement::Words100 => "ach.words_100",
+            Achievement::Streak7 => "ach.streak_7",
+            Achievement::Streak30 => "ach.streak_30",
+            Achievement::Streak100 => "ach.streak_100",
+            Achievement::Reviews1000 => "ach.reviews_1000",
+            Achievement::WordbookMastered => "ach.wordbook_mastered",
+        }
+    }
+
+    /// Whether the current progress reaches this milestone
+    pub fn reached(self, progress: &AchievementProgress) -> bool {
+        match self {
+            Achievement::Words100 => progress.words >= 100,
+            Achievement::Streak7 => progress.streak >= 7,
+            Achievement::Streak30 => progress.streak >= 30,
+            Achievement::Streak100 => progress.streak >= 100,
+            Achievement::Reviews1000 => progress.reviews >= 1000,
+            Achievement::WordbookMastered => progress.wordbook_mastered,
+        }
+    }
+}
+
+/// Numbers the achievement thresholds are compared against
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AchievementProgress {
+    pub words: i64,              // Studied words (status != 0), as on the statistics page
+    pub streak: i64,             // Current check-in streak
+    pub reviews: i64,            // review_history plus pruned daily summaries
+    pub wordbook_mastered: bool, // Some wordbook has every word mastered
+}
+
+/// Suggested new-word intake given the upcoming review load
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NewWordBudget {
+    pub budget: i64,       // New words to introduce in the next session
+    pub forecast_due: i64, // Reviews due over the next 7 days
+    pub overdue: i64,      // Due reviews already past the overdue grace period
+    pub backlogged: bool,  // Forecast exceeds what the daily goal can absorb
+}
+
+/// ECDICT build found at startup, compared with the release the app is written against
+/// (`db::ECDICT_VERSION`); column meanings such as `tag` codes can differ between releases
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DictVersion {
+    pub marker: Option<u32>,          // Release from a `metadata` table, when the build has one
+    pub missing_columns: Vec<String>, // Core `stardict` columns absent (optional or unused ones are not counted)
+    pub extra_columns: Vec<String>,   // `stardict` columns this app does not know
+}
+
+impl DictVersion {
+    /// Older / newer than expected; a version marker wins over the inferred schema
+    pub fn age(&self, expected: u32) -> Ordering {
+        match self.marker {
+            Some(version) => version.cmp(&expected),
+            None if !self.missing_columns.is_empty() => Ordering::Less,
+            None if !self.extra_columns.is_empty() => Ordering::Greater,
+            None => Ordering::Equal,
+        }
+    }
+
+    /// One-line explanation for the doctor report and the log
+    pub fn describe(&self, expected: u32) -> String {
+        let mut parts = vec![match self.marker {
+            Some(version) => format!("metadata says release {}", version),
+            None => "no version marker".to_string(),
+        }];
+        if !self.missing_columns.is_empty() {
+            parts.push(format!("missing columns: {}", self.missing_columns.join(", ")));
+        }
+        if !self.extra_columns.is_empty() {
+            parts.push(format!("unknown columns: {}", self.extra_columns.join(", ")));
+        }
+        let verdict = match self.age(expected) {
+            Ordering::Less => "looks older than",
+            Ordering::Equal => "matches",
+            Ordering::Greater => "looks newer than",
+        };
+        format!("{} ecdict-sqlite-{} ({})", verdict, expected, parts.join("; "))
+    }
+}
+
+/// Learned words by maturity, as in Anki: New (never passed), Young, Mature
+/// (interval past `sm2::MASTERY_INTERVAL`, or Mastered)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MaturityCounts {
+    pub new: i64,
+    pub young: i64,
+    pub mature: i64,
+}
+
+/// 总览页（主页 `o`）的全部数字，一次查好
+#[derive(Debug, Clone, Default)]
+pub struct DeckSummary {
+    pub new: i64,
+    pub learning: i64,
+    pub mastered: i64,
+    pub suspended: i64,
+    pub favorites: i64,
+    pub due_today: u64,                   // Overdue included
+    pub due_week: u64,                    // Today plus the next 6 days
+    pub avg_interval: f64,                // Days, over reviewed words
+    pub avg_ease: f64,                    // Over reviewed words
+    pub ease_buckets: Vec<u64>,           // Reviewed words per EASE_BUCKET_BOUNDS bucket
+    pub streak: i64,                      // Days in a row the daily goal was met
+    pub recent_reviews: i64,              // Reviews in the last RETENTION_DAYS
+    pub recent_recalled: i64,             // ... rated 3 or 4
+    pub tag_coverage: Vec<(String, usize, usize)>, // Exam tag, learned words, words in the book
+}
+
+/// 限时优先单词本：到 `until`（含当天）为止学新词和复习都先排这本书的词，过期自动失效
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriorityWordbook {
+    pub tag: String,
+    pub until: NaiveDate,
+}
+
+/// Vocabulary overlap between two wordbooks
+#[derive(Debug, Clone, Default)]
+pub struct WordbookOverlap {
+    pub only_a: usize,
+    pub shared: usize,
+    pub only_b: usize,
+    pub shared_sample: Vec<String>, // Most important shared spellings
+}
+
+/// Hits of the global search, grouped by where the word was seen
+#[derive(Debug, Clone, Default)]
+pub struct GlobalSearchHits {
+    pub learned: Vec<(Word, LearningLog)>,
+    pub history: Vec<(Word, String, u8)>, // Latest review per word: reviewed_at, quality
+    pub favorites: Vec<Word>,
+}
+
+/// Result of importing a plain-text favorites list
+#[derive(Debug, Clone, Default)]
+pub struct FavoritesImport {
+    pub added: usize,           // Words newly favorited
+    pub already: usize,         // Words that were favorites already
+    pub not_found: Vec<String>, // Lines with no ECDICT entry
+}
+
+/// 用户数据备份（`user-data export`）：只含收藏、暂停和设置，单词按拼写记录，
+/// 不含 learning_log / review_history，适合放进 dotfiles 在多台机器间同步
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UserDataBackup {
+    pub version: u32,
+    #[serde(default)]
+    pub favorites: Vec<UserDataWord>,
+    #[serde(default)]
+    pub suspended: Vec<UserDataWord>,
+    #[serde(default)]
+    pub settings: std::collections::BTreeMap<String, String>, // Sorted so the file diffs cleanly
+}
+
+/// A word in the user data backup, with the time it was favorited/suspended (RFC 3339)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserDataWord {
+    pub word: String,
+    pub at: String,
+}
+
+/// Result of importing a user data backup
+#[derive(Debug, Clone, Default)]
+pub struct UserDataImport {
+    pub favorites_added: usize,
+    pub suspended_added: usize,
+    pub settings_applied: usize,
+    pub not_found: Vec<String>, // Spellings with no ECDICT entry (each listed once)
+}
+
+/// Lifecycle dates of a learned word (None = not recorded / not mastered right now)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WordDates {
+    pub added_at: Option<DateTime<Utc>>,    // First init_learning_log
+    pub mastered_at: Option<DateTime<Utc>>, // Last time a review moved it into Mastered
+}
+
+/// Result of merging another install's progress database
+#[derive(Debug, Clone, Default)]
+pub struct MergeSummary {
+    pub merged: usize,        // learning_log rows copied or replaced from the other DB
+    pub skipped: usize,       // rows where the local log was kept
+    pub conflicted: usize,    // words with differing progress on both sides
+    pub history_added: usize, // review_history rows appended
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(marker: Option<u32>, missing: &[&str], extra: &[&str]) -> DictVersion {
+        DictVersion {
+            marker,
+            missing_columns: missing.iter().map(|c| c.to_string()).collect(),
+            extra_columns: extra.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn dict_version_marker_wins_over_the_schema() {
+        assert_eq!(version(Some(28), &[], &[]).age(28), Ordering::Equal);
+        assert_eq!(version(Some(27), &[], &["extra"]).age(28), Ordering::Less);
+        assert_eq!(version(Some(29), &["pos"], &[]).age(28), Ordering::Greater);
+    }
+
+    #[test]
+    fn dict_version_without_marker_is_inferred_from_columns() {
+        assert_eq!(version(None, &[], &[]).age(28), Ordering::Equal);
+        assert_eq!(version(None, &["pos"], &[]).age(28), Ordering::Less);
+        assert_eq!(version(None, &[], &["ipa"]).age(28), Ordering::Greater);
+        // Missing core columns outweigh unknown ones
+        assert_eq!(version(None, &["pos"], &["ipa"]).age(28), Ordering::Less);
+    }
+}
+
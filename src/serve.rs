@@ -0,0 +1,69 @@
+use crate::db::Database;
+use crate::logger;
+use anyhow::{Context, Result};
+use serde_json::json;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::time::Duration;
+
+/// Port used by `--serve` unless `--serve-port` is given
+pub const DEFAULT_SERVE_PORT: u16 = 7878;
+
+/// A client that connects but never sends its request line is dropped after this
+const READ_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// `--serve`：在 127.0.0.1 上开一个极简 HTTP 接口，`GET /stats` 返回 JSON 统计，
+/// 给 tmux / polybar 之类的状态栏用
+///
+/// Binding happens here so a taken port fails before the TUI starts; requests are
+/// answered one at a time on a background thread with its own read-only connection.
+pub fn spawn(db: &Database, port: u16) -> Result<()> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+        .with_context(|| format!("cannot listen on 127.0.0.1:{}", port))?;
+    let db = db.read_only_clone()?;
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let result = stream.map_err(anyhow::Error::from).and_then(|stream| respond(&db, stream));
+            if let Err(e) = result {
+                logger::warn(&format!("--serve request failed: {:#}", e));
+            }
+        }
+    });
+    Ok(())
+}
+
+fn respond(db: &Database, mut stream: TcpStream) -> Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+
+    // "GET /stats HTTP/1.1"; headers and body are ignored
+    let mut parts = request_line.split_whitespace();
+    let (status, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/" | "/stats")) => ("200 OK", stats_json(db)?),
+        (Some("GET"), _) => ("404 Not Found", json!({ "error": "not found; try /stats" })),
+        _ => ("405 Method Not Allowed", json!({ "error": "only GET is supported" })),
+    };
+    let body = body.to_string();
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )?;
+    Ok(())
+}
+
+/// Same numbers as the glance line under the tabs
+fn stats_json(db: &Database) -> Result<serde_json::Value> {
+    let (total, mastered, due) = db.get_stats()?;
+    Ok(json!({
+        "due": due,
+        "today_completed": db.get_today_completed_count()?,
+        "daily_goal": db.get_daily_goal()?,
+        "streak": db.get_checkin_streak()?,
+        "total": total,
+        "mastered": mastered,
+    }))
+}
@@ -1,5 +1,8 @@
 use chrono::{Duration, Utc};
-use crate::models::{LearningLog, LearningStatus};
+use crate::models::{LearningLog, LearningStatus, MasteredReview, MasteryRule};
+
+/// A word whose interval grows past this many days is mastered (and counts as mature)
+pub const MASTERY_INTERVAL: i32 = 21;
 
 /// SuperMemo-2 Algorithm Implementation
 ///
@@ -42,22 +45,92 @@ pub fn update_memory_state(
     (next_repetition, next_interval, next_ef)
 }
 
-pub fn process_review(log: &mut LearningLog, quality: u8) {
-    let (n, i, ef) = update_memory_state(log.repetition, log.e_factor, quality);
-    
+/// `mastery`: extra conditions (Settings) a word must meet besides the interval to count as mastered
+pub fn process_review(log: &mut LearningLog, quality: u8, mastered_review: MasteredReview, mastery: MasteryRule) {
+    let (n, mut i, ef) = update_memory_state(log.repetition, log.e_factor, quality);
     log.repetition = n;
-    log.interval = i;
-    log.e_factor = ef;
-    log.next_review = Utc::now() + Duration::days(i as i64);
-    
+    log.consecutive_good = if quality >= 3 { log.consecutive_good + 1 } else { 0 };
+
     if quality >= 3 {
         // Simple logic: if interval > 21 days, consider mastered for now, or just keep as Learning
-        if i > 21 {
+        if i > MASTERY_INTERVAL && mastery.is_met(log) {
             log.status = LearningStatus::Mastered;
+            // Mastered words can be scheduled less often (see Settings)
+            if let MasteredReview::Reduced(factor) = mastered_review {
+                i = (i as f64 * factor).round() as i32;
+            }
         } else {
             log.status = LearningStatus::Learning;
         }
     } else {
         log.status = LearningStatus::Learning; // Reset to learning if forgot
     }
+
+    log.interval = i;
+    log.e_factor = ef;
+    log.next_review = Utc::now() + Duration::days(i as i64);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A log whose next good review schedules `round(repetition * e_factor)` days
+    fn log(repetition: i32, e_factor: f64, consecutive_good: i32) -> LearningLog {
+        LearningLog {
+            word_id: 1,
+            repetition,
+            interval: 0,
+            e_factor,
+            next_review: Utc::now(),
+            status: LearningStatus::Learning,
+            consecutive_good,
+        }
+    }
+
+    fn review(mut log: LearningLog, quality: u8, mastery: MasteryRule) -> LearningLog {
+        process_review(&mut log, quality, MasteredReview::Normal, mastery);
+        log
+    }
+
+    #[test]
+    fn interval_only_rule_masters_past_the_interval() {
+        let rule = MasteryRule::default();
+        let at_limit = review(log(10, 2.1, 0), 4, rule);
+        assert_eq!(at_limit.interval, MASTERY_INTERVAL);
+        assert_eq!(at_limit.status, LearningStatus::Learning);
+
+        let past_limit = review(log(10, 2.2, 0), 4, rule);
+        assert_eq!(past_limit.interval, MASTERY_INTERVAL + 1);
+        assert_eq!(past_limit.status, LearningStatus::Mastered);
+    }
+
+    #[test]
+    fn streak_only_rule_needs_the_full_streak_and_the_interval() {
+        let rule = MasteryRule { min_repetition: 0, consecutive_good: 5 };
+        // The review being processed counts towards the streak
+        assert_eq!(review(log(10, 2.2, 3), 4, rule).status, LearningStatus::Learning);
+        assert_eq!(review(log(10, 2.2, 4), 4, rule).status, LearningStatus::Mastered);
+        // A long streak alone is not enough below the interval
+        assert_eq!(review(log(10, 2.1, 20), 4, rule).status, LearningStatus::Learning);
+    }
+
+    #[test]
+    fn combined_rule_needs_every_condition() {
+        let rule = MasteryRule { min_repetition: 12, consecutive_good: 5 };
+        // 11 × 2.0 = 22 days; repetition becomes 12
+        assert_eq!(review(log(11, 2.0, 4), 4, rule).status, LearningStatus::Mastered);
+        assert_eq!(review(log(11, 2.0, 3), 4, rule).status, LearningStatus::Learning);
+        // 10 × 2.2 = 22 days but repetition only reaches 11
+        assert_eq!(review(log(10, 2.2, 4), 4, rule).status, LearningStatus::Learning);
+    }
+
+    #[test]
+    fn lapse_resets_the_streak_and_never_masters() {
+        let lapsed = review(log(11, 2.5, 9), 1, MasteryRule::default());
+        assert_eq!(lapsed.consecutive_good, 0);
+        assert_eq!(lapsed.repetition, 0);
+        assert_eq!(lapsed.interval, 1);
+        assert_eq!(lapsed.status, LearningStatus::Learning);
+    }
 }
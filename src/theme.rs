@@ -1,7 +1,56 @@
 use ratatui::{
     style::{Color, Modifier, Style},
+    text::Line,
     widgets::{Block, BorderType, Borders},
 };
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+/// 突出显示（设置“突出显示单词和释义”）：与界面语言一样是全局开关，渲染时直接读取
+static EMPHASIS: AtomicBool = AtomicBool::new(false);
+
+/// 边框样式（设置“边框样式”），同样在渲染时直接读取
+static BORDER_DENSITY: AtomicU8 = AtomicU8::new(BorderDensity::Thick as u8);
+
+/// Border drawn by the theme's block constructors
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BorderDensity {
+    Thick,
+    Plain,
+    Rounded,
+    None, // No frame; titles stay on their own row
+}
+
+impl BorderDensity {
+    pub const ALL: [BorderDensity; 4] = [BorderDensity::Thick, BorderDensity::Plain, BorderDensity::Rounded, BorderDensity::None];
+
+    /// Parse the stored setting value, defaulting to Thick
+    pub fn from_setting(value: &str) -> Self {
+        match value {
+            "plain" => BorderDensity::Plain,
+            "rounded" => BorderDensity::Rounded,
+            "none" => BorderDensity::None,
+            _ => BorderDensity::Thick,
+        }
+    }
+
+    pub fn to_setting(self) -> &'static str {
+        match self {
+            BorderDensity::Thick => "thick",
+            BorderDensity::Plain => "plain",
+            BorderDensity::Rounded => "rounded",
+            BorderDensity::None => "none",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BorderDensity::Thick => "粗线",
+            BorderDensity::Plain => "细线",
+            BorderDensity::Rounded => "圆角",
+            BorderDensity::None => "无边框",
+        }
+    }
+}
 
 /// 应用主题配色方案
 /// 
@@ -28,8 +77,8 @@ use ratatui::{
 /// 
 /// 修改 `BACKGROUND` 常量后重新编译即可生效。
 pub struct Theme;
-#[allow(unused)]
 
+#[allow(unused)]
 impl Theme {
     // === 主要颜色 ===
     /// 主色调 - 蓝色（用于标题、重点信息）
@@ -72,19 +121,56 @@ impl Theme {
     /// Quality 4 - 很好（完全记得）- 亮绿色
     pub const QUALITY_4: Color = Color::Rgb(50, 205, 50);
 
+    /// 高对比前景色 - 纯白（突出显示时的单词和中文释义）
+    pub const HIGH_CONTRAST: Color = Color::White;
+
+    pub fn set_emphasis(enabled: bool) {
+        EMPHASIS.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn emphasis() -> bool {
+        EMPHASIS.load(Ordering::Relaxed)
+    }
+
+    pub fn set_border_density(density: BorderDensity) {
+        BORDER_DENSITY.store(density as u8, Ordering::Relaxed);
+    }
+
+    pub fn border_density() -> BorderDensity {
+        let value = BORDER_DENSITY.load(Ordering::Relaxed);
+        BorderDensity::ALL.into_iter().find(|d| *d as u8 == value).unwrap_or(BorderDensity::Thick)
+    }
+
+    /// 按边框样式设置的空白边框，其他 block 构造函数在此基础上加颜色和标题
+    pub fn bordered() -> Block<'static> {
+        let border_type = match Self::border_density() {
+            BorderDensity::None => return Block::default().borders(Borders::NONE),
+            BorderDensity::Thick => BorderType::Thick,
+            BorderDensity::Plain => BorderType::Plain,
+            BorderDensity::Rounded => BorderType::Rounded,
+        };
+        Block::default().borders(Borders::ALL).border_type(border_type)
+    }
+
+    /// 难度系数颜色：1.3（易忘）红色 → 2.5+（稳固）绿色
+    pub fn ease_color(e_factor: f64) -> Color {
+        let t = ((e_factor - 1.3) / (2.5 - 1.3)).clamp(0.0, 1.0);
+        let red = 220.0 + (50.0 - 220.0) * t;
+        let green = 50.0 + (205.0 - 50.0) * t;
+        Color::Rgb(red as u8, green as u8, 50)
+    }
+
     // === 边框样式 ===
     
-    /// 标准边框样式（白色粗边框 + 背景色）
+    /// 标准边框样式（白色边框 + 背景色，粗细按边框样式设置）
     pub fn block_default() -> Block<'static> {
-        Block::default()
-            .borders(Borders::ALL)
-            .border_type(BorderType::Thick)
+        Self::bordered()
             .border_style(Style::default().fg(Self::FOREGROUND))
             .style(Style::default().bg(Self::BACKGROUND))
     }
 
     /// 带标题的标准边框（白色边框，蓝底蓝色文字标题）
-    pub fn block_with_title(title: &'static str) -> Block<'static> {
+    pub fn block_with_title(title: impl Into<Line<'static>>) -> Block<'static> {
         Self::block_default()
             .title(title)
             .title_style(Style::default()
@@ -93,11 +179,9 @@ impl Theme {
                 .add_modifier(Modifier::BOLD))
     }
 
-    /// 强调边框样式（白色粗边框 + 背景色）
+    /// 强调边框样式（白色边框 + 背景色）
     pub fn block_accent() -> Block<'static> {
-        Block::default()
-            .borders(Borders::ALL)
-            .border_type(BorderType::Thick)
+        Self::bordered()
             .border_style(Style::default().fg(Self::FOREGROUND))
             .style(Style::default().bg(Self::BACKGROUND))
     }
@@ -112,11 +196,9 @@ impl Theme {
                 .add_modifier(Modifier::BOLD))
     }
 
-    /// 成功边框样式（白色粗边框 + 背景色）
+    /// 成功边框样式（白色边框 + 背景色）
     pub fn block_success() -> Block<'static> {
-        Block::default()
-            .borders(Borders::ALL)
-            .border_type(BorderType::Thick)
+        Self::bordered()
             .border_style(Style::default().fg(Self::FOREGROUND))
             .style(Style::default().bg(Self::BACKGROUND))
     }
@@ -131,11 +213,9 @@ impl Theme {
                 .add_modifier(Modifier::BOLD))
     }
 
-    /// 警告边框样式（白色粗边框 + 背景色）
+    /// 警告边框样式（白色边框 + 背景色）
     pub fn block_warning() -> Block<'static> {
-        Block::default()
-            .borders(Borders::ALL)
-            .border_type(BorderType::Thick)
+        Self::bordered()
             .border_style(Style::default().fg(Self::FOREGROUND))
             .style(Style::default().bg(Self::BACKGROUND))
     }
@@ -203,6 +283,29 @@ impl Theme {
         Style::default().fg(Self::SECONDARY)
     }
 
+    /// 单词拼写（蓝色粗体；突出显示时白色粗体）
+    pub fn text_headword() -> Style {
+        if Self::emphasis() {
+            Style::default().fg(Self::HIGH_CONTRAST).add_modifier(Modifier::BOLD)
+        } else {
+            Self::text_title()
+        }
+    }
+
+    /// 释义正文（普通白色；突出显示时纯白粗体）
+    pub fn text_content() -> Style {
+        if Self::emphasis() {
+            Style::default().fg(Self::HIGH_CONTRAST).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        }
+    }
+
+    /// 小节之间的空行数（突出显示时加倍）
+    pub fn section_gap() -> usize {
+        if Self::emphasis() { 2 } else { 1 }
+    }
+
     /// 高亮文本样式（蓝色 + 反转）
     pub fn text_highlight() -> Style {
         Style::default()
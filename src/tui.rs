@@ -1,4 +1,5 @@
 use std::io;
+use std::panic;
 use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
@@ -18,3 +19,86 @@ pub fn restore() -> io::Result<()> {
     disable_raw_mode()?;
     Ok(())
 }
+
+/// Restore the terminal before the default panic message is printed.
+///
+/// Without this a panic mid-render leaves the shell in raw mode on the
+/// alternate screen, and the message itself is lost. Call before `init`.
+pub fn install_panic_hook() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let _ = restore();
+        crate::logger::error(&format!("panic: {}", info));
+        default_hook(info);
+    }));
+}
+
+/// 离开作用域时恢复终端（包括 `?` 提前返回的错误路径）
+///
+/// The restore step is a closure so tests can count calls without a terminal.
+pub struct TerminalGuard<F: FnMut() -> io::Result<()> = fn() -> io::Result<()>> {
+    restore: F,
+    restored: bool,
+}
+
+impl Default for TerminalGuard {
+    fn default() -> Self {
+        Self::with_restore(restore)
+    }
+}
+
+impl<F: FnMut() -> io::Result<()>> TerminalGuard<F> {
+    pub fn with_restore(restore: F) -> Self {
+        Self { restore, restored: false }
+    }
+
+    /// Restore now and report the error; dropping afterwards is a no-op
+    pub fn restore(&mut self) -> io::Result<()> {
+        debug_assert!(!self.restored, "terminal restored twice");
+        self.restored = true;
+        (self.restore)()
+    }
+}
+
+impl<F: FnMut() -> io::Result<()>> Drop for TerminalGuard<F> {
+    fn drop(&mut self) {
+        if !self.restored {
+            let _ = (self.restore)();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    fn counting(calls: &Cell<usize>) -> impl FnMut() -> io::Result<()> + '_ {
+        move || {
+            calls.set(calls.get() + 1);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn drop_restores_after_early_error_return() {
+        let calls = Cell::new(0);
+        let run = || -> io::Result<()> {
+            let _guard = TerminalGuard::with_restore(counting(&calls));
+            Err(io::Error::other("injected"))?;
+            unreachable!();
+        };
+        assert!(run().is_err());
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn explicit_restore_is_not_repeated_on_drop() {
+        let calls = Cell::new(0);
+        {
+            let mut guard = TerminalGuard::with_restore(counting(&calls));
+            guard.restore().unwrap();
+        }
+        assert_eq!(calls.get(), 1);
+    }
+}